@@ -37,8 +37,13 @@ impl From<horo::Error> for Error {
         match value {
             horo::Error::Function(s) => Error::InternalServerError(s),
             horo::Error::InvalidProfectionDateTime(s) => Error::BadRequest(s),
+            horo::Error::InvalidProcessDateTime(s) => Error::BadRequest(s),
             horo::Error::InvalidDateTime(s) => Error::BadRequest(s),
             horo::Error::InvalidZone(s) => Error::BadRequest(s),
+            horo::Error::InvalidLot(s) => Error::BadRequest(s),
+            horo::Error::InvalidFixedStar(s) => Error::BadRequest(s),
+            horo::Error::InvalidHouse(s) => Error::BadRequest(s),
+            horo::Error::InvalidHarmonic(s) => Error::BadRequest(s),
         }
     }
 }
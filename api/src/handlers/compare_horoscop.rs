@@ -1,7 +1,11 @@
-use crate::{error::Error, request::HoroscopeComparisonRequst, state::AppState};
+use crate::{
+    error::Error,
+    request::{HoroscopeComparisonRequst, lots_config},
+    state::AppState,
+};
 use actix_web::{post, web, HttpResponse, Responder};
 use geo_position::GeoPosition;
-use horo::{HoroscopeComparison, PlanetConfig};
+use horo::{HoroscopeComparison, HoroscopeConfig, PlanetConfig};
 use horo_date_time::horo_date_time;
 
 /// 比较盘
@@ -47,13 +51,25 @@ pub async fn compare(
     let original_geo = GeoPosition::new(r.original_geo.long, r.original_geo.lat)?;
     let comparison_geo = GeoPosition::new(r.comparison_geo.long, r.comparison_geo.lat)?;
 
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        position_mode: r.position_mode,
+        conditions: r.conditions,
+        aspects: r.aspects,
+        parallels: r.parallels,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
     let pan = HoroscopeComparison::new(
         original_date,
         comparison_date,
         original_geo,
         comparison_geo,
         r.house,
-        &PlanetConfig::default_all_configs(),
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
         &app_state.ephe_path,
     )?;
 
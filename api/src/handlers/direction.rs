@@ -1,7 +1,11 @@
-use crate::{error::Error, request::DirectionRequest, state::AppState};
+use crate::{
+    error::Error,
+    request::{DirectionRequest, lots_config},
+    state::AppState,
+};
 use actix_web::{HttpResponse, Responder, post, web};
 use geo_position::GeoPosition;
-use horo::{PlanetConfig, direction_process};
+use horo::{HoroscopeConfig, PlanetConfig, direction_process};
 use horo_date_time::horo_date_time;
 
 #[cfg(feature = "swagger")]
@@ -38,10 +42,19 @@ pub async fn directions(
 
     let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
 
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
     let directions = direction_process(
         native_date,
         geo,
         &PlanetConfig::default_all_configs(),
+        &config,
+        r.key,
         &app_state.ephe_path,
     )?;
 
@@ -0,0 +1,54 @@
+use crate::{error::Error, request::HarmonicRequest, state::AppState};
+use actix_web::{HttpResponse, Responder, post, web};
+use geo_position::GeoPosition;
+use horo::{HarmonicChart, Horoscope, HoroscopeConfig, PlanetConfig};
+use horo_date_time::horo_date_time;
+
+/// 谐波盘
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="谐波盘",
+    context_path="/api/horo",
+    request_body=HarmonicRequest,
+    responses(
+        (status = 201, description = "返回谐波盘", body = HarmonicChart),
+    ),
+)
+)]
+#[post("/harmonic")]
+pub async fn harmonic(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<HarmonicRequest>,
+) -> Result<impl Responder, Error> {
+    let r = r.into_inner();
+
+    let t = horo_date_time(
+        r.date.year,
+        r.date.month,
+        r.date.day,
+        r.date.hour,
+        r.date.minute,
+        r.date.second,
+        r.date.tz,
+        r.date.st,
+    )?;
+    let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
+
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        ..Default::default()
+    };
+
+    let horoscope = Horoscope::new(
+        t,
+        geo,
+        r.house,
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
+        &app_state.ephe_path,
+    )?;
+    let chart = HarmonicChart::new(&horoscope, r.harmonic, &r.aspects)?;
+
+    Ok(HttpResponse::Created().json(chart))
+}
@@ -0,0 +1,56 @@
+use crate::{error::Error, request::HoraryRequest, state::AppState};
+use actix_web::{HttpResponse, Responder, post, web};
+use geo_position::GeoPosition;
+use horo::{Horary, Horoscope, HoroscopeConfig, PlanetConfig};
+use horo_date_time::horo_date_time;
+
+/// 卜卦盘
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="卜卦盘",
+    context_path="/api",
+    request_body=HoraryRequest,
+    responses(
+        (status = 201, description = "返回卜卦盘、判断前的各项告诫及问卜者、所问之事的征象星", body = Horary),
+    ),
+)
+)]
+#[post("/horary")]
+pub async fn horary(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<HoraryRequest>,
+) -> Result<impl Responder, Error> {
+    let r = r.into_inner();
+
+    let t = horo_date_time(
+        r.date.year,
+        r.date.month,
+        r.date.day,
+        r.date.hour,
+        r.date.minute,
+        r.date.second,
+        r.date.tz,
+        r.date.st,
+    )?;
+    let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
+
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        conditions: r.conditions,
+        aspects: r.aspects,
+        ..Default::default()
+    };
+
+    let horoscope = Horoscope::new(
+        t,
+        geo,
+        r.house,
+        &PlanetConfig::default_configs(false),
+        &config,
+        &app_state.ephe_path,
+    )?;
+    let horary = Horary::new(horoscope, r.quesited_house)?;
+
+    Ok(HttpResponse::Created().json(horary))
+}
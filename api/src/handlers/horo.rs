@@ -1,7 +1,11 @@
-use crate::{error::Error, request::HoroNativeRenReust, state::AppState};
+use crate::{
+    error::Error,
+    request::{HoroNativeRenReust, lots_config},
+    state::AppState,
+};
 use actix_web::{post, web, HttpResponse, Responder};
 use geo_position::GeoPosition;
-use horo::{Horoscope, PlanetConfig};
+use horo::{Horoscope, HoroscopeConfig, PlanetConfig};
 use horo_date_time::horo_date_time;
 
 /// 本命星盘
@@ -45,11 +49,25 @@ pub async fn horo_native(
     )?;
     let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
 
+    let config = HoroscopeConfig {
+        dignity_scoring: r.dignity_scoring,
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        position_mode: r.position_mode,
+        conditions: r.conditions,
+        aspects: r.aspects,
+        parallels: r.parallels,
+        lots: lots_config(r.lots, r.custom_lots),
+        fixed_stars: r.fixed_stars,
+        midpoints: r.midpoints,
+    };
+
     let pan = Horoscope::new(
         t,
         geo,
         r.house,
-        &PlanetConfig::default_all_configs(),
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
         &app_state.ephe_path,
     )?;
 
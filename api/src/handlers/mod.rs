@@ -8,4 +8,10 @@ pub mod firdaria;
 pub mod qizheng;
 pub mod direction;
 pub mod quadrant_process;
+pub mod planetary_hours;
+pub mod horary;
+pub mod harmonic;
 
+pub mod transit;
+pub mod progression;
+pub mod solar_arc;
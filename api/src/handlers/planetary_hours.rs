@@ -0,0 +1,68 @@
+use crate::{error::Error, request::PlanetaryHoursRequest, state::AppState};
+use actix_web::{HttpResponse, Responder, post, web};
+use geo_position::GeoPosition;
+use horo::planetary_hours;
+use horo_date_time::horo_date_time;
+
+#[cfg(feature = "swagger")]
+use horo::PlanetaryHours;
+
+// 一次最多计算的天数
+const MAX_DAYS: f64 = 31.0;
+
+/// 行星时
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="行星时",
+    context_path="/api/horo",
+    request_body=PlanetaryHoursRequest,
+    responses(
+        (status = 201, description = "返回每个行星日的24个行星时", body = Vec<PlanetaryHours>),
+    ),
+)
+)]
+#[post("/planetary_hours")]
+pub async fn planetary_hours_handler(
+    r: actix_web_validator::Json<PlanetaryHoursRequest>,
+    app_state: web::Data<AppState>,
+) -> Result<impl Responder, Error> {
+    let r = r.into_inner();
+
+    let start_date = horo_date_time(
+        r.start_date.year,
+        r.start_date.month,
+        r.start_date.day,
+        r.start_date.hour,
+        r.start_date.minute,
+        r.start_date.second,
+        r.start_date.tz,
+        r.start_date.st,
+    )?;
+
+    let end_date = if let Some(end_date) = r.end_date {
+        horo_date_time(
+            end_date.year,
+            end_date.month,
+            end_date.day,
+            end_date.hour,
+            end_date.minute,
+            end_date.second,
+            end_date.tz,
+            end_date.st,
+        )?
+    } else {
+        start_date
+    };
+
+    if end_date.jd_utc - start_date.jd_utc > MAX_DAYS {
+        return Err(Error::BadRequest(format!(
+            "结束时间与开始时间最多相差{MAX_DAYS}天"
+        )));
+    }
+
+    let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
+
+    let hours = planetary_hours(start_date, end_date, geo, &app_state.ephe_path)?;
+
+    Ok(HttpResponse::Created().json(hours))
+}
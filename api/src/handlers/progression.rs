@@ -0,0 +1,230 @@
+use crate::{
+    error::Error,
+    request::{ProgressedAspectsRequest, ProgressionRequest, lots_config},
+    state::AppState,
+};
+use actix_web::{HttpResponse, Responder, post, web};
+use geo_position::GeoPosition;
+use horo::{
+    Horoscope, HoroscopeConfig, PlanetConfig, Progression, ProgressionType, progressed_aspects,
+    progression,
+};
+use horo_date_time::horo_date_time;
+
+#[cfg(feature = "swagger")]
+use horo::Transit;
+
+// 查询推运相位时，结束时间距出生时间最多的天数，约120年
+const MAX_DAYS: f64 = 43830.0;
+
+/// 次限推运
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="推运",
+    context_path="/api/process",
+    request_body=ProgressionRequest,
+    responses(
+        (status = 201, description = "返回次限推运盘及推运盘与本命盘的相位", body = Progression),
+    ),
+)
+)]
+#[post("/progression/secondary")]
+pub async fn secondary_progression_handler(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<ProgressionRequest>,
+) -> Result<impl Responder, Error> {
+    let progression = progression_of(
+        ProgressionType::Secondary,
+        r.into_inner(),
+        &app_state.ephe_path,
+    )?;
+
+    Ok(HttpResponse::Created().json(progression))
+}
+
+/// 三限推运
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="推运",
+    context_path="/api/process",
+    request_body=ProgressionRequest,
+    responses(
+        (status = 201, description = "返回三限推运盘及推运盘与本命盘的相位", body = Progression),
+    ),
+)
+)]
+#[post("/progression/tertiary")]
+pub async fn tertiary_progression_handler(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<ProgressionRequest>,
+) -> Result<impl Responder, Error> {
+    let progression = progression_of(
+        ProgressionType::Tertiary,
+        r.into_inner(),
+        &app_state.ephe_path,
+    )?;
+
+    Ok(HttpResponse::Created().json(progression))
+}
+
+/// 小限推运
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="推运",
+    context_path="/api/process",
+    request_body=ProgressionRequest,
+    responses(
+        (status = 201, description = "返回小限推运盘及推运盘与本命盘的相位", body = Progression),
+    ),
+)
+)]
+#[post("/progression/minor")]
+pub async fn minor_progression_handler(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<ProgressionRequest>,
+) -> Result<impl Responder, Error> {
+    let progression = progression_of(ProgressionType::Minor, r.into_inner(), &app_state.ephe_path)?;
+
+    Ok(HttpResponse::Created().json(progression))
+}
+
+/// 推运行星与本命盘形成相位的时间
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="推运",
+    context_path="/api/process",
+    request_body=ProgressedAspectsRequest,
+    responses(
+        (status = 201, description = "返回查询期间推运行星与本命盘形成的相位", body = Vec<Transit>),
+    ),
+)
+)]
+#[post("/progression/aspects")]
+pub async fn progressed_aspects_handler(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<ProgressedAspectsRequest>,
+) -> Result<impl Responder, Error> {
+    let r = r.into_inner();
+
+    let native_date = horo_date_time(
+        r.native_date.year,
+        r.native_date.month,
+        r.native_date.day,
+        r.native_date.hour,
+        r.native_date.minute,
+        r.native_date.second,
+        r.native_date.tz,
+        r.native_date.st,
+    )?;
+
+    let start_date = horo_date_time(
+        r.start_date.year,
+        r.start_date.month,
+        r.start_date.day,
+        r.start_date.hour,
+        r.start_date.minute,
+        r.start_date.second,
+        r.start_date.tz,
+        r.start_date.st,
+    )?;
+
+    let end_date = horo_date_time(
+        r.end_date.year,
+        r.end_date.month,
+        r.end_date.day,
+        r.end_date.hour,
+        r.end_date.minute,
+        r.end_date.second,
+        r.end_date.tz,
+        r.end_date.st,
+    )?;
+
+    if end_date.jd_utc - native_date.jd_utc > MAX_DAYS {
+        return Err(Error::BadRequest(format!(
+            "结束时间与出生时间最多相差{MAX_DAYS}天"
+        )));
+    }
+
+    let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
+
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
+    let natal = Horoscope::new(
+        native_date,
+        geo,
+        r.house,
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
+        &app_state.ephe_path,
+    )?;
+
+    let aspects = progressed_aspects(
+        &natal,
+        start_date,
+        end_date,
+        r.progression_type,
+        &r.aspects,
+        &app_state.ephe_path,
+    )?;
+
+    Ok(HttpResponse::Created().json(aspects))
+}
+
+// 次限、三限、小限推运的请求相同，只有推运的方法不同
+fn progression_of(
+    progression_type: ProgressionType,
+    r: ProgressionRequest,
+    ephe_path: &str,
+) -> Result<Progression, Error> {
+    let native_date = horo_date_time(
+        r.native_date.year,
+        r.native_date.month,
+        r.native_date.day,
+        r.native_date.hour,
+        r.native_date.minute,
+        r.native_date.second,
+        r.native_date.tz,
+        r.native_date.st,
+    )?;
+
+    let process_date = horo_date_time(
+        r.process_date.year,
+        r.process_date.month,
+        r.process_date.day,
+        r.process_date.hour,
+        r.process_date.minute,
+        r.process_date.second,
+        r.process_date.tz,
+        r.process_date.st,
+    )?;
+
+    let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
+
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        aspects: r.aspects,
+        parallels: r.parallels,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
+    let progression = progression(
+        progression_type,
+        native_date,
+        process_date,
+        geo,
+        r.house,
+        &PlanetConfig::default_configs(r.outer_planets),
+        r.mc_progression,
+        &config,
+        ephe_path,
+    )?;
+
+    Ok(progression)
+}
@@ -52,6 +52,7 @@ pub async fn qizheng_horo(
         geo,
         &PlanetConfig::default_all_configs(),
         &DistanceStarConfig::default_all_configs(),
+        r.node_type,
         &app_state.ephe_path,
     )?;
 
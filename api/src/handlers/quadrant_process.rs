@@ -1,7 +1,11 @@
-use crate::{error::Error, request::{QuadrantProcessRequest, QuadrantProcessLongitudeRequest}, state::AppState};
+use crate::{
+    error::Error,
+    request::{QuadrantProcessLongitudeRequest, QuadrantProcessRequest, lots_config},
+    state::AppState,
+};
 use actix_web::{HttpResponse, Responder, post, web};
 use geo_position::GeoPosition;
-use horo::{PlanetConfig, quadrant_process, quadrant_process_longitude};
+use horo::{HoroscopeConfig, PlanetConfig, quadrant_process, quadrant_process_longitude};
 use horo_date_time::horo_date_time;
 
 #[cfg(feature = "swagger")]
@@ -38,11 +42,19 @@ pub async fn quadrant_process_handler(
 
     let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
 
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
     let directions = quadrant_process(
         native_date,
         geo,
         r.house,
-        &PlanetConfig::default_all_configs(),
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
         &app_state.ephe_path,
     )?;
 
@@ -91,12 +103,20 @@ pub async fn quadrant_process_longitude_handler(
 
     let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
 
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
     let longitude = quadrant_process_longitude(
         native_date,
         process_date,
         geo,
         r.house,
-        &PlanetConfig::default_all_configs(),
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
         &app_state.ephe_path,
     )?;
 
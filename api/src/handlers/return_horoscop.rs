@@ -1,7 +1,11 @@
-use crate::{error::Error, request::ReturnRequest, state::AppState};
+use crate::{
+    error::Error,
+    request::{ReturnRequest, lots_config},
+    state::AppState,
+};
 use actix_web::{post, web, HttpResponse, Responder};
 use geo_position::GeoPosition;
-use horo::{lunar_return, solar_return, PlanetConfig};
+use horo::{lunar_return, solar_return, HoroscopeConfig, PlanetConfig};
 use horo_date_time::horo_date_time;
 
 #[cfg(feature = "swagger")]
@@ -50,12 +54,22 @@ pub async fn solar_return_horo(
 
     let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
 
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        conditions: r.conditions,
+        aspects: r.aspects,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
     let pan = solar_return(
         native_date,
         process_date,
         geo,
         r.house,
-        &PlanetConfig::default_all_configs(),
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
         &app_state.ephe_path,
     )?;
 
@@ -105,12 +119,22 @@ pub async fn lunar_return_horo(
 
     let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
 
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        conditions: r.conditions,
+        aspects: r.aspects,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
     let pan = lunar_return(
         native_date,
         process_date,
         geo,
         r.house,
-        &PlanetConfig::default_all_configs(),
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
         &app_state.ephe_path,
     )?;
 
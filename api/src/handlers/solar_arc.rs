@@ -0,0 +1,111 @@
+use crate::{
+    error::Error,
+    request::{SolarArcRequest, lots_config},
+    state::AppState,
+};
+use actix_web::{HttpResponse, Responder, post, web};
+use geo_position::GeoPosition;
+use horo::{Horoscope, HoroscopeConfig, PlanetConfig, SolarArc};
+use horo_date_time::horo_date_time;
+
+/// 太阳弧推运
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="推运",
+    context_path="/api/process",
+    request_body=SolarArcRequest,
+    responses(
+        (status = 201, description = "返回推运时间的太阳弧推运盘及查询期间的正相位", body = SolarArc),
+    ),
+)
+)]
+#[post("/solar_arc")]
+pub async fn solar_arc_handler(
+    app_state: web::Data<AppState>,
+    r: actix_web_validator::Json<SolarArcRequest>,
+) -> Result<impl Responder, Error> {
+    let r = r.into_inner();
+
+    let native_date = horo_date_time(
+        r.native_date.year,
+        r.native_date.month,
+        r.native_date.day,
+        r.native_date.hour,
+        r.native_date.minute,
+        r.native_date.second,
+        r.native_date.tz,
+        r.native_date.st,
+    )?;
+
+    let process_date = horo_date_time(
+        r.process_date.year,
+        r.process_date.month,
+        r.process_date.day,
+        r.process_date.hour,
+        r.process_date.minute,
+        r.process_date.second,
+        r.process_date.tz,
+        r.process_date.st,
+    )?;
+
+    let start_date = if let Some(start_date) = r.start_date {
+        horo_date_time(
+            start_date.year,
+            start_date.month,
+            start_date.day,
+            start_date.hour,
+            start_date.minute,
+            start_date.second,
+            start_date.tz,
+            start_date.st,
+        )?
+    } else {
+        native_date
+    };
+
+    let end_date = if let Some(end_date) = r.end_date {
+        horo_date_time(
+            end_date.year,
+            end_date.month,
+            end_date.day,
+            end_date.hour,
+            end_date.minute,
+            end_date.second,
+            end_date.tz,
+            end_date.st,
+        )?
+    } else {
+        process_date
+    };
+
+    let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
+
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        aspects: r.aspects,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
+    let natal = Horoscope::new(
+        native_date,
+        geo,
+        r.house,
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
+        &app_state.ephe_path,
+    )?;
+
+    let solar_arc = SolarArc::new(
+        &natal,
+        process_date,
+        start_date,
+        end_date,
+        r.key,
+        &config.aspects,
+        &app_state.ephe_path,
+    )?;
+
+    Ok(HttpResponse::Created().json(solar_arc))
+}
@@ -0,0 +1,101 @@
+use crate::{
+    error::Error,
+    request::{TransitRequest, lots_config},
+    state::AppState,
+};
+use actix_web::{HttpResponse, Responder, post, web};
+use geo_position::GeoPosition;
+use horo::{Horoscope, HoroscopeConfig, PlanetConfig, transits};
+use horo_date_time::horo_date_time;
+
+#[cfg(feature = "swagger")]
+use horo::Transit;
+
+// 一次最多查询的天数
+const MAX_DAYS: f64 = 3660.0;
+
+/// 行运
+#[cfg_attr(feature = "swagger", 
+utoipa::path(
+    tag="推运",
+    context_path="/api/process",
+    request_body=TransitRequest,
+    responses(
+        (status = 201, description = "返回查询期间行运星体与本命盘的全部相位", body = Vec<Transit>),
+    ),
+)
+)]
+#[post("/transits")]
+pub async fn transits_handler(
+    r: actix_web_validator::Json<TransitRequest>,
+    app_state: web::Data<AppState>,
+) -> Result<impl Responder, Error> {
+    let r = r.into_inner();
+
+    let native_date = horo_date_time(
+        r.native_date.year,
+        r.native_date.month,
+        r.native_date.day,
+        r.native_date.hour,
+        r.native_date.minute,
+        r.native_date.second,
+        r.native_date.tz,
+        r.native_date.st,
+    )?;
+
+    let start_date = horo_date_time(
+        r.start_date.year,
+        r.start_date.month,
+        r.start_date.day,
+        r.start_date.hour,
+        r.start_date.minute,
+        r.start_date.second,
+        r.start_date.tz,
+        r.start_date.st,
+    )?;
+
+    let end_date = horo_date_time(
+        r.end_date.year,
+        r.end_date.month,
+        r.end_date.day,
+        r.end_date.hour,
+        r.end_date.minute,
+        r.end_date.second,
+        r.end_date.tz,
+        r.end_date.st,
+    )?;
+
+    if end_date.jd_utc - start_date.jd_utc > MAX_DAYS {
+        return Err(Error::BadRequest(format!(
+            "结束时间与开始时间最多相差{MAX_DAYS}天"
+        )));
+    }
+
+    let geo = GeoPosition::new(r.geo.long, r.geo.lat)?;
+
+    let config = HoroscopeConfig {
+        node_type: r.node_type,
+        zodiac: r.zodiac,
+        lots: lots_config(r.lots, r.custom_lots),
+        ..Default::default()
+    };
+
+    let natal = Horoscope::new(
+        native_date,
+        geo,
+        r.house,
+        &PlanetConfig::default_configs(r.outer_planets),
+        &config,
+        &app_state.ephe_path,
+    )?;
+
+    let transits = transits(
+        &natal,
+        start_date,
+        end_date,
+        &r.transit,
+        &app_state.ephe_path,
+    )?;
+
+    Ok(HttpResponse::Created().json(transits))
+}
@@ -1,4 +1,8 @@
-use horo::HouseName;
+use horo::{
+    AspectConfig, ConditionConfig, DignityScoring, DirectionKey, FixedStarConfig, HouseName,
+    LotConfig, LotName, McProgression, MidpointConfig, NodeType, ParallelConfig, PositionMode,
+    ProgressionType, SolarArcKey, TransitConfig, Zodiac,
+};
 use serde::Deserialize;
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
@@ -55,6 +59,42 @@ pub struct HoroNativeRenReust {
     pub geo: GeoRequest,
     /// 宫位系统，Alcabitus：阿卡比特
     pub house: HouseName,
+    /// 必然尊贵计分方案，默认：Lilly
+    #[serde(default)]
+    pub dignity_scoring: DignityScoring,
+    /// 是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 行星位置的计算方式，Geocentric：地心，{"Topocentric":海拔高度（米）}：站心，Heliocentric：日心，默认：Geocentric
+    #[serde(default)]
+    pub position_mode: PositionMode,
+    /// 行星状态的配置：日核、焦伤、在日光下的度数，留的天数，默认：17分、8.5度、17度、2天
+    #[serde(default)]
+    pub conditions: ConditionConfig,
+    /// 相位配置，启用的相位及容许度，默认：托勒密相位，半容许度
+    #[serde(default)]
+    pub aspects: AspectConfig,
+    /// 平行、对等平行的配置，默认：容许度1度
+    #[serde(default)]
+    pub parallels: ParallelConfig,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
+    /// 恒星的配置：恒星表、星等过滤、合相及Paran的容许度，默认：内置的14颗恒星，容许度1度
+    #[serde(default)]
+    pub fixed_stars: FixedStarConfig,
+    /// 中点树的度盘及容许度，Degree360、Degree90、Degree45，默认：90度盘，容许度1.5度
+    #[serde(default)]
+    pub midpoints: MidpointConfig,
 }
 
 #[derive(Deserialize, Validate)]
@@ -89,6 +129,33 @@ pub struct HoroscopeComparisonRequst {
 
     /// 宫位系统，Alcabitus：阿卡比特
     pub house: HouseName,
+    /// 是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 行星位置的计算方式，Geocentric：地心，{"Topocentric":海拔高度（米）}：站心，Heliocentric：日心，默认：Geocentric
+    #[serde(default)]
+    pub position_mode: PositionMode,
+    /// 行星状态的配置：日核、焦伤、在日光下的度数，留的天数，默认：17分、8.5度、17度、2天
+    #[serde(default)]
+    pub conditions: ConditionConfig,
+    /// 相位配置，启用的相位及容许度，默认：托勒密相位，半容许度
+    #[serde(default)]
+    pub aspects: AspectConfig,
+    /// 平行、对等平行的配置，默认：容许度1度
+    #[serde(default)]
+    pub parallels: ParallelConfig,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
 }
 
 /// 返照盘
@@ -109,6 +176,27 @@ pub struct ReturnRequest {
 
     /// 宫位系统，Alcabitus：阿卡比特
     pub house: HouseName,
+    /// 是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 行星状态的配置：日核、焦伤、在日光下的度数，留的天数，默认：17分、8.5度、17度、2天
+    #[serde(default)]
+    pub conditions: ConditionConfig,
+    /// 相位配置，启用的相位及容许度，默认：托勒密相位，半容许度
+    #[serde(default)]
+    pub aspects: AspectConfig,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
 }
 
 /// 法达
@@ -124,8 +212,319 @@ pub struct FirdariaRequest {
     pub geo: GeoRequest,
 }
 
-pub type DirectionRequest = FirdariaRequest;
-pub type QuadrantProcessRequest = HoroNativeRenReust;
+/// 主向推运
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct DirectionRequest {
+    /// 出生时间
+    #[validate(nested)]
+    pub native_date: DateRequest,
+
+    /// 出生地大地经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
+    /// 时间换算，Ptolemy、Naibod、Cardan、TrueSolarArc、Simmonite，默认：Ptolemy
+    #[serde(default)]
+    pub key: DirectionKey,
+}
+
+/// 行星时
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct PlanetaryHoursRequest {
+    /// 开始时间
+    #[validate(nested)]
+    pub start_date: DateRequest,
+
+    /// 结束时间，默认：只计算开始时间所在的行星日
+    #[serde(default)]
+    #[validate(nested)]
+    pub end_date: Option<DateRequest>,
+
+    /// 大地经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+}
+
+/// 谐波盘
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct HarmonicRequest {
+    /// 出生时间
+    #[validate(nested)]
+    pub date: DateRequest,
+
+    /// 地理经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+    /// 宫位系统，Alcabitus：阿卡比特
+    pub house: HouseName,
+    /// 谐波数，最小值1
+    #[validate(range(min = 1, message = "谐波数最小1"))]
+    pub harmonic: u16,
+    /// 是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 谐波盘的相位配置，启用的相位及容许度，默认：托勒密相位，半容许度
+    #[serde(default)]
+    pub aspects: AspectConfig,
+}
+
+/// 卜卦盘
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct HoraryRequest {
+    /// 起卦时间
+    #[validate(nested)]
+    pub date: DateRequest,
+
+    /// 起卦地点的地理经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+    /// 宫位系统，Regiomontanus：雷格蒙塔努斯
+    pub house: HouseName,
+    /// 所问之事所在的宫位，2-12
+    #[validate(range(min = 2, max = 12, message = "2<=所问之事的宫位<=12"))]
+    pub quesited_house: u8,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 行星状态的配置：日核、焦伤、在日光下的度数，留的天数，默认：17分、8.5度、17度、2天
+    #[serde(default)]
+    pub conditions: ConditionConfig,
+    /// 相位配置，启用的相位及容许度，默认：托勒密相位，半容许度
+    #[serde(default)]
+    pub aspects: AspectConfig,
+}
+
+/// 行运
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct TransitRequest {
+    /// 出生时间
+    #[validate(nested)]
+    pub native_date: DateRequest,
+
+    /// 出生地大地经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+    /// 宫位系统，Alcabitus：阿卡比特
+    pub house: HouseName,
+    /// 本命盘是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
+
+    /// 查询的开始时间
+    #[validate(nested)]
+    pub start_date: DateRequest,
+    /// 查询的结束时间
+    #[validate(nested)]
+    pub end_date: DateRequest,
+    /// 行运星体、相位及容许度，默认：除月亮外的太阳至冥王星，托勒密相位，容许度1度
+    #[serde(default)]
+    pub transit: TransitConfig,
+}
+
+/// 次限、三限、小限推运
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ProgressionRequest {
+    /// 出生时间
+    #[validate(nested)]
+    pub native_date: DateRequest,
+
+    /// 推运时间
+    #[validate(nested)]
+    pub process_date: DateRequest,
+
+    /// 出生地大地经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+
+    /// 宫位系统，Alcabitus：阿卡比特
+    pub house: HouseName,
+    /// 推运中天的方法，SolarArc：太阳弧，NaibodInRa：Naibod赤经，MeanSun：平太阳，默认：SolarArc
+    #[serde(default)]
+    pub mc_progression: McProgression,
+    /// 是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 相位配置，启用的相位及容许度，默认：托勒密相位，半容许度
+    #[serde(default)]
+    pub aspects: AspectConfig,
+    /// 平行、对等平行的配置，默认：容许度1度
+    #[serde(default)]
+    pub parallels: ParallelConfig,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
+}
+
+/// 推运行星与本命盘形成相位的时间
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ProgressedAspectsRequest {
+    /// 出生时间
+    #[validate(nested)]
+    pub native_date: DateRequest,
+
+    /// 出生地大地经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+    /// 宫位系统，Alcabitus：阿卡比特
+    pub house: HouseName,
+
+    /// 查询的开始时间
+    #[validate(nested)]
+    pub start_date: DateRequest,
+    /// 查询的结束时间，距出生时间最多43830天（约120年）
+    #[validate(nested)]
+    pub end_date: DateRequest,
+    /// 推运的方法，Secondary：次限，Tertiary：三限，Minor：小限，默认：Secondary
+    #[serde(default)]
+    pub progression_type: ProgressionType,
+    /// 是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 相位配置，只使用其中启用的相位，默认：托勒密相位
+    #[serde(default)]
+    pub aspects: AspectConfig,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
+}
+
+/// 太阳弧推运
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SolarArcRequest {
+    /// 出生时间
+    #[validate(nested)]
+    pub native_date: DateRequest,
+
+    /// 推运时间
+    #[validate(nested)]
+    pub process_date: DateRequest,
+
+    /// 出生地大地经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+
+    /// 宫位系统，Alcabitus：阿卡比特
+    pub house: HouseName,
+    /// 查询正相位的开始时间，默认：出生时间
+    #[serde(default)]
+    #[validate(nested)]
+    pub start_date: Option<DateRequest>,
+    /// 查询正相位的结束时间，默认：推运时间
+    #[serde(default)]
+    #[validate(nested)]
+    pub end_date: Option<DateRequest>,
+    /// 推运的弧，SolarArc：太阳弧，OneDegree：每年1度，默认：SolarArc
+    #[serde(default)]
+    pub key: SolarArcKey,
+    /// 是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 相位配置，启用的相位及容许度，默认：托勒密相位，半容许度
+    #[serde(default)]
+    pub aspects: AspectConfig,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
+}
+
+/// 象限推运
+#[derive(Deserialize, Validate)]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct QuadrantProcessRequest {
+    /// 出生时间
+    #[validate(nested)]
+    pub date: DateRequest,
+
+    /// 地理经纬度
+    #[validate(nested)]
+    pub geo: GeoRequest,
+    /// 宫位系统，Alcabitus：阿卡比特
+    pub house: HouseName,
+    /// 是否计算天王星、海王星、冥王星，默认：false
+    #[serde(default)]
+    pub outer_planets: bool,
+    /// 月交点的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// 黄道，Tropical：回归黄道，{"Sidereal":"Lahiri"}：恒星黄道及岁差，默认：Tropical
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// 内置的阿拉伯点，默认：不计算
+    #[serde(default)]
+    pub lots: Vec<LotName>,
+    /// 自定义的阿拉伯点，在内置的点之后按顺序计算，默认：无
+    #[serde(default)]
+    pub custom_lots: Vec<LotConfig>,
+}
+
 pub type QuadrantProcessLongitudeRequest = ReturnRequest;
 
 /// 七政
@@ -143,4 +542,16 @@ pub struct QiZhengRequst {
     /// 推运时间
     #[validate(nested)]
     pub process_date: DateRequest,
+
+    /// 计都、罗喉的计算方法，Mean：平均交点，True：真交点，默认：Mean
+    #[serde(default)]
+    pub node_type: NodeType,
+}
+
+/// 阿拉伯点的公式，内置的点在前，自定义的点在后
+pub fn lots_config(lots: Vec<LotName>, custom_lots: Vec<LotConfig>) -> Vec<LotConfig> {
+    lots.into_iter()
+        .filter_map(LotConfig::builtin)
+        .chain(custom_lots)
+        .collect()
 }
@@ -4,13 +4,22 @@ use crate::handlers::{
     compare_horoscop::compare,
     direction::directions,
     firdaria::firdaria,
+    harmonic::harmonic,
     healthz::{liveness_handler, readiness_handler},
+    horary::horary,
     horo::horo_native,
     house::houses,
+    planetary_hours::planetary_hours_handler,
     profection::profection,
+    progression::{
+        minor_progression_handler, progressed_aspects_handler, secondary_progression_handler,
+        tertiary_progression_handler,
+    },
     qizheng::qizheng_horo,
     quadrant_process::{quadrant_process_handler, quadrant_process_longitude_handler},
     return_horoscop::{lunar_return_horo, solar_return_horo},
+    solar_arc::solar_arc_handler,
+    transit::transits_handler,
 };
 
 pub fn health_routes(cfg: &mut web::ServiceConfig) {
@@ -18,18 +27,30 @@ pub fn health_routes(cfg: &mut web::ServiceConfig) {
 }
 
 pub fn horo_routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::scope("/horo").service(horo_native))
-        .service(houses)
-        .service(
-            web::scope("/process")
-                .service(profection)
-                .service(firdaria)
-                .service(compare)
-                .service(solar_return_horo)
-                .service(lunar_return_horo)
-                .service(directions)
-                .service(quadrant_process_handler)
-                .service(quadrant_process_longitude_handler),
-        )
-        .service(web::scope("/qizheng").service(qizheng_horo));
+    cfg.service(
+        web::scope("/horo")
+            .service(horo_native)
+            .service(planetary_hours_handler)
+            .service(harmonic),
+    )
+    .service(houses)
+    .service(horary)
+    .service(
+        web::scope("/process")
+            .service(profection)
+            .service(firdaria)
+            .service(compare)
+            .service(solar_return_horo)
+            .service(lunar_return_horo)
+            .service(directions)
+            .service(quadrant_process_handler)
+            .service(quadrant_process_longitude_handler)
+            .service(transits_handler)
+            .service(secondary_progression_handler)
+            .service(tertiary_progression_handler)
+            .service(minor_progression_handler)
+            .service(progressed_aspects_handler)
+            .service(solar_arc_handler),
+    )
+    .service(web::scope("/qizheng").service(qizheng_horo));
 }
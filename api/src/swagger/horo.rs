@@ -2,20 +2,42 @@ use crate::handlers::{
     compare_horoscop::__path_compare,
     direction::__path_directions,
     firdaria::__path_firdaria,
+    harmonic::__path_harmonic,
+    horary::__path_horary,
     horo::__path_horo_native,
     house::__path_houses,
+    planetary_hours::__path_planetary_hours_handler,
     profection::__path_profection,
+    progression::{
+        __path_minor_progression_handler, __path_progressed_aspects_handler,
+        __path_secondary_progression_handler, __path_tertiary_progression_handler,
+    },
     quadrant_process::{__path_quadrant_process_handler, __path_quadrant_process_longitude_handler},
     return_horoscop::{__path_lunar_return_horo, __path_solar_return_horo},
+    solar_arc::__path_solar_arc_handler,
+    transit::__path_transits_handler,
 };
 use crate::request::{
-    DateRequest, FirdariaRequest, GeoRequest, HoroNativeRenReust, HoroscopeComparisonRequst,
-    ProfectionRequest, ReturnRequest,
+    DateRequest, DirectionRequest, FirdariaRequest, GeoRequest, HarmonicRequest, HoraryRequest,
+    HoroNativeRenReust, HoroscopeComparisonRequst, PlanetaryHoursRequest, ProfectionRequest,
+    ProgressedAspectsRequest, ProgressionRequest, QuadrantProcessRequest, ReturnRequest,
+    SolarArcRequest, TransitRequest,
 };
 use geo_position::GeoPosition;
 use horo::{
-    Aspect, FirdariaPeriod, FirdariaSubPeriod, Horoscope, HoroscopeComparison, HouseName, Planet,
-    PlanetName, PlanetSpeedState, Profection, ReturnHoroscop,
+    Almuten, AlmutenPoint, AlmutenPointName, AlmutenScore, AngularEvent, Aspect, AspectConfig,
+    AspectSetting, AspectType, Ayanamsa, ConditionConfig, Dial, DialPosition, DignityScoring,
+    DignityType, DirectionKey, DispositorChain, Dispositors, DivisionalPosition,
+    EssentialDignities, FirdariaPeriod, FirdariaSubPeriod, FixedStar, FixedStarCatalogue,
+    FixedStarConfig, FixedStarConjunction, FixedStarName, FixedStarPoint, HarmonicChart, Horary,
+    Horoscope, HoroscopeComparison, HouseName, HousePlacement, Lot, LotConfig, LotName,
+    McProgression, Midpoint, MidpointConfig, MidpointContact, MidpointTree, MoonAspect,
+    MoonCondition, MoonPhase, MotionDirection, MutualReception, NodeType, OrbRule, ParallelConfig,
+    Paran, Planet, PlanetCondition, PlanetName, PlanetScore, PlanetSect, PlanetSpeedState,
+    PlanetaryHour, PlanetaryHours, PositionMode, Profection, Progression, ProgressionType,
+    Reception, ReturnHoroscop, Sect, SectAnalysis, Significator, SolarArc, SolarArcDirection,
+    SolarArcKey, SolarOrientation, SolarPhase, Stricture, StrictureVerdict, Transit, TransitConfig,
+    TransitPoint, Zodiac,
 };
 use horo_date_time::HoroDateTime;
 use utoipa::OpenApi;
@@ -27,6 +49,12 @@ use utoipa::OpenApi;
         // 本命星盘
         horo_native,
         houses,
+        // 行星时
+        planetary_hours_handler,
+        // 谐波盘
+        harmonic,
+        // 卜卦盘
+        horary,
         // 小限
         profection,
         // 比较盘
@@ -42,28 +70,133 @@ use utoipa::OpenApi;
         // 象限推运
         quadrant_process_handler,
         quadrant_process_longitude_handler,
+        // 行运
+        transits_handler,
+        // 次限、三限、小限推运
+        secondary_progression_handler,
+        tertiary_progression_handler,
+        minor_progression_handler,
+        progressed_aspects_handler,
+        // 太阳弧推运
+        solar_arc_handler,
     ),
     components(schemas(
         DateRequest,
         GeoRequest,
         HoroNativeRenReust,
+        QuadrantProcessRequest,
         ProfectionRequest,
         HoroscopeComparisonRequst,
         FirdariaRequest,
+        DirectionRequest,
+        DirectionKey,
         Horoscope,HoroscopeComparison,
         HouseName,
+        HousePlacement,
         Planet,
         PlanetName,
         Aspect,
+        // 相位配置
+        AspectType,
+        AspectConfig,
+        AspectSetting,
+        OrbRule,
+        ParallelConfig,
+        // 行星状态
+        ConditionConfig,
+        PlanetCondition,
+        MotionDirection,
+        SolarPhase,
+        SolarOrientation,
+        // 月亮状态
+        MoonCondition,
+        MoonPhase,
+        MoonAspect,
+        // 接纳、互容、定位星
+        Reception,
+        MutualReception,
+        DispositorChain,
+        Dispositors,
+        // 区分
+        Sect,
+        SectAnalysis,
+        PlanetSect,
+        // 十二分部、九分部
+        DivisionalPosition,
+        // 中点、度盘
+        Midpoint,
+        MidpointConfig,
+        MidpointContact,
+        MidpointTree,
+        Dial,
+        DialPosition,
+        // 谐波盘
+        HarmonicRequest,
+        HarmonicChart,
+        // 恒星
+        FixedStar,
+        FixedStarName,
+        FixedStarCatalogue,
+        FixedStarConfig,
+        FixedStarConjunction,
+        FixedStarPoint,
+        Paran,
+        AngularEvent,
+        // 阿拉伯点
+        Lot,
+        LotName,
+        LotConfig,
         HoroDateTime,
         GeoPosition,
         PlanetSpeedState,
+        // 必然尊贵
+        EssentialDignities,
+        DignityScoring,
+        DignityType,
+        NodeType,
+        // 黄道
+        Zodiac,
+        Ayanamsa,
+        // 行星位置的计算方式
+        PositionMode,
+        // 胜利星
+        Almuten,
+        AlmutenPoint,
+        AlmutenPointName,
+        AlmutenScore,
+        PlanetScore,
         Profection,
         ReturnRequest,
         ReturnHoroscop,
         // 法达
         FirdariaPeriod,
-        FirdariaSubPeriod
+        FirdariaSubPeriod,
+        // 行星时
+        PlanetaryHoursRequest,
+        PlanetaryHours,
+        PlanetaryHour,
+        // 卜卦盘
+        HoraryRequest,
+        Horary,
+        Stricture,
+        StrictureVerdict,
+        Significator,
+        // 行运
+        TransitRequest,
+        TransitConfig,
+        TransitPoint,
+        Transit,
+        // 次限、三限、小限推运
+        ProgressionRequest,
+        ProgressedAspectsRequest,
+        ProgressionType,
+        McProgression,
+        Progression,
+        // 太阳弧推运
+        SolarArcRequest,
+        SolarArcKey,
+        SolarArc,
+        SolarArcDirection
     ))
 )]
 pub struct HoroApiDoc;
@@ -3,7 +3,7 @@ use crate::request::{DateRequest, GeoRequest, QiZhengRequst};
 
 use geo_position::GeoPosition;
 use horo_date_time::HoroDateTime;
-use qizheng::{Horoscope, ASCHouse, LunarMansionsName, Planet, PlanetName, DistanceStarLong, House,HouseName,PlanetSpeedState, DongWei};
+use qizheng::{Horoscope, ASCHouse, LunarMansionsName, Planet, PlanetName, DistanceStarLong, House,HouseName,PlanetSpeedState, DongWei, NodeType};
 use lunar_calendar::LunarCalendar;
 use ganzhiwuxing::GanZhi;
 
@@ -24,6 +24,7 @@ use utoipa::OpenApi;
         DateRequest, GeoRequest,
         // 七政 request
         QiZhengRequst,
+        NodeType,
 
         HoroDateTime, GeoPosition,
 
@@ -0,0 +1,250 @@
+use crate::{
+    DignityScoring, DignityType, PlanetName,
+    dignity::{SEVEN_PLANETS, dignity_ruler},
+};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 参与计算胜利星的点
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum AlmutenPointName {
+    /// 行星或虚点，如：日、月、上升、中天、福点
+    Planet(PlanetName),
+    /// 宫头，1-12
+    Cusp(u8),
+}
+
+/// 某行星在某点上获得的尊贵及得分
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct AlmutenScore {
+    /// 行星
+    pub planet: PlanetName,
+    /// 行星在此点上拥有的尊贵
+    pub dignities: Vec<DignityType>,
+    /// 得分，庙5、旺4、三分3、界2、面1
+    pub score: i32,
+}
+
+/// 某点的胜利星
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct AlmutenPoint {
+    /// 点
+    pub name: AlmutenPointName,
+    /// 点的黄经
+    pub long: f64,
+    /// 七颗古典行星在此点上的得分
+    pub scores: Vec<AlmutenScore>,
+    /// 胜利星，得分最高的行星，同分时有多颗
+    pub almuten: Vec<PlanetName>,
+}
+
+impl AlmutenPoint {
+    /// 计算某点的胜利星
+    /// * name: 点
+    /// * long: 点的黄经
+    /// * is_diurnal: 白天盘：true，夜间盘：false，用于三分主星
+    pub fn new(name: AlmutenPointName, long: f64, is_diurnal: bool) -> Self {
+        let scores: Vec<_> = SEVEN_PLANETS
+            .iter()
+            .map(|&planet| {
+                let dignities: Vec<_> = [
+                    DignityType::Domicile,
+                    DignityType::Exaltation,
+                    DignityType::Triplicity,
+                    DignityType::Term,
+                    DignityType::Face,
+                ]
+                .into_iter()
+                .filter(|&dignity| dignity_ruler(dignity, long, is_diurnal) == Some(planet))
+                .collect();
+                let score = dignities
+                    .iter()
+                    .map(|&dignity| DignityScoring::default().score(dignity))
+                    .sum();
+                AlmutenScore {
+                    planet,
+                    dignities,
+                    score,
+                }
+            })
+            .collect();
+
+        let almuten = highest(scores.iter().map(|s| (s.planet, s.score)));
+
+        Self {
+            name,
+            long,
+            scores,
+            almuten,
+        }
+    }
+}
+
+/// 行星的总得分
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct PlanetScore {
+    /// 行星
+    pub planet: PlanetName,
+    /// 总分
+    pub score: i32,
+}
+
+/// 胜利星
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Almuten {
+    /// 全盘胜利星（Almuten Figuris），同分时有多颗
+    pub figuris: Vec<PlanetName>,
+    /// 七颗古典行星在日、月、上升、中天、福点上的总得分
+    pub figuris_scores: Vec<PlanetScore>,
+    /// 参与计算全盘胜利星的点：日、月、上升、中天、福点
+    pub figuris_points: Vec<AlmutenPoint>,
+    /// 12宫头的胜利星
+    pub cusps: Vec<AlmutenPoint>,
+}
+
+impl Almuten {
+    /// 计算全盘胜利星及各宫头的胜利星
+    /// * figuris_points: 参与计算全盘胜利星的点及其黄经
+    /// * cusps: 12宫头黄经
+    /// * is_diurnal: 白天盘：true，夜间盘：false，用于三分主星
+    pub fn new(figuris_points: &[(PlanetName, f64)], cusps: &[f64], is_diurnal: bool) -> Self {
+        let figuris_points: Vec<_> = figuris_points
+            .iter()
+            .map(|&(name, long)| {
+                AlmutenPoint::new(AlmutenPointName::Planet(name), long, is_diurnal)
+            })
+            .collect();
+
+        let figuris_scores: Vec<_> = SEVEN_PLANETS
+            .iter()
+            .map(|&planet| PlanetScore {
+                planet,
+                score: figuris_points
+                    .iter()
+                    .flat_map(|point| point.scores.iter())
+                    .filter(|s| s.planet == planet)
+                    .map(|s| s.score)
+                    .sum(),
+            })
+            .collect();
+
+        let figuris = highest(figuris_scores.iter().map(|s| (s.planet, s.score)));
+
+        let cusps = cusps
+            .iter()
+            .enumerate()
+            .map(|(i, &long)| {
+                AlmutenPoint::new(AlmutenPointName::Cusp(i as u8 + 1), long, is_diurnal)
+            })
+            .collect();
+
+        Self {
+            figuris,
+            figuris_scores,
+            figuris_points,
+            cusps,
+        }
+    }
+}
+
+/// 得分最高的行星
+fn highest(scores: impl Iterator<Item = (PlanetName, i32)> + Clone) -> Vec<PlanetName> {
+    let max = scores.clone().map(|(_, score)| score).max().unwrap_or(0);
+    scores
+        .filter(|&(_, score)| score == max)
+        .map(|(planet, _)| planet)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PlanetName::*;
+
+    use super::*;
+
+    fn score_of(point: &AlmutenPoint, planet: PlanetName) -> i32 {
+        point
+            .scores
+            .iter()
+            .find(|s| s.planet == planet)
+            .unwrap()
+            .score
+    }
+
+    #[test]
+    fn test_almuten_point_diurnal() {
+        // 白羊19度，白天盘：火星庙5，太阳旺4、三分3、面1，水星界2
+        let point = AlmutenPoint::new(AlmutenPointName::Planet(ASC), 19.0, true);
+        assert_eq!(point.scores.len(), 7);
+        assert_eq!(score_of(&point, Mars), 5);
+        assert_eq!(score_of(&point, Sun), 4 + 3 + 1);
+        assert_eq!(score_of(&point, Mercury), 2);
+        assert_eq!(score_of(&point, Jupiter), 0);
+        assert_eq!(
+            point
+                .scores
+                .iter()
+                .find(|s| s.planet == Sun)
+                .unwrap()
+                .dignities,
+            vec![
+                DignityType::Exaltation,
+                DignityType::Triplicity,
+                DignityType::Face
+            ]
+        );
+        assert_eq!(point.almuten, vec![Sun]);
+    }
+
+    #[test]
+    fn test_almuten_point_nocturnal() {
+        // 白羊19度，夜间盘：火星庙5，太阳旺4、面1，木星三分3，水星界2，日火同分
+        let point = AlmutenPoint::new(AlmutenPointName::Cusp(1), 19.0, false);
+        assert_eq!(score_of(&point, Sun), 4 + 1);
+        assert_eq!(score_of(&point, Jupiter), 3);
+        assert_eq!(point.almuten, vec![Sun, Mars]);
+    }
+
+    #[test]
+    fn test_almuten() {
+        let cusps: Vec<_> = (0..12).map(|i| f64::from(i) * 30.0 + 19.0).collect();
+        let almuten = Almuten::new(&[(Sun, 19.0), (Moon, 19.0), (ASC, 49.0)], &cusps, true);
+
+        assert_eq!(almuten.figuris_points.len(), 3);
+        assert_eq!(almuten.cusps.len(), 12);
+        assert_eq!(almuten.cusps[0].name, AlmutenPointName::Cusp(1));
+        assert_eq!(almuten.cusps[11].name, AlmutenPointName::Cusp(12));
+
+        // 金牛19度，白天盘：金星庙5、三分3，月亮旺4，木星界2，月亮面1
+        assert_eq!(score_of(&almuten.figuris_points[2], Venus), 5 + 3);
+        assert_eq!(score_of(&almuten.figuris_points[2], Moon), 4 + 1);
+        assert_eq!(score_of(&almuten.figuris_points[2], Jupiter), 2);
+
+        let total = |planet| {
+            almuten
+                .figuris_scores
+                .iter()
+                .find(|s| s.planet == planet)
+                .unwrap()
+                .score
+        };
+        assert_eq!(total(Sun), 8 * 2);
+        assert_eq!(total(Mars), 5 * 2);
+        assert_eq!(total(Venus), 8);
+        assert_eq!(almuten.figuris, vec![Sun]);
+    }
+}
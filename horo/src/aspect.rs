@@ -1,13 +1,209 @@
-use crate::planet::PlanetName;
+use crate::{
+    Reception,
+    planet::{Planet, PlanetName},
+};
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
 
+/// 相位类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum AspectType {
+    /// 合相，0度
+    Conjunction,
+    /// 半六合，30度
+    Semisextile,
+    /// 半刑，45度
+    Semisquare,
+    /// 六合，60度
+    Sextile,
+    /// 五分相，72度
+    Quintile,
+    /// 刑，90度
+    Square,
+    /// 三合，120度
+    Trine,
+    /// 八分之三相，135度
+    Sesquiquadrate,
+    /// 倍五分相，144度
+    Biquintile,
+    /// 梅花相，150度
+    Quincunx,
+    /// 冲，180度
+    Opposition,
+}
+
+impl AspectType {
+    /// 相位的度数
+    pub fn angle(&self) -> u8 {
+        match self {
+            AspectType::Conjunction => 0,
+            AspectType::Semisextile => 30,
+            AspectType::Semisquare => 45,
+            AspectType::Sextile => 60,
+            AspectType::Quintile => 72,
+            AspectType::Square => 90,
+            AspectType::Trine => 120,
+            AspectType::Sesquiquadrate => 135,
+            AspectType::Biquintile => 144,
+            AspectType::Quincunx => 150,
+            AspectType::Opposition => 180,
+        }
+    }
+
+    /// 托勒密相位：合、六合、刑、三合、冲
+    pub fn ptolemaic() -> [AspectType; 5] {
+        [
+            AspectType::Conjunction,
+            AspectType::Sextile,
+            AspectType::Square,
+            AspectType::Trine,
+            AspectType::Opposition,
+        ]
+    }
+
+    /// 次要相位：半六合、半刑、八分之三相、梅花相、五分相、倍五分相
+    pub fn minor() -> [AspectType; 6] {
+        [
+            AspectType::Semisextile,
+            AspectType::Semisquare,
+            AspectType::Sesquiquadrate,
+            AspectType::Quincunx,
+            AspectType::Quintile,
+            AspectType::Biquintile,
+        ]
+    }
+
+    /// 由相位度数得到相位类型，映点、反映点的相位值为0，也视为合相
+    fn from_angle(angle: u8) -> Self {
+        match angle {
+            30 => AspectType::Semisextile,
+            45 => AspectType::Semisquare,
+            60 => AspectType::Sextile,
+            72 => AspectType::Quintile,
+            90 => AspectType::Square,
+            120 => AspectType::Trine,
+            135 => AspectType::Sesquiquadrate,
+            144 => AspectType::Biquintile,
+            150 => AspectType::Quincunx,
+            180 => AspectType::Opposition,
+            _ => AspectType::Conjunction,
+        }
+    }
+}
+
+/// 容许度规则
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum OrbRule {
+    /// 半容许度（Moiety）：两星容许度之和的一半
+    #[default]
+    Moiety,
+    /// 固定容许度，单位：度
+    Fixed(f64),
+}
+
+/// 启用的相位及其容许度
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct AspectSetting {
+    /// 相位类型
+    pub aspect_type: AspectType,
+    /// 此相位的容许度，单位：度，设置后不再使用AspectConfig.orb_rule
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub orb: Option<f64>,
+}
+
+impl AspectSetting {
+    pub fn new(aspect_type: AspectType, orb: Option<f64>) -> Self {
+        Self { aspect_type, orb }
+    }
+}
+
+/// 相位配置
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct AspectConfig {
+    /// 启用的相位
+    pub aspects: Vec<AspectSetting>,
+    /// 未单独设置容许度的相位所用的容许度规则，默认：半容许度
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub orb_rule: OrbRule,
+}
+
+/// 默认只启用托勒密相位，容许度为半容许度
+impl Default for AspectConfig {
+    fn default() -> Self {
+        Self {
+            aspects: AspectType::ptolemaic()
+                .into_iter()
+                .map(|aspect_type| AspectSetting::new(aspect_type, None))
+                .collect(),
+            orb_rule: OrbRule::Moiety,
+        }
+    }
+}
+
+impl AspectConfig {
+    /// 启用托勒密相位及所有次要相位，容许度为半容许度
+    pub fn all() -> Self {
+        Self {
+            aspects: AspectType::ptolemaic()
+                .into_iter()
+                .chain(AspectType::minor())
+                .map(|aspect_type| AspectSetting::new(aspect_type, None))
+                .collect(),
+            orb_rule: OrbRule::Moiety,
+        }
+    }
+
+    /// 行星p0与p1成某相位的容许度
+    /// 四轴间只有正相位，容许度为0
+    pub(crate) fn orb(&self, setting: &AspectSetting, p0: &Planet, p1: &Planet) -> f64 {
+        let ascmc = [
+            PlanetName::ASC,
+            PlanetName::MC,
+            PlanetName::DSC,
+            PlanetName::IC,
+        ];
+        if ascmc.contains(&p0.name) && ascmc.contains(&p1.name) {
+            return 0.0;
+        }
+
+        match (setting.orb, self.orb_rule) {
+            (Some(orb), _) => orb,
+            (None, OrbRule::Fixed(orb)) => orb,
+            (None, OrbRule::Moiety) => f64::from(p0.orb + p1.orb) / 2.0,
+        }
+    }
+}
+
+/// 平行、对等平行的配置
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ParallelConfig {
+    /// 容许度，单位：度
+    pub orb: f64,
+}
+
+/// 默认容许度为1度
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self { orb: 1.0 }
+    }
+}
+
 /// 相位
 /// @param aspectValue
-/// 相位值: 0, 60, 90, 120, 180, null
+/// 相位值: 0, 30, 45, 60, 72, 90, 120, 135, 144, 150, 180
 /// @param apply
 /// true: 入相位, false: 出相位
 /// @param d
@@ -22,6 +218,8 @@ use utoipa::ToSchema;
 pub struct Aspect {
     /// 行星p0与p1的相位值
     pub aspect_value: u8,
+    /// 相位类型
+    pub aspect_type: AspectType,
     /// 入相位: true, 出相位: false
     pub apply: bool,
     /// 入相位或出相位多少度
@@ -30,25 +228,34 @@ pub struct Aspect {
     pub p0: PlanetName,
     /// 行星p1的name
     pub p1: PlanetName,
+    /// 两颗古典行星间的接纳及是否互容，只有本命盘计算
+    pub receptions: Vec<Reception>,
 }
 
 impl Aspect {
     pub fn new(aspect_value: u8, apply: bool, d: f64, p0: PlanetName, p1: PlanetName) -> Self {
         Self {
             aspect_value,
+            aspect_type: AspectType::from_angle(aspect_value),
             apply,
             d,
             p0,
             p1,
+            receptions: vec![],
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::planet::PlanetName;
+    use parameterized::parameterized;
+
+    use crate::{
+        config::PlanetConfig,
+        planet::{Planet, PlanetName},
+    };
 
-    use super::Aspect;
+    use super::*;
 
     #[test]
     fn test_new() {
@@ -61,9 +268,107 @@ mod tests {
         let aspect = Aspect::new(aspect_value, apply, d, p0.clone(), p1.clone());
 
         assert_eq!(aspect.aspect_value, 120);
+        assert_eq!(aspect.aspect_type, AspectType::Trine);
         assert!(aspect.apply);
         assert_eq!(aspect.d, d);
         assert_eq!(aspect.p0, p0);
         assert_eq!(aspect.p1, p1);
     }
+
+    #[parameterized(aspect_type = {
+        AspectType::Conjunction,
+        AspectType::Semisextile,
+        AspectType::Semisquare,
+        AspectType::Sextile,
+        AspectType::Quintile,
+        AspectType::Square,
+        AspectType::Trine,
+        AspectType::Sesquiquadrate,
+        AspectType::Biquintile,
+        AspectType::Quincunx,
+        AspectType::Opposition,
+    })]
+    fn test_aspect_type_from_angle(aspect_type: AspectType) {
+        assert_eq!(aspect_type, AspectType::from_angle(aspect_type.angle()));
+    }
+
+    #[test]
+    fn test_default_aspect_config() {
+        let config = AspectConfig::default();
+        assert_eq!(config.orb_rule, OrbRule::Moiety);
+        assert_eq!(
+            config
+                .aspects
+                .iter()
+                .map(|setting| setting.aspect_type)
+                .collect::<Vec<_>>(),
+            AspectType::ptolemaic().to_vec()
+        );
+        assert!(config.aspects.iter().all(|setting| setting.orb.is_none()));
+
+        assert_eq!(AspectConfig::all().aspects.len(), 11);
+    }
+
+    #[test]
+    fn test_orb() {
+        let sun = Planet::new(
+            PlanetName::Sun,
+            0.0,
+            0.0,
+            1.0,
+            0.0,
+            0.0,
+            &PlanetConfig::default_config(&PlanetName::Sun),
+        );
+        let moon = Planet::new(
+            PlanetName::Moon,
+            0.0,
+            0.0,
+            13.0,
+            0.0,
+            0.0,
+            &PlanetConfig::default_config(&PlanetName::Moon),
+        );
+        let asc = Planet::new(
+            PlanetName::ASC,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            &PlanetConfig::default_config(&PlanetName::ASC),
+        );
+        let mc = Planet::new(
+            PlanetName::MC,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            &PlanetConfig::default_config(&PlanetName::MC),
+        );
+
+        let trine = AspectSetting::new(AspectType::Trine, None);
+        let quintile = AspectSetting::new(AspectType::Quintile, Some(2.0));
+
+        // 半容许度
+        let config = AspectConfig::default();
+        assert_eq!(config.orb(&trine, &sun, &moon), (15.0 + 12.0) / 2.0);
+        assert_eq!(config.orb(&trine, &sun, &asc), 15.0 / 2.0);
+        // 单独设置的容许度
+        assert_eq!(config.orb(&quintile, &sun, &moon), 2.0);
+
+        // 固定容许度
+        let config = AspectConfig {
+            aspects: vec![trine, quintile],
+            orb_rule: OrbRule::Fixed(6.0),
+        };
+        assert_eq!(config.orb(&trine, &sun, &moon), 6.0);
+        assert_eq!(config.orb(&trine, &sun, &asc), 6.0);
+        assert_eq!(config.orb(&quintile, &sun, &moon), 2.0);
+
+        // 四轴间容许度为0
+        assert_eq!(config.orb(&trine, &asc, &mc), 0.0);
+        assert_eq!(config.orb(&quintile, &asc, &mc), 0.0);
+    }
 }
@@ -0,0 +1,365 @@
+use crate::{Error, Planet, PlanetName, position::PositionMode, utils::included_angle};
+use geo_position::GeoPosition;
+use horo_date_time::HoroDateTime;
+use swe::{Body, Flag, swe_calc_ut, swe_close, swe_degnorm, swe_set_ephe_path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+// 二分法求留的时刻的精度，单位：天，约1分钟
+const STATION_PRECISION: f64 = 1.0 / 1440.0;
+// 以速度的差分计算加速度时的时间间隔，单位：天
+const ACCELERATION_DELTA: f64 = 0.5;
+
+/// 行星的运动方向
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum MotionDirection {
+    /// 顺行
+    Direct,
+    /// 逆行
+    Retrograde,
+    /// 留
+    Stationary,
+}
+
+/// 行星与太阳的相位状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum SolarPhase {
+    /// 日核
+    Cazimi,
+    /// 焦伤
+    Combust,
+    /// 在日光下
+    UnderTheBeams,
+    /// 不受太阳影响
+    Free,
+}
+
+/// 行星在太阳东方或西方
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum SolarOrientation {
+    /// 东方：先于太阳升起，黄经小于太阳
+    Oriental,
+    /// 西方：后于太阳升起，黄经大于太阳
+    Occidental,
+}
+
+/// 行星状态的配置
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct ConditionConfig {
+    /// 日核，与太阳相距的最大度数，默认：17分
+    pub cazimi: f64,
+    /// 焦伤，与太阳相距的最大度数，默认：8.5度
+    pub combust: f64,
+    /// 在日光下，与太阳相距的最大度数，默认：17度
+    pub under_the_beams: f64,
+    /// 距留的时间在此天数内，视为留，默认：2天
+    pub stationary_days: f64,
+    /// 是否搜索前后最近一次留的时间，默认：true，
+    /// 不搜索时按行星的速度及其变化估算距留的时间
+    #[cfg_attr(feature = "serde", serde(default = "default_stations"))]
+    pub stations: bool,
+}
+
+#[cfg(feature = "serde")]
+fn default_stations() -> bool {
+    true
+}
+
+impl Default for ConditionConfig {
+    fn default() -> Self {
+        Self {
+            cazimi: 17.0 / 60.0,
+            combust: 8.5,
+            under_the_beams: 17.0,
+            stationary_days: 2.0,
+            stations: true,
+        }
+    }
+}
+
+impl ConditionConfig {
+    /// 与太阳相距d度时的相位状态
+    pub fn solar_phase(&self, d: f64) -> SolarPhase {
+        if d <= self.cazimi {
+            SolarPhase::Cazimi
+        } else if d <= self.combust {
+            SolarPhase::Combust
+        } else if d <= self.under_the_beams {
+            SolarPhase::UnderTheBeams
+        } else {
+            SolarPhase::Free
+        }
+    }
+}
+
+/// 行星状态，太阳、交点、四轴等没有此值
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct PlanetCondition {
+    /// 运动方向
+    pub direction: MotionDirection,
+    /// 之前最近一次留的时间，月亮及未启用ConditionConfig.stations时没有此值
+    pub previous_station: Option<HoroDateTime>,
+    /// 之后最近一次留的时间，月亮及未启用ConditionConfig.stations时没有此值
+    pub next_station: Option<HoroDateTime>,
+    /// 与太阳的相位状态
+    pub solar_phase: SolarPhase,
+    /// 在太阳东方或西方
+    pub orientation: SolarOrientation,
+    /// 与太阳的黄经距离，单位：度
+    pub sun_distance: f64,
+}
+
+/// 计算行星状态，写入planets中各行星的condition
+pub(crate) fn calc_conditions(
+    planets: &mut [Planet],
+    date: &HoroDateTime,
+    geo: &GeoPosition,
+    config: &ConditionConfig,
+    position_mode: PositionMode,
+    ephe_path: &str,
+) -> Result<(), Error> {
+    let Some(sun_long) = planets
+        .iter()
+        .find(|p| p.name == PlanetName::Sun)
+        .map(|p| p.long)
+    else {
+        return Ok(());
+    };
+
+    swe_set_ephe_path(ephe_path);
+    position_mode.set_topo(geo);
+    for planet in planets.iter_mut() {
+        let body = match planet.name {
+            PlanetName::Moon => None,
+            PlanetName::Mercury => Some(Body::SeMercury),
+            PlanetName::Venus => Some(Body::SeVenus),
+            PlanetName::Mars => Some(Body::SeMars),
+            PlanetName::Jupiter => Some(Body::SeJupiter),
+            PlanetName::Saturn => Some(Body::SeSaturn),
+            PlanetName::Uranus => Some(Body::SeUranus),
+            PlanetName::Neptune => Some(Body::SeNeptune),
+            PlanetName::Pluto => Some(Body::SePluto),
+            _ => continue,
+        };
+
+        let (previous_station, next_station) = match body {
+            Some(body) if config.stations => (
+                find_station(date.jd_ut1, body, position_mode, false)?,
+                find_station(date.jd_ut1, body, position_mode, true)?,
+            ),
+            _ => (None, None),
+        };
+
+        let is_stationary = match body {
+            Some(_) if config.stations => previous_station
+                .into_iter()
+                .chain(next_station)
+                .any(|jd| (jd - date.jd_ut1).abs() <= config.stationary_days),
+            Some(body) => {
+                days_to_station(date.jd_ut1, planet.speed, body, position_mode)?
+                    <= config.stationary_days
+            }
+            None => false,
+        };
+        let direction = if is_stationary {
+            MotionDirection::Stationary
+        } else if planet.speed < 0.0 {
+            MotionDirection::Retrograde
+        } else {
+            MotionDirection::Direct
+        };
+
+        let sun_distance = included_angle(planet.long, sun_long);
+        let orientation = if swe_degnorm(sun_long - planet.long) < 180.0 {
+            SolarOrientation::Oriental
+        } else {
+            SolarOrientation::Occidental
+        };
+
+        planet.condition = Some(PlanetCondition {
+            direction,
+            previous_station: previous_station
+                .map(|jd| HoroDateTime::from_jd_ut1_zone(jd, date.tz))
+                .transpose()?,
+            next_station: next_station
+                .map(|jd| HoroDateTime::from_jd_ut1_zone(jd, date.tz))
+                .transpose()?,
+            solar_phase: config.solar_phase(sun_distance),
+            orientation,
+            sun_distance,
+        });
+    }
+    swe_close();
+
+    Ok(())
+}
+
+// 行星的黄经速度
+fn speed(jd: f64, body: Body, position_mode: PositionMode) -> Result<f64, Error> {
+    let flags: Vec<_> = [Flag::SeflgSpeed]
+        .into_iter()
+        .chain(position_mode.flag(body))
+        .collect();
+    let xx = swe_calc_ut(jd, body, &flags)
+        .map_err(|e| Error::Function(format!("计算行星留的时间错误:{e}")))?;
+    Ok(xx[3])
+}
+
+// 搜索留的步长及向前、向后搜索的最大天数，单位：天
+// 步长小于逆行天数的一半，不会漏掉相邻的两次留；最大天数大于相邻两次留的最大间隔
+fn station_search_range(body: Body) -> (f64, f64) {
+    match body {
+        // 逆行约21天，顺行不超过约100天
+        Body::SeMercury => (8.0, 150.0),
+        // 逆行约40天，顺行约540天
+        Body::SeVenus => (15.0, 600.0),
+        // 逆行约60至80天，顺行不超过约730天
+        Body::SeMars => (25.0, 800.0),
+        // 木星及更外的行星逆行约4至5个月，会合周期不超过400天
+        _ => (45.0, 400.0),
+    }
+}
+
+// 按行星的速度及其变化（加速度）估算距最近一次留的天数，速度变化很慢时返回无穷大
+fn days_to_station(jd: f64, v: f64, body: Body, position_mode: PositionMode) -> Result<f64, Error> {
+    let acceleration = (speed(jd + ACCELERATION_DELTA, body, position_mode)?
+        - speed(jd - ACCELERATION_DELTA, body, position_mode)?)
+        / (2.0 * ACCELERATION_DELTA);
+    Ok((v / acceleration).abs())
+}
+
+/// 搜索行星留的时刻，即黄经速度为0的时刻
+/// * forward: true，向后搜索；false，向前搜索
+///
+/// 返回值：留的时刻的儒略日（ut1），搜索范围内没有留，返回None
+fn find_station(
+    jd: f64,
+    body: Body,
+    position_mode: PositionMode,
+    forward: bool,
+) -> Result<Option<f64>, Error> {
+    let (step, max_days) = station_search_range(body);
+    let step = if forward { step } else { -step };
+
+    let mut t0 = jd;
+    let mut v0 = speed(t0, body, position_mode)?;
+    while (t0 - jd).abs() < max_days {
+        let t1 = t0 + step;
+        let v1 = speed(t1, body, position_mode)?;
+        if v0 * v1 <= 0.0 {
+            // 二分法求速度为0的时刻
+            let (mut a, mut b, mut va) = (t0, t1, v0);
+            while (b - a).abs() > STATION_PRECISION {
+                let m = (a + b) / 2.0;
+                let vm = speed(m, body, position_mode)?;
+                if va * vm <= 0.0 {
+                    b = m;
+                } else {
+                    a = m;
+                    va = vm;
+                }
+            }
+            return Ok(Some((a + b) / 2.0));
+        }
+        t0 = t1;
+        v0 = v1;
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use horo_date_time::HoroDateTime;
+    use swe::{Body, swe_close, swe_set_ephe_path};
+
+    use super::{ConditionConfig, SolarPhase, days_to_station, find_station, speed};
+    use crate::position::PositionMode;
+
+    #[test]
+    fn test_solar_phase() {
+        let config = ConditionConfig::default();
+        assert_eq!(config.solar_phase(0.0), SolarPhase::Cazimi);
+        assert_eq!(config.solar_phase(17.0 / 60.0), SolarPhase::Cazimi);
+        assert_eq!(config.solar_phase(0.3), SolarPhase::Combust);
+        assert_eq!(config.solar_phase(8.5), SolarPhase::Combust);
+        assert_eq!(config.solar_phase(8.6), SolarPhase::UnderTheBeams);
+        assert_eq!(config.solar_phase(17.0), SolarPhase::UnderTheBeams);
+        assert_eq!(config.solar_phase(17.1), SolarPhase::Free);
+
+        let config = ConditionConfig {
+            under_the_beams: 15.0,
+            ..Default::default()
+        };
+        assert_eq!(config.solar_phase(16.0), SolarPhase::Free);
+    }
+
+    // 2024年4月水星逆行：4月1日22:14（UTC）留逆，4月25日12:54（UTC）留顺
+    #[test]
+    fn test_find_station() {
+        dotenvy::dotenv().ok();
+        let ephe_path = env::var("EPHE_PATH")
+            .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+        let t = HoroDateTime::new(2024, 4, 10, 0, 0, 0, 0.0).unwrap();
+        let station_retrograde = HoroDateTime::new(2024, 4, 1, 22, 14, 0, 0.0).unwrap();
+        let station_direct = HoroDateTime::new(2024, 4, 25, 12, 54, 0, 0.0).unwrap();
+
+        swe_set_ephe_path(&ephe_path);
+        let mode = PositionMode::Geocentric;
+        let previous = find_station(t.jd_ut1, Body::SeMercury, mode, false)
+            .unwrap()
+            .unwrap();
+        let next = find_station(t.jd_ut1, Body::SeMercury, mode, true)
+            .unwrap()
+            .unwrap();
+        swe_close();
+
+        assert!((previous - station_retrograde.jd_ut1).abs() < 1.0 / 24.0);
+        assert!((next - station_direct.jd_ut1).abs() < 1.0 / 24.0);
+    }
+
+    // 2024年4月1日22:14（UTC）水星留逆
+    #[test]
+    fn test_days_to_station() {
+        dotenvy::dotenv().ok();
+        let ephe_path = env::var("EPHE_PATH")
+            .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+        let mode = PositionMode::Geocentric;
+        let station = HoroDateTime::new(2024, 4, 1, 22, 14, 0, 0.0).unwrap();
+        swe_set_ephe_path(&ephe_path);
+        for (days_before, max_error) in [(0.5, 0.1), (1.5, 0.3)] {
+            let jd = station.jd_ut1 - days_before;
+            let v = speed(jd, Body::SeMercury, mode).unwrap();
+            let days = days_to_station(jd, v, Body::SeMercury, mode).unwrap();
+            assert!(
+                (days - days_before).abs() < max_error,
+                "{days_before}: {days}"
+            );
+        }
+
+        // 留后8天，不是留
+        let jd = station.jd_ut1 + 8.0;
+        let v = speed(jd, Body::SeMercury, mode).unwrap();
+        let days = days_to_station(jd, v, Body::SeMercury, mode).unwrap();
+        assert!(days > ConditionConfig::default().stationary_days, "{days}");
+        swe_close();
+    }
+}
@@ -1,4 +1,16 @@
-use crate::planet::PlanetName::{self, *};
+use qizheng::NodeType;
+
+use crate::{
+    aspect::{AspectConfig, ParallelConfig},
+    condition::ConditionConfig,
+    dignity::DignityScoring,
+    fixed_star::FixedStarConfig,
+    lot::LotConfig,
+    midpoint::MidpointConfig,
+    planet::PlanetName::{self, *},
+    position::PositionMode,
+    zodiac::Zodiac,
+};
 
 pub struct PlanetConfig {
     pub name: PlanetName,
@@ -45,8 +57,14 @@ impl PlanetConfig {
             Mars => PlanetConfig::new(Mars, 8, 30.0 / 60.0, 40.0 / 60.0),
             Jupiter => PlanetConfig::new(Jupiter, 9, 5.0 / 60.0, 10.0 / 60.0),
             Saturn => PlanetConfig::new(Saturn, 9, 2.0 / 60.0, 5.0 / 60.0),
+            Uranus => PlanetConfig::new(Uranus, 5, 0.5 / 60.0, 3.0 / 60.0),
+            Neptune => PlanetConfig::new(Neptune, 5, 0.3 / 60.0, 2.0 / 60.0),
+            Pluto => PlanetConfig::new(Pluto, 5, 0.2 / 60.0, 2.0 / 60.0),
+            // 地球的容许度同太阳
+            Earth => PlanetConfig::new(Earth, 15, 0.0, 0.0),
             NorthNode => PlanetConfig::new(NorthNode, 0, 0.0, 0.0),
             SouthNode => PlanetConfig::new(SouthNode, 0, 0.0, 0.0),
+            Lot(name) => PlanetConfig::new(Lot(*name), 0, 0.0, 0.0),
         }
     }
 
@@ -69,6 +87,50 @@ impl PlanetConfig {
             PlanetConfig::default_config(&SouthNode),
         ]
     }
+
+    // 天王星、海王星、冥王星的默认配置
+    pub fn default_outer_planets_configs() -> [PlanetConfig; 3] {
+        [
+            PlanetConfig::default_config(&Uranus),
+            PlanetConfig::default_config(&Neptune),
+            PlanetConfig::default_config(&Pluto),
+        ]
+    }
+
+    /// 所有行星的默认配置
+    /// * outer_planets: true，包含天王星、海王星、冥王星；false，与default_all_configs()相同
+    pub fn default_configs(outer_planets: bool) -> Vec<PlanetConfig> {
+        let mut configs: Vec<_> = Self::default_all_configs().into_iter().collect();
+        if outer_planets {
+            configs.extend(Self::default_outer_planets_configs());
+        }
+        configs
+    }
+}
+
+/// 星盘配置
+#[derive(Clone, Debug, Default)]
+pub struct HoroscopeConfig {
+    /// 必然尊贵计分方案
+    pub dignity_scoring: DignityScoring,
+    /// 月交点的计算方法，默认：平均交点
+    pub node_type: NodeType,
+    /// 黄道，默认：回归黄道
+    pub zodiac: Zodiac,
+    /// 行星位置的计算方式，默认：地心
+    pub position_mode: PositionMode,
+    /// 相位配置，默认：托勒密相位，半容许度
+    pub aspects: AspectConfig,
+    /// 平行、对等平行的配置，默认：容许度1度
+    pub parallels: ParallelConfig,
+    /// 行星状态的配置，默认：日核17分，焦伤8.5度，在日光下17度，留前后2天
+    pub conditions: ConditionConfig,
+    /// 阿拉伯点，按顺序计算，默认：不计算
+    pub lots: Vec<LotConfig>,
+    /// 恒星的配置，默认：内置的14颗恒星，合相容许度1度
+    pub fixed_stars: FixedStarConfig,
+    /// 中点的配置，默认：90度盘，容许度1.5度
+    pub midpoints: MidpointConfig,
 }
 
 #[cfg(test)]
@@ -108,6 +170,9 @@ mod tests {
         (Mars, 8, 30.0 / 60.0, 40.0 / 60.0),
         (Jupiter, 9, 5.0 / 60.0, 10.0 / 60.0),
         (Saturn, 9, 2.0 / 60.0, 5.0 / 60.0),
+        (Uranus, 5, 0.5 / 60.0, 3.0 / 60.0),
+        (Neptune, 5, 0.3 / 60.0, 2.0 / 60.0),
+        (Pluto, 5, 0.2 / 60.0, 2.0 / 60.0),
         (NorthNode, 0, 0.0, 0.0),
         (SouthNode, 0, 0.0, 0.0),
     }
@@ -149,4 +214,27 @@ mod tests {
         assert_eq!(planet_config.min, config.2);
         assert_eq!(planet_config.max, config.3);
     }
+
+    #[test]
+    fn test_default_configs() {
+        let configs = PlanetConfig::default_configs(false);
+        assert_eq!(14, configs.len());
+        assert!(
+            !configs
+                .iter()
+                .any(|c| matches!(c.name, Uranus | Neptune | Pluto))
+        );
+
+        let configs = PlanetConfig::default_configs(true);
+        assert_eq!(17, configs.len());
+        for name in [Uranus, Neptune, Pluto] {
+            let config = configs.iter().find(|c| c.name == name);
+            assert!(config.is_some(), "{:?}的配置", name);
+            let config = config.unwrap();
+            let default_config = PlanetConfig::default_config(&name);
+            assert_eq!(config.orb, default_config.orb);
+            assert_eq!(config.min, default_config.min);
+            assert_eq!(config.max, default_config.max);
+        }
+    }
 }
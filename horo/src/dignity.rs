@@ -0,0 +1,369 @@
+use swe::swe_degnorm;
+
+use crate::{
+    PlanetName::{self, *},
+    direction::PTOLEMY_TERM,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 七颗古典行星
+pub(crate) const SEVEN_PLANETS: [PlanetName; 7] =
+    [Sun, Moon, Mercury, Venus, Mars, Jupiter, Saturn];
+
+/// 必然尊贵的类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum DignityType {
+    /// 庙
+    Domicile,
+    /// 旺
+    Exaltation,
+    /// 三分
+    Triplicity,
+    /// 界
+    Term,
+    /// 面
+    Face,
+}
+
+/// 必然尊贵计分方案
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum DignityScoring {
+    /// 利立计分：庙5、旺4、三分3、界2、面1、陷-5、落-4、游走-5
+    #[default]
+    Lilly,
+    /// 只计尊贵：庙5、旺4、三分3、界2、面1，不计陷、落、游走
+    DignitiesOnly,
+}
+
+impl DignityScoring {
+    /// 各项尊贵的分值
+    pub fn score(&self, dignity: DignityType) -> i32 {
+        match dignity {
+            DignityType::Domicile => 5,
+            DignityType::Exaltation => 4,
+            DignityType::Triplicity => 3,
+            DignityType::Term => 2,
+            DignityType::Face => 1,
+        }
+    }
+
+    /// 陷的分值
+    fn detriment_score(&self) -> i32 {
+        match self {
+            DignityScoring::Lilly => -5,
+            DignityScoring::DignitiesOnly => 0,
+        }
+    }
+
+    /// 落的分值
+    fn fall_score(&self) -> i32 {
+        match self {
+            DignityScoring::Lilly => -4,
+            DignityScoring::DignitiesOnly => 0,
+        }
+    }
+
+    /// 游走的分值
+    fn peregrine_score(&self) -> i32 {
+        match self {
+            DignityScoring::Lilly => -5,
+            DignityScoring::DignitiesOnly => 0,
+        }
+    }
+}
+
+/// 行星的必然尊贵
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct EssentialDignities {
+    /// 入庙
+    pub domicile: bool,
+    /// 入旺
+    pub exaltation: bool,
+    /// 得三分
+    pub triplicity: bool,
+    /// 入界
+    pub term: bool,
+    /// 入面
+    pub face: bool,
+    /// 陷
+    pub detriment: bool,
+    /// 落
+    pub fall: bool,
+    /// 游走：无庙、旺、三分、界、面
+    pub peregrine: bool,
+    /// 总分
+    pub score: i32,
+}
+
+impl EssentialDignities {
+    /// 计算行星的必然尊贵，只计算七颗古典行星，其它星体返回None
+    /// * planet: 行星
+    /// * long: 行星黄经
+    /// * is_diurnal: 白天盘：true，夜间盘：false，用于三分主星
+    /// * scoring: 计分方案
+    pub fn new(
+        planet: PlanetName,
+        long: f64,
+        is_diurnal: bool,
+        scoring: DignityScoring,
+    ) -> Option<Self> {
+        if !SEVEN_PLANETS.contains(&planet) {
+            return None;
+        }
+
+        let sign = sign_of(long);
+
+        let domicile = domicile_ruler(sign) == planet;
+        let exaltation = exaltation_ruler(sign) == Some(planet);
+        let triplicity = triplicity_ruler(sign, is_diurnal) == planet;
+        let term = term_ruler(long) == planet;
+        let face = face_ruler(long) == planet;
+        // 对宫的庙主星，即陷
+        let detriment = domicile_ruler((sign + 6) % 12) == planet;
+        // 对宫的旺主星，即落
+        let fall = exaltation_ruler((sign + 6) % 12) == Some(planet);
+        let peregrine = !(domicile || exaltation || triplicity || term || face);
+
+        let mut score = 0;
+        for (has, dignity) in [
+            (domicile, DignityType::Domicile),
+            (exaltation, DignityType::Exaltation),
+            (triplicity, DignityType::Triplicity),
+            (term, DignityType::Term),
+            (face, DignityType::Face),
+        ] {
+            if has {
+                score += scoring.score(dignity);
+            }
+        }
+        if detriment {
+            score += scoring.detriment_score();
+        }
+        if fall {
+            score += scoring.fall_score();
+        }
+        if peregrine {
+            score += scoring.peregrine_score();
+        }
+
+        Some(Self {
+            domicile,
+            exaltation,
+            triplicity,
+            term,
+            face,
+            detriment,
+            fall,
+            peregrine,
+            score,
+        })
+    }
+}
+
+/// 黄经所在星座，0: 白羊，1: 金牛，……，11: 双鱼
+pub(crate) fn sign_of(long: f64) -> usize {
+    (swe_degnorm(long) / 30.0).floor() as usize % 12
+}
+
+/// 星座的庙主星
+pub(crate) fn domicile_ruler(sign: usize) -> PlanetName {
+    match sign % 12 {
+        0 => Mars,
+        1 => Venus,
+        2 => Mercury,
+        3 => Moon,
+        4 => Sun,
+        5 => Mercury,
+        6 => Venus,
+        7 => Mars,
+        8 => Jupiter,
+        9 => Saturn,
+        10 => Saturn,
+        _ => Jupiter,
+    }
+}
+
+/// 星座的旺主星，双子、狮子、天蝎、人马、宝瓶没有旺主星
+pub(crate) fn exaltation_ruler(sign: usize) -> Option<PlanetName> {
+    match sign % 12 {
+        0 => Some(Sun),
+        1 => Some(Moon),
+        3 => Some(Jupiter),
+        5 => Some(Mercury),
+        6 => Some(Saturn),
+        9 => Some(Mars),
+        11 => Some(Venus),
+        _ => None,
+    }
+}
+
+/// 星座的三分主星，按多罗修斯体系，白天盘取日间主星，夜间盘取夜间主星
+pub(crate) fn triplicity_ruler(sign: usize, is_diurnal: bool) -> PlanetName {
+    let (day, night) = match sign % 4 {
+        // 火象：白羊、狮子、人马
+        0 => (Sun, Jupiter),
+        // 土象：金牛、室女、摩羯
+        1 => (Venus, Moon),
+        // 风象：双子、天秤、宝瓶
+        2 => (Saturn, Mercury),
+        // 水象：巨蟹、天蝎、双鱼
+        _ => (Venus, Mars),
+    };
+    if is_diurnal { day } else { night }
+}
+
+/// 黄经所在的托勒密界的界主星
+pub(crate) fn term_ruler(long: f64) -> PlanetName {
+    let long = swe_degnorm(long);
+    PTOLEMY_TERM
+        .iter()
+        .rev()
+        .find(|term| f64::from(term.long) <= long)
+        .map(|term| term.planet)
+        .unwrap_or(PTOLEMY_TERM[0].planet)
+}
+
+/// 黄经所在的面的面主星，从白羊座第一个面起，按迦勒底序：火、日、金、水、月、土、木
+pub(crate) fn face_ruler(long: f64) -> PlanetName {
+    let chaldean_order = [Mars, Sun, Venus, Mercury, Moon, Saturn, Jupiter];
+    let n = (swe_degnorm(long) / 10.0).floor() as usize;
+    chaldean_order[n % 7]
+}
+
+/// 黄经位置上某项尊贵的主星
+pub(crate) fn dignity_ruler(
+    dignity: DignityType,
+    long: f64,
+    is_diurnal: bool,
+) -> Option<PlanetName> {
+    let sign = sign_of(long);
+    match dignity {
+        DignityType::Domicile => Some(domicile_ruler(sign)),
+        DignityType::Exaltation => exaltation_ruler(sign),
+        DignityType::Triplicity => Some(triplicity_ruler(sign, is_diurnal)),
+        DignityType::Term => Some(term_ruler(long)),
+        DignityType::Face => Some(face_ruler(long)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parameterized::parameterized;
+
+    use super::*;
+
+    #[parameterized(sign_ruler = {
+        (0, Mars), (1, Venus), (2, Mercury), (3, Moon), (4, Sun), (5, Mercury),
+        (6, Venus), (7, Mars), (8, Jupiter), (9, Saturn), (10, Saturn), (11, Jupiter),
+    })]
+    fn test_domicile_ruler(sign_ruler: (usize, PlanetName)) {
+        assert_eq!(domicile_ruler(sign_ruler.0), sign_ruler.1);
+    }
+
+    #[parameterized(sign_ruler = {
+        (0, Some(Sun)), (1, Some(Moon)), (2, None), (3, Some(Jupiter)), (4, None), (5, Some(Mercury)),
+        (6, Some(Saturn)), (7, None), (8, None), (9, Some(Mars)), (10, None), (11, Some(Venus)),
+    })]
+    fn test_exaltation_ruler(sign_ruler: (usize, Option<PlanetName>)) {
+        assert_eq!(exaltation_ruler(sign_ruler.0), sign_ruler.1);
+    }
+
+    #[test]
+    fn test_triplicity_ruler() {
+        assert_eq!(triplicity_ruler(4, true), Sun);
+        assert_eq!(triplicity_ruler(4, false), Jupiter);
+        assert_eq!(triplicity_ruler(9, true), Venus);
+        assert_eq!(triplicity_ruler(9, false), Moon);
+        assert_eq!(triplicity_ruler(10, true), Saturn);
+        assert_eq!(triplicity_ruler(10, false), Mercury);
+        assert_eq!(triplicity_ruler(7, true), Venus);
+        assert_eq!(triplicity_ruler(7, false), Mars);
+    }
+
+    #[parameterized(long_ruler = {
+        (0.0, Jupiter), (6.99, Jupiter), (7.0, Venus), (29.9, Saturn),
+        (30.0, Venus), (30.0 * 4.0 + 20.5, Jupiter), (359.9, Saturn),
+    })]
+    fn test_term_ruler(long_ruler: (f64, PlanetName)) {
+        assert_eq!(term_ruler(long_ruler.0), long_ruler.1);
+    }
+
+    #[parameterized(long_ruler = {
+        (0.0, Mars), (10.0, Sun), (20.0, Venus), (30.0, Mercury), (45.0, Moon),
+        (55.0, Saturn), (60.0, Jupiter), (359.0, Mars),
+    })]
+    fn test_face_ruler(long_ruler: (f64, PlanetName)) {
+        assert_eq!(face_ruler(long_ruler.0), long_ruler.1);
+    }
+
+    #[test]
+    fn test_dignity_ruler() {
+        assert_eq!(dignity_ruler(DignityType::Domicile, 19.0, true), Some(Mars));
+        assert_eq!(
+            dignity_ruler(DignityType::Exaltation, 19.0, true),
+            Some(Sun)
+        );
+        assert_eq!(dignity_ruler(DignityType::Exaltation, 79.0, true), None);
+        assert_eq!(
+            dignity_ruler(DignityType::Triplicity, 19.0, false),
+            Some(Jupiter)
+        );
+        assert_eq!(dignity_ruler(DignityType::Term, 19.0, true), Some(Mercury));
+        assert_eq!(dignity_ruler(DignityType::Face, 19.0, true), Some(Sun));
+    }
+
+    #[test]
+    fn test_essential_dignities_lilly() {
+        // 太阳在白羊19度：旺、白天盘三分、界（水星界15-22度，无）、面（日面10-20度）
+        let dignities = EssentialDignities::new(Sun, 19.0, true, DignityScoring::Lilly).unwrap();
+        assert!(!dignities.domicile);
+        assert!(dignities.exaltation);
+        assert!(dignities.triplicity);
+        assert!(!dignities.term);
+        assert!(dignities.face);
+        assert!(!dignities.detriment);
+        assert!(!dignities.fall);
+        assert!(!dignities.peregrine);
+        assert_eq!(dignities.score, 4 + 3 + 1);
+
+        // 土星在白羊5度，夜间盘：落，游走
+        let dignities = EssentialDignities::new(Saturn, 5.0, false, DignityScoring::Lilly).unwrap();
+        assert!(dignities.fall);
+        assert!(dignities.peregrine);
+        assert_eq!(dignities.score, -4 - 5);
+
+        // 金星在白羊：陷
+        let dignities = EssentialDignities::new(Venus, 8.0, true, DignityScoring::Lilly).unwrap();
+        assert!(dignities.detriment);
+        assert!(dignities.term);
+        assert!(!dignities.peregrine);
+        assert_eq!(dignities.score, -5 + 2);
+    }
+
+    #[test]
+    fn test_essential_dignities_dignities_only() {
+        let dignities =
+            EssentialDignities::new(Saturn, 5.0, false, DignityScoring::DignitiesOnly).unwrap();
+        assert!(dignities.fall);
+        assert!(dignities.peregrine);
+        assert_eq!(dignities.score, 0);
+    }
+
+    #[test]
+    fn test_essential_dignities_not_planet() {
+        assert!(EssentialDignities::new(ASC, 0.0, true, DignityScoring::Lilly).is_none());
+        assert!(EssentialDignities::new(NorthNode, 0.0, true, DignityScoring::Lilly).is_none());
+        assert!(EssentialDignities::new(PartOfFortune, 0.0, true, DignityScoring::Lilly).is_none());
+    }
+}
@@ -22,17 +22,19 @@ use swe::swe_degnorm;
 use crate::{
     Direction, Error, Horoscope, Planet, PlanetName, Promittor,
     direction::{
-        MAX_ARC, arc_to_date,
+        MAX_ARC, TimeKey,
         utils::{calc_asc_oa, calc_promittor_oa, calc_promittor_od},
     },
 };
 
 /// 计算ASC的主向推运方向
 /// * horo: 星盘数据
-/// * ephe_path: 星历表文件路径
+/// * promittors: 推运的相位点
+/// * time_key: 方向弧的时间换算
 pub(crate) fn asc_direction(
     horo: &Horoscope,
     promittors: &[(Promittor, Planet)],
+    time_key: &TimeKey,
 ) -> Result<Vec<Direction>, Error> {
     let asc_oa = calc_asc_oa(horo.mc.ra);
 
@@ -52,7 +54,7 @@ pub(crate) fn asc_direction(
         let arc = swe_degnorm(promittor_oa - asc_oa);
 
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(PlanetName::ASC, promittor, arc, t);
             directions.push(direction);
         }
@@ -69,7 +71,7 @@ pub(crate) fn asc_direction(
 
         let arc = swe_degnorm(-arc);
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(PlanetName::ASC, promittor, -arc, t);
             directions.push(direction);
         }
@@ -82,6 +84,7 @@ pub(crate) fn asc_direction(
 pub(crate) fn dsc_direction(
     horo: &Horoscope,
     promittors: &[(Promittor, Planet)],
+    time_key: &TimeKey,
 ) -> Result<Vec<Direction>, Error> {
     let dsc_od = swe_degnorm(horo.ic.ra + 90.0);
 
@@ -101,7 +104,7 @@ pub(crate) fn dsc_direction(
         let arc = swe_degnorm(promittor_od - dsc_od);
 
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(PlanetName::DSC, promittor, arc, t);
             directions.push(direction);
         }
@@ -118,7 +121,7 @@ pub(crate) fn dsc_direction(
 
         let arc = swe_degnorm(-arc);
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(PlanetName::DSC, promittor, -arc, t);
             directions.push(direction);
         }
@@ -2,13 +2,14 @@ use swe::swe_degnorm;
 
 use crate::{
     Direction, Error, Horoscope, Planet, PlanetName, Promittor,
-    direction::{MAX_ARC, arc_to_date},
+    direction::{MAX_ARC, TimeKey},
 };
 
 // 计算MC的推运
 pub(crate) fn mc_direction(
     horo: &Horoscope,
     promittors: &[(Promittor, Planet)],
+    time_key: &TimeKey,
 ) -> Result<Vec<Direction>, Error> {
     // 找出所有 Term 的索引
     let term_indices: Vec<usize> = promittors
@@ -25,7 +26,7 @@ pub(crate) fn mc_direction(
         let arc = swe_degnorm(planet.ra - horo.mc.ra);
 
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(PlanetName::MC, promittor, arc, t);
 
             directions.push(direction);
@@ -43,7 +44,7 @@ pub(crate) fn mc_direction(
 
         let arc = swe_degnorm(-arc);
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(PlanetName::MC, promittor, -arc, t);
             directions.push(direction);
         }
@@ -55,6 +56,7 @@ pub(crate) fn mc_direction(
 pub(crate) fn ic_direction(
     horo: &Horoscope,
     promittors: &[(Promittor, Planet)],
+    time_key: &TimeKey,
 ) -> Result<Vec<Direction>, Error> {
     // 找出所有 Term 的索引
     let term_indices: Vec<usize> = promittors
@@ -71,7 +73,7 @@ pub(crate) fn ic_direction(
         let arc = swe_degnorm(planet.ra - horo.ic.ra);
 
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(PlanetName::IC, promittor, arc, t);
 
             directions.push(direction);
@@ -89,7 +91,7 @@ pub(crate) fn ic_direction(
 
         let arc = swe_degnorm(-arc);
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(PlanetName::IC, promittor, -arc, t);
             directions.push(direction);
         }
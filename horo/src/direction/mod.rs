@@ -8,21 +8,27 @@ pub(crate) use term::{PTOLEMY_TERM, PtolemyTerm};
 
 use geo_position::GeoPosition;
 use horo_date_time::{HoroDateTime, horo_date_time};
+use swe::{Body, Flag, swe_calc_ut, swe_close, swe_set_ephe_path};
 
 use crate::{
-    Error, Horoscope, HouseName, PlanetConfig, PlanetName,
+    Error, Horoscope, HoroscopeConfig, HouseName, PlanetConfig, PlanetName,
     direction::{
         asc::{asc_direction, dsc_direction},
         mc::{ic_direction, mc_direction},
         planet::planet_direction,
         utils::{planet_to_planet_direction, promittors_of_planets},
     },
+    progression::{NAIBOD, TROPICAL_YEAR},
+    utils::{mod180, newton_iteration},
 };
 
 const MAX_ARC: f64 = 120.0;
 
+// Cardan的时间换算：59分12秒为1年
+const CARDAN: f64 = 59.0 / 60.0 + 12.0 / 3600.0;
+
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
@@ -81,6 +87,25 @@ pub enum Promittor {
     // 宫头
     Cusp(u8),
 }
+
+/// 主向推运的时间换算，即赤经上的弧与年数的换算
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum DirectionKey {
+    /// 托勒密：1度为1年
+    #[default]
+    Ptolemy,
+    /// Naibod：太阳的平均日行度，59分08秒为1年
+    Naibod,
+    /// Cardan：59分12秒为1年
+    Cardan,
+    /// 真太阳弧（Placidus）：出生后第n天太阳在赤经上实际走过的弧为第n年
+    TrueSolarArc,
+    /// Simmonite：出生当天太阳在赤经上的日行度为1年
+    Simmonite,
+}
+
 // 推运点
 // ASC
 // MC
@@ -93,6 +118,8 @@ pub fn direction_process(
     native_date: HoroDateTime,
     geo: GeoPosition,
     planets_config: &[PlanetConfig],
+    config: &HoroscopeConfig,
+    key: DirectionKey,
     ephe_path: &str,
 ) -> Result<Vec<Direction>, Error> {
     // 计算原星盘
@@ -101,27 +128,107 @@ pub fn direction_process(
         geo,
         HouseName::Regiomontanus,
         planets_config,
+        config,
         ephe_path,
     )?;
 
     let promittors = promittors_of_planets(&horo);
+    let time_key = TimeKey::new(key, native_date, ephe_path)?;
 
-    let mut directions = mc_direction(&horo, &promittors)?;
-    directions.extend(asc_direction(&horo, &promittors)?);
-    directions.extend(dsc_direction(&horo, &promittors)?);
-    directions.extend(ic_direction(&horo, &promittors)?);
+    let mut directions = mc_direction(&horo, &promittors, &time_key)?;
+    directions.extend(asc_direction(&horo, &promittors, &time_key)?);
+    directions.extend(dsc_direction(&horo, &promittors, &time_key)?);
+    directions.extend(ic_direction(&horo, &promittors, &time_key)?);
 
     for significator in &horo.planets {
-        directions.extend(planet_direction(&horo, significator, &promittors)?);
+        directions.extend(planet_direction(
+            &horo,
+            significator,
+            &promittors,
+            &time_key,
+        )?);
     }
 
-    directions.extend(planet_direction(&horo, &horo.part_of_fortune, &promittors)?);
+    directions.extend(planet_direction(
+        &horo,
+        &horo.part_of_fortune,
+        &promittors,
+        &time_key,
+    )?);
 
     directions.sort_by(|a, b| a.arc.abs().total_cmp(&b.arc.abs()));
 
     Ok(directions)
 }
 
+// 按时间换算将方向弧换算为日期
+pub(crate) struct TimeKey<'a> {
+    key: DirectionKey,
+    native_date: HoroDateTime,
+    // 出生时太阳的赤经
+    sun_ra: f64,
+    // 出生时太阳在赤经上的日行度
+    sun_ra_speed: f64,
+    ephe_path: &'a str,
+}
+
+impl<'a> TimeKey<'a> {
+    fn new(
+        key: DirectionKey,
+        native_date: HoroDateTime,
+        ephe_path: &'a str,
+    ) -> Result<Self, Error> {
+        let (sun_ra, sun_ra_speed) = match key {
+            DirectionKey::TrueSolarArc | DirectionKey::Simmonite => {
+                sun_ra_and_speed(native_date.jd_ut1, ephe_path)?
+            }
+            _ => (0.0, 0.0),
+        };
+        Ok(Self {
+            key,
+            native_date,
+            sun_ra,
+            sun_ra_speed,
+            ephe_path,
+        })
+    }
+
+    /// 方向弧对应的日期
+    /// * arc: 方向弧，单位：度，不小于0
+    pub(crate) fn arc_to_date(&self, arc: f64) -> Result<HoroDateTime, Error> {
+        let years = match self.key {
+            DirectionKey::Ptolemy => return arc_to_date(arc, &self.native_date),
+            DirectionKey::Naibod => arc / NAIBOD,
+            DirectionKey::Cardan => arc / CARDAN,
+            DirectionKey::Simmonite => arc / self.sun_ra_speed,
+            DirectionKey::TrueSolarArc => {
+                // 出生后太阳在赤经上走过arc所用的天数，即年数
+                let jd0 = self.native_date.jd_ut1;
+                newton_iteration(arc / NAIBOD, |days| {
+                    let ra = sun_ra_and_speed(jd0 + days, self.ephe_path)?.0;
+                    Ok(mod180(ra - self.sun_ra - arc))
+                })?
+            }
+        };
+
+        Ok(self.native_date.plus_days(years * TROPICAL_YEAR)?)
+    }
+}
+
+// 太阳的赤经及其日行度
+fn sun_ra_and_speed(jd_ut1: f64, ephe_path: &str) -> Result<(f64, f64), Error> {
+    swe_set_ephe_path(ephe_path);
+    let xx = swe_calc_ut(
+        jd_ut1,
+        Body::SeSun,
+        &[Flag::SeflgEquatorial, Flag::SeflgSpeed],
+    )
+    .map_err(|e| Error::Function(format!("计算太阳赤经错误:{e}")))?;
+    swe_close();
+    Ok((xx[0], xx[3]))
+}
+
+// 托勒密时间换算：1度为1年
 fn arc_to_date(arc: f64, native_date: &HoroDateTime) -> Result<HoroDateTime, Error> {
     let y = arc.floor();
 
@@ -157,6 +264,7 @@ mod tests {
     use super::*;
     use horo_date_time::HoroDateTime;
     use std::env;
+    use swe::swe_degnorm;
 
     pub(crate) fn get_ephe_path() -> String {
         dotenvy::dotenv().ok();
@@ -217,6 +325,71 @@ mod tests {
         assert_eq!(result.day, 29);
     }
 
+    #[test]
+    fn test_time_key_ptolemy() {
+        let native_date = HoroDateTime::new(2000, 1, 1, 12, 0, 0, 8.0).unwrap();
+        let time_key = TimeKey::new(DirectionKey::Ptolemy, native_date, "").unwrap();
+        let result = time_key.arc_to_date(10.5).unwrap();
+        let expected = arc_to_date(10.5, &native_date).unwrap();
+
+        assert!((result.jd_utc - expected.jd_utc).abs() * 24.0 * 3600.0 < 1.0);
+    }
+
+    #[test]
+    fn test_time_key_naibod_and_cardan() {
+        let native_date = HoroDateTime::new(2000, 1, 1, 12, 0, 0, 8.0).unwrap();
+
+        let time_key = TimeKey::new(DirectionKey::Naibod, native_date, "").unwrap();
+        let result = time_key.arc_to_date(NAIBOD * 10.0).unwrap();
+        let days = result.jd_utc - native_date.jd_utc;
+        assert!((days - 10.0 * TROPICAL_YEAR).abs() < 1e-3, "{days}");
+
+        let time_key = TimeKey::new(DirectionKey::Cardan, native_date, "").unwrap();
+        let result = time_key.arc_to_date(CARDAN * 10.0).unwrap();
+        let days = result.jd_utc - native_date.jd_utc;
+        assert!((days - 10.0 * TROPICAL_YEAR).abs() < 1e-3, "{days}");
+
+        // Cardan的年度弧比Naibod大，同样的弧对应的时间更早
+        let naibod = TimeKey::new(DirectionKey::Naibod, native_date, "").unwrap();
+        assert!(
+            time_key.arc_to_date(30.0).unwrap().jd_utc < naibod.arc_to_date(30.0).unwrap().jd_utc
+        );
+    }
+
+    #[test]
+    fn test_time_key_simmonite() {
+        let ephe_path = get_ephe_path();
+        let native_date = HoroDateTime::new(2000, 1, 1, 12, 0, 0, 8.0).unwrap();
+        let time_key = TimeKey::new(DirectionKey::Simmonite, native_date, &ephe_path).unwrap();
+
+        // 一月初太阳在赤经上的日行度大于1度
+        assert!(time_key.sun_ra_speed > 1.0, "{}", time_key.sun_ra_speed);
+
+        let result = time_key.arc_to_date(time_key.sun_ra_speed).unwrap();
+        let days = result.jd_utc - native_date.jd_utc;
+        assert!((days - TROPICAL_YEAR).abs() < 1e-3, "{days}");
+    }
+
+    #[test]
+    fn test_time_key_true_solar_arc() {
+        let ephe_path = get_ephe_path();
+        let native_date = HoroDateTime::new(2000, 1, 1, 12, 0, 0, 8.0).unwrap();
+        let time_key = TimeKey::new(DirectionKey::TrueSolarArc, native_date, &ephe_path).unwrap();
+
+        let arc = 30.0;
+        let result = time_key.arc_to_date(arc).unwrap();
+        let years = (result.jd_utc - native_date.jd_utc) / TROPICAL_YEAR;
+
+        // 出生后第years天太阳在赤经上走过的弧等于方向弧
+        let (ra, _) = sun_ra_and_speed(native_date.jd_ut1 + years, &ephe_path).unwrap();
+        assert!(
+            (swe_degnorm(ra - time_key.sun_ra) - arc).abs() < 1e-6,
+            "{ra}"
+        );
+        // 冬季太阳在赤经上移动较快，所用年数少于Naibod
+        assert!(years < arc / NAIBOD, "{years}");
+    }
+
     #[test]
     fn test_direction_new() {
         let date = HoroDateTime::new(2000, 1, 1, 12, 0, 0, 8.0).unwrap();
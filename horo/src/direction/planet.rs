@@ -2,13 +2,14 @@ use swe::swe_degnorm;
 
 use crate::{
     Direction, Error, Horoscope, Planet, Promittor,
-    direction::{MAX_ARC, arc_to_date, planet_to_planet_direction},
+    direction::{MAX_ARC, TimeKey, planet_to_planet_direction},
 };
 
 pub(super) fn planet_direction(
     horo: &Horoscope,
     significator: &Planet,
     promittors: &[(Promittor, Planet)],
+    time_key: &TimeKey,
 ) -> Result<Vec<Direction>, Error> {
     // 找出所有 Term 的索引
     let term_indices: Vec<usize> = promittors
@@ -29,7 +30,7 @@ pub(super) fn planet_direction(
 
         // 正向弧度
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(significator.name, promittor, arc, t);
             directions.push(direction);
         }
@@ -46,7 +47,7 @@ pub(super) fn planet_direction(
 
         let arc = swe_degnorm(-arc);
         if arc < MAX_ARC {
-            let t = arc_to_date(arc, &horo.date)?;
+            let t = time_key.arc_to_date(arc)?;
             let direction = Direction::new(significator.name, promittor, -arc, t);
             directions.push(direction);
         }
@@ -65,7 +65,11 @@ pub(super) fn planet_to_planet_direction(
         &[horo.geo.long, horo.geo.lat, 0.0],
         0.0,
         0.0,
-        &[significator.long, significator.lat, 0.0],
+        &[
+            swe_degnorm(significator.long + horo.ayanamsa),
+            significator.lat,
+            0.0,
+        ],
     );
 
     let significator_ad = calc_ad(significator.dec, horo.geo.lat)?;
@@ -134,6 +138,8 @@ pub(super) fn planet_to_planet_direction(
 
 pub(crate) fn promittors_of_planets(horo: &Horoscope) -> Vec<(Promittor, Planet)> {
     let eps = horo.eps;
+    // 黄经是所选黄道的经度，计算赤经、赤纬时换回回归黄道
+    let ayanamsa = horo.ayanamsa;
 
     // 相位点使用此值，这个值实际不会被用到，仅起占位作用
     let planet_config = PlanetConfig::default_config(&PlanetName::ASC);
@@ -142,6 +148,7 @@ pub(crate) fn promittors_of_planets(horo: &Horoscope) -> Vec<(Promittor, Planet)
         .planets
         .iter()
         .chain(std::iter::once(&horo.part_of_fortune))
+        .chain(horo.lots.iter().map(|lot| &lot.planet))
         .flat_map(|planet| {
             let mut promittors = vec![];
             // 计算合相
@@ -153,9 +160,9 @@ pub(crate) fn promittors_of_planets(horo: &Horoscope) -> Vec<(Promittor, Planet)
             //     DirectionType::Conjunction,
             // ));
 
-            // 映点
-            let antiscoins_long = swe_degnorm(180.0 - planet.long);
-            let equator = swe_cotrans(antiscoins_long, 0.0, 1.0, -eps);
+            // 映点，以回归黄道的冬夏至轴对称，再换回所选黄道
+            let antiscoins_long = swe_degnorm(180.0 - (planet.long + ayanamsa) - ayanamsa);
+            let equator = swe_cotrans(swe_degnorm(antiscoins_long + ayanamsa), 0.0, 1.0, -eps);
             let p = Planet::new(
                 planet.name,
                 antiscoins_long,
@@ -174,7 +181,7 @@ pub(crate) fn promittors_of_planets(horo: &Horoscope) -> Vec<(Promittor, Planet)
 
             // 反映点
             let contraantiscias_long = swe_degnorm(180.0 + antiscoins_long);
-            let equator = swe_cotrans(contraantiscias_long, 0.0, 1.0, -eps);
+            let equator = swe_cotrans(swe_degnorm(contraantiscias_long + ayanamsa), 0.0, 1.0, -eps);
             let p = Planet::new(
                 planet.name,
                 contraantiscias_long,
@@ -190,7 +197,7 @@ pub(crate) fn promittors_of_planets(horo: &Horoscope) -> Vec<(Promittor, Planet)
                 .into_iter()
                 .map(|aspect| {
                     let aspect_long = swe_degnorm(planet.long + aspect as f64);
-                    let equator = swe_cotrans(aspect_long, 0.0, 1.0, -eps);
+                    let equator = swe_cotrans(swe_degnorm(aspect_long + ayanamsa), 0.0, 1.0, -eps);
                     let p = Planet::new(
                         planet.name,
                         aspect_long,
@@ -225,7 +232,7 @@ pub(crate) fn promittors_of_planets(horo: &Horoscope) -> Vec<(Promittor, Planet)
     let ptolemy_term: Vec<(Promittor, Planet)> = PTOLEMY_TERM
         .into_iter()
         .map(|PtolemyTerm { planet, long }| {
-            let equator = swe_cotrans(f64::from(long), 0.0, 1.0, -eps);
+            let equator = swe_cotrans(swe_degnorm(f64::from(long) + ayanamsa), 0.0, 1.0, -eps);
             let ra = equator[0];
             let dec = equator[1];
             let p = Planet::new(
@@ -250,7 +257,10 @@ pub(crate) fn promittors_of_planets(horo: &Horoscope) -> Vec<(Promittor, Planet)
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{HouseName, PlanetConfig, direction::tests::get_ephe_path};
+    use crate::{
+        Ayanamsa, HoroscopeConfig, HouseName, LotConfig, LotName, PlanetConfig, Zodiac,
+        direction::tests::get_ephe_path,
+    };
     use geo_position::GeoPosition;
     use horo_date_time::HoroDateTime;
 
@@ -446,6 +456,7 @@ mod tests {
             geo,
             HouseName::Regiomontanus,
             &planet_configs,
+            &HoroscopeConfig::default(),
             &ephe_path,
         )
         .unwrap()
@@ -612,6 +623,42 @@ mod tests {
         assert!(!promittors.is_empty(), "应生成promittor列表");
     }
 
+    #[test]
+    fn test_promittors_of_planets_contains_lots() {
+        let ephe_path = get_ephe_path();
+        let date = HoroDateTime::new(2000, 1, 1, 12, 0, 0, 8.0).unwrap();
+        let geo = GeoPosition::new(116.4, 39.9).unwrap();
+        let config = HoroscopeConfig {
+            lots: vec![LotConfig::builtin(LotName::Spirit).unwrap()],
+            ..Default::default()
+        };
+        let horo = Horoscope::new(
+            date,
+            geo,
+            HouseName::Regiomontanus,
+            &PlanetConfig::default_all_configs(),
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+
+        let promittors = promittors_of_planets(&horo);
+
+        let spirit = PlanetName::Lot(LotName::Spirit);
+        assert!(
+            promittors
+                .iter()
+                .any(|(p, _)| *p == Promittor::Conjunction(spirit)),
+            "应包含阿拉伯点的合相"
+        );
+        assert!(
+            promittors
+                .iter()
+                .any(|(p, _)| *p == Promittor::Opposition(spirit)),
+            "应包含阿拉伯点的相位"
+        );
+    }
+
     #[test]
     fn test_promittors_of_planets_contains_conjunctions() {
         let horo = create_test_horoscope(2000, 1, 1, 12, 0, 0, 8.0, 116.4, 39.9);
@@ -683,6 +730,50 @@ mod tests {
         assert!(has_contraantiscias, "应包含反映点");
     }
 
+    #[test]
+    fn test_promittors_of_planets_sidereal_antiscoins() {
+        let ephe_path = get_ephe_path();
+        let date = HoroDateTime::new(2000, 1, 1, 12, 0, 0, 8.0).unwrap();
+        let geo = GeoPosition::new(116.4, 39.9).unwrap();
+        let config = HoroscopeConfig {
+            zodiac: Zodiac::Sidereal(Ayanamsa::Lahiri),
+            ..Default::default()
+        };
+        let horo = Horoscope::new(
+            date,
+            geo,
+            HouseName::Regiomontanus,
+            &PlanetConfig::default_all_configs(),
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+        assert!(horo.ayanamsa > 20.0);
+
+        let promittors = promittors_of_planets(&horo);
+
+        // 映点与行星以冬夏至轴对称，黄纬为0时两者赤纬相同
+        for planet in &horo.planets {
+            let (_, antiscoin) = promittors
+                .iter()
+                .find(|(p, _)| *p == Promittor::Antiscoins(planet.name))
+                .unwrap();
+            let dec = swe_cotrans(
+                swe_degnorm(planet.long + horo.ayanamsa),
+                0.0,
+                1.0,
+                -horo.eps,
+            )[1];
+            assert!(
+                (antiscoin.dec - dec).abs() < 1e-6,
+                "{:?}的映点赤纬应与行星赤纬相同，期望 {}，实际 {}",
+                planet.name,
+                dec,
+                antiscoin.dec
+            );
+        }
+    }
+
     #[test]
     fn test_promittors_of_planets_contains_terms() {
         let horo = create_test_horoscope(2000, 1, 1, 12, 0, 0, 8.0, 116.4, 39.9);
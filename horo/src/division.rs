@@ -0,0 +1,114 @@
+use swe::swe_degnorm;
+
+use crate::{Aspect, AspectConfig, Planet, PlanetName};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 星体的十二分部、九分部位置
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct DivisionalPosition {
+    /// 星体
+    pub name: PlanetName,
+    /// 十二分部（Dodecatemorion）的黄经
+    pub dodecatemorion: f64,
+    /// 九分部（Navamsa）的黄经
+    pub navamsa: f64,
+}
+
+impl DivisionalPosition {
+    pub fn new(name: PlanetName, long: f64) -> Self {
+        Self {
+            name,
+            dodecatemorion: dodecatemorion(long),
+            navamsa: navamsa(long),
+        }
+    }
+}
+
+/// 十二分部：星体在所落星座内的度数乘以12，从所落星座起算
+pub fn dodecatemorion(long: f64) -> f64 {
+    let sign_start = (long / 30.0).floor() * 30.0;
+    swe_degnorm(sign_start + (long - sign_start) * 12.0)
+}
+
+/// 九分部：每个星座九等分，每分3度20分，火象星座从白羊座、土象星座从摩羯座、
+/// 风象星座从天秤座、水象星座从巨蟹座起算，等价于黄经乘以9
+pub fn navamsa(long: f64) -> f64 {
+    swe_degnorm(long * 9.0)
+}
+
+/// 十二分部、九分部与本命星体的相位
+/// * points: 计算分部位置的星体，也是与之成相位的本命星体
+///
+/// 返回值：(十二分部的相位, 九分部的相位)，相位的p0为本命星体，p1为分部位置所属的星体
+pub(crate) fn calc_divisional_aspects(
+    points: &[&Planet],
+    config: &AspectConfig,
+) -> (Vec<Aspect>, Vec<Aspect>) {
+    // 分部位置的移动速度是星体的12倍、9倍，按比较盘计算相位，分部位置为动的一方
+    let divisional_points = |f: fn(f64) -> f64, factor: f64| -> Vec<Planet> {
+        points
+            .iter()
+            .map(|&p| {
+                let mut planet = *p;
+                planet.long = f(p.long);
+                planet.speed = p.speed * factor;
+                planet
+            })
+            .collect()
+    };
+    let dodecatemoria = divisional_points(dodecatemorion, 12.0);
+    let navamsas = divisional_points(navamsa, 9.0);
+
+    let aspects = |divisional_points: &[Planet]| -> Vec<Aspect> {
+        points
+            .iter()
+            .flat_map(|&p0| {
+                divisional_points
+                    .iter()
+                    .filter_map(move |p1| p0.has_aspect_with_config(p1, true, config))
+            })
+            .collect()
+    };
+
+    (aspects(&dodecatemoria), aspects(&navamsas))
+}
+
+#[cfg(test)]
+mod tests {
+    use parameterized::parameterized;
+
+    use super::{dodecatemorion, navamsa};
+
+    #[parameterized(case = {
+        (0.0, 0.0),
+        (2.5, 30.0),
+        (10.0, 120.0),
+        (29.0, 348.0),
+        (45.0, 210.0),
+        (355.0, 270.0),
+    })]
+    fn test_dodecatemorion(case: (f64, f64)) {
+        assert!((dodecatemorion(case.0) - case.1).abs() < 1e-9);
+    }
+
+    // 白羊座0度起于白羊座，金牛座0度起于摩羯座，双子座0度起于天秤座，巨蟹座0度起于巨蟹座
+    #[parameterized(case = {
+        (0.0, 0.0),
+        (3.0 + 20.0 / 60.0, 30.0),
+        (30.0, 270.0),
+        (60.0, 180.0),
+        (90.0, 90.0),
+        (29.0, 261.0),
+        (359.0, 351.0),
+    })]
+    fn test_navamsa(case: (f64, f64)) {
+        assert!((navamsa(case.0) - case.1).abs() < 1e-9);
+    }
+}
@@ -7,6 +7,16 @@ pub enum Error {
     Function(String),
     // 无效的小限时间
     InvalidProfectionDateTime(String),
+    // 无效的推运时间
+    InvalidProcessDateTime(String),
+    // 无效的阿拉伯点公式
+    InvalidLot(String),
+    // 恒星表中没有的恒星
+    InvalidFixedStar(String),
+    // 无效的宫位
+    InvalidHouse(String),
+    // 无效的谐波数
+    InvalidHarmonic(String),
 }
 
 impl From<horo_date_time::Error> for Error {
@@ -23,7 +33,7 @@ impl From<qizheng::Error> for Error {
     fn from(value: qizheng::Error) -> Self {
         match value {
             qizheng::Error::Function(s) => Error::Function(s),
-            qizheng::Error::InvalidProcessDateTime(s) => Error::InvalidProfectionDateTime(s),
+            qizheng::Error::InvalidProcessDateTime(s) => Error::InvalidProcessDateTime(s),
             qizheng::Error::InvalidDateTime(s) => Error::InvalidDateTime(s),
             qizheng::Error::InvalidZone(s) => Error::InvalidZone(s),
         }
@@ -35,8 +45,13 @@ impl Display for Error {
         let s = match self {
             Error::Function(s) => s,
             Error::InvalidProfectionDateTime(s) => s,
+            Error::InvalidProcessDateTime(s) => s,
             Error::InvalidDateTime(s) => s,
             Error::InvalidZone(s) => s,
+            Error::InvalidLot(s) => s,
+            Error::InvalidFixedStar(s) => s,
+            Error::InvalidHouse(s) => s,
+            Error::InvalidHarmonic(s) => s,
         };
         write!(f, "{}", s)
     }
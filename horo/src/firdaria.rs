@@ -1,4 +1,4 @@
-use crate::{Error, Horoscope, HouseName, PlanetConfig, PlanetName};
+use crate::{Error, Horoscope, HoroscopeConfig, HouseName, PlanetConfig, PlanetName};
 use geo_position::GeoPosition;
 use horo_date_time::HoroDateTime;
 
@@ -36,6 +36,7 @@ pub fn firdaria_process(
         geo,
         HouseName::Alcabitus,
         planets_config,
+        &HoroscopeConfig::default(),
         ephe_path,
     )?;
 
@@ -1,10 +1,17 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::{Arc, Mutex, OnceLock},
+};
+
 use qizheng::{DistanceStarConfig, LunarMansionsName, calc_distance_star_long, calc_xiu_degree};
-use swe::{Flag, swe_close, swe_fixstar2_ut, swe_set_ephe_path};
+use swe::{Flag, swe_close, swe_degnorm, swe_fixstar2_ut, swe_set_ephe_path};
 
-use crate::error::Error;
+use crate::{Planet, PlanetName, error::Error, utils::included_angle};
 
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
@@ -32,24 +39,34 @@ pub enum FixedStarName {
     轩辕十四,
 }
 
-/// 恒星的黄道经度
-#[cfg_attr(feature = "serde", derive(Serialize))]
-#[cfg_attr(feature = "swagger", derive(ToSchema))]
-#[derive(Clone, Copy, Debug)]
-pub struct FixedStar {
-    /// 恒星的名称
-    pub fixed_star: FixedStarName,
-    /// 恒星距星的黄道经度
-    pub long: f64,
-    /// 行星在黄道上的入宿名
-    pub xiu: LunarMansionsName,
-    /// 行星在黄道上的入宿度
-    pub xiu_degree: f64,
-    /// 描述
-    pub desc: &'static str,
-}
-
 impl FixedStarName {
+    /// 内置的全部恒星
+    pub fn builtin_stars() -> [FixedStarName; 14] {
+        [
+            角宿一,
+            大角星,
+            氐宿一,
+            氐宿四,
+            心宿二,
+            北门师落,
+            大陵五,
+            毕宿五,
+            五车二,
+            参宿四,
+            天狼星,
+            北河三,
+            南河三,
+            轩辕十四,
+        ]
+    }
+
+    // sefstars.txt中的星名为nomenclature的内置恒星
+    fn from_swe_name(nomenclature: &str) -> Option<Self> {
+        Self::builtin_stars()
+            .into_iter()
+            .find(|star| star.to_swe_name() == nomenclature)
+    }
+
     fn to_swe_name(&self) -> &'static str {
         match self {
             氐宿一 => "al-2Lib",
@@ -69,6 +86,31 @@ impl FixedStarName {
         }
     }
 
+    // sefstars.txt中的传统名及星等，内置恒星不必读取恒星表
+    fn catalogue_entry(&self) -> CatalogueEntry {
+        let (name, magnitude) = match self {
+            角宿一 => ("Spica", 0.97),
+            大角星 => ("Arcturus", -0.05),
+            氐宿一 => ("Zubenelgenubi", 2.75),
+            氐宿四 => ("Zubeneshamali", 2.62),
+            心宿二 => ("Antares", 0.91),
+            北门师落 => ("Fomalhaut", 1.16),
+            大陵五 => ("Algol", 2.12),
+            毕宿五 => ("Aldebaran", 0.86),
+            五车二 => ("Capella", 0.08),
+            参宿四 => ("Betelgeuse", 0.42),
+            天狼星 => ("Sirius", -1.46),
+            北河三 => ("Pollux", 1.14),
+            南河三 => ("Procyon", 0.37),
+            轩辕十四 => ("Regulus", 1.4),
+        };
+        CatalogueEntry {
+            name: name.to_string(),
+            nomenclature: self.to_swe_name().to_string(),
+            magnitude,
+        }
+    }
+
     /// 描述
     pub fn desc(&self) -> &'static str {
         match self {
@@ -90,8 +132,232 @@ impl FixedStarName {
     }
 }
 
+/// 恒星表
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum FixedStarCatalogue {
+    /// 内置的14颗恒星
+    #[default]
+    Builtin,
+    /// 星历表目录中sefstars.txt的全部恒星
+    Sefstars,
+    /// 指定的恒星，用sefstars.txt中的传统名或星名，如：Spica、alVir
+    Custom(Vec<String>),
+}
+
+/// 恒星的配置
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct FixedStarConfig {
+    /// 恒星表，默认：内置的14颗恒星
+    pub catalogue: FixedStarCatalogue,
+    /// 只计算星等不大于此值的恒星，默认：不限
+    pub magnitude: Option<f64>,
+    /// 恒星与行星、四轴、福点、阿拉伯点合相的容许度，默认：1度
+    pub orb: f64,
+    /// 恒星与宫头合相的容许度，默认：1度
+    pub cusp_orb: f64,
+    /// 恒星与行星同时在轴上的容许度，以赤经天顶之差计，默认：1度，约4分钟
+    pub paran_orb: f64,
+}
+
+impl Default for FixedStarConfig {
+    fn default() -> Self {
+        Self {
+            catalogue: FixedStarCatalogue::default(),
+            magnitude: None,
+            orb: 1.0,
+            cusp_orb: 1.0,
+            paran_orb: 1.0,
+        }
+    }
+}
+
+/// 恒星的黄道经度
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+#[derive(Clone, Debug)]
+pub struct FixedStar {
+    /// 内置恒星的名称，其它恒星没有此值
+    pub fixed_star: Option<FixedStarName>,
+    /// sefstars.txt中的传统名，没有传统名的用星名
+    pub name: String,
+    /// sefstars.txt中的星名，如：alVir
+    pub nomenclature: String,
+    /// 星等
+    pub magnitude: f64,
+    /// 恒星的黄道经度
+    pub long: f64,
+    /// 行星在黄道上的入宿名
+    pub xiu: LunarMansionsName,
+    /// 行星在黄道上的入宿度
+    pub xiu_degree: f64,
+    /// 描述，内置恒星才有此值
+    pub desc: Option<&'static str>,
+}
+
+/// 与恒星合相的点
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum FixedStarPoint {
+    /// 行星、四轴、福点、阿拉伯点
+    Planet(PlanetName),
+    /// 宫头，1-12
+    Cusp(u8),
+}
+
+/// 恒星与星盘中的点合相
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct FixedStarConjunction {
+    /// 恒星
+    pub fixed_star: FixedStar,
+    /// 与恒星合相的点
+    pub point: FixedStarPoint,
+    /// 恒星与该点的黄经距离
+    pub distance: f64,
+}
+
+/// 星体在轴上的时刻
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum AngularEvent {
+    /// 东升，在上升点
+    Rising,
+    /// 上中天，在中天
+    Culminating,
+    /// 西落，在下降点
+    Setting,
+    /// 下中天，在天底
+    AntiCulminating,
+}
+
+/// 恒星与行星在出生地同时在轴上（Paran）
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Paran {
+    /// 恒星
+    pub fixed_star: FixedStar,
+    /// 恒星所在的轴
+    pub star_event: AngularEvent,
+    /// 行星
+    pub planet: PlanetName,
+    /// 行星所在的轴
+    pub planet_event: AngularEvent,
+    /// 两者在轴上时赤经天顶之差，单位：度
+    pub distance: f64,
+}
+
+// sefstars.txt中的一条恒星记录
+#[derive(Clone)]
+struct CatalogueEntry {
+    name: String,
+    nomenclature: String,
+    magnitude: f64,
+}
+
+// 解析sefstars.txt，跳过注释及星等无效的记录
+// 每条记录：传统名,星名,历元,赤经时,分,秒,赤纬度,分,秒,赤经自行,赤纬自行,视向速度,视差,星等,...
+fn parse_catalogue(content: &str) -> Vec<CatalogueEntry> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<_> = line.split(',').map(str::trim).collect();
+            if fields.len() < 14 || fields[1].is_empty() {
+                return None;
+            }
+            let magnitude = fields[13].parse().ok()?;
+            let name = if fields[0].is_empty() {
+                fields[1]
+            } else {
+                fields[0]
+            };
+            Some(CatalogueEntry {
+                name: name.to_string(),
+                nomenclature: fields[1].to_string(),
+                magnitude,
+            })
+        })
+        .collect()
+}
+
+// 读取并解析星历表目录中的sefstars.txt，按目录缓存，每个目录只解析一次
+fn read_catalogue(ephe_path: &str) -> Result<Arc<Vec<CatalogueEntry>>, Error> {
+    static CATALOGUES: OnceLock<Mutex<HashMap<String, Arc<Vec<CatalogueEntry>>>>> = OnceLock::new();
+    let catalogues = CATALOGUES.get_or_init(Default::default);
+    let lock_error = |e| Error::Function(format!("读取恒星表缓存错误:{e}"));
+
+    if let Some(entries) = catalogues.lock().map_err(lock_error)?.get(ephe_path) {
+        return Ok(Arc::clone(entries));
+    }
+
+    let path = Path::new(ephe_path).join("sefstars.txt");
+    let content = fs::read_to_string(&path)
+        .map_err(|e| Error::Function(format!("读取恒星表`{}`错误:{e}", path.display())))?;
+    let entries = Arc::new(parse_catalogue(&content));
+    catalogues
+        .lock()
+        .map_err(lock_error)?
+        .insert(ephe_path.to_string(), Arc::clone(&entries));
+    Ok(entries)
+}
+
+// 按配置从恒星表中选出恒星，同一颗星有多个名称时，只取第一条记录，内置恒星不使用恒星表
+fn select_stars(
+    entries: &[CatalogueEntry],
+    config: &FixedStarConfig,
+) -> Result<Vec<CatalogueEntry>, Error> {
+    let stars: Vec<_> = match &config.catalogue {
+        FixedStarCatalogue::Builtin => FixedStarName::builtin_stars()
+            .iter()
+            .map(FixedStarName::catalogue_entry)
+            .collect(),
+        FixedStarCatalogue::Sefstars => {
+            let mut nomenclatures = HashSet::new();
+            entries
+                .iter()
+                .filter(|e| nomenclatures.insert(e.nomenclature.as_str()))
+                .cloned()
+                .collect()
+        }
+        FixedStarCatalogue::Custom(names) => names
+            .iter()
+            .map(|name| {
+                entries
+                    .iter()
+                    .find(|e| e.name.eq_ignore_ascii_case(name) || e.nomenclature == *name)
+                    .cloned()
+                    .ok_or_else(|| Error::InvalidFixedStar(format!("恒星表中没有恒星`{name}`")))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    Ok(stars
+        .into_iter()
+        .filter(|e| config.magnitude.is_none_or(|m| e.magnitude <= m))
+        .collect())
+}
+
 /// 计算恒星的黄道经度
-pub fn calc_fixed_star_long(jd_ut: f64, ephe_path: &str) -> Result<Vec<FixedStar>, Error> {
+pub fn calc_fixed_star_long(
+    jd_ut: f64,
+    config: &FixedStarConfig,
+    ephe_path: &str,
+) -> Result<Vec<FixedStar>, Error> {
+    let entries = match config.catalogue {
+        FixedStarCatalogue::Builtin => Arc::default(),
+        _ => read_catalogue(ephe_path)?,
+    };
+    let stars = select_stars(&entries, config)?;
+
     let distance_star_config = DistanceStarConfig::default_all_configs();
 
     let distance_star_long = calc_distance_star_long(jd_ut, &distance_star_config, ephe_path)?;
@@ -100,45 +366,262 @@ pub fn calc_fixed_star_long(jd_ut: f64, ephe_path: &str) -> Result<Vec<FixedStar
 
     swe_set_ephe_path(ephe_path);
 
-    for fixed_star in [
-        角宿一,
-        大角星,
-        氐宿一,
-        氐宿四,
-        心宿二,
-        北门师落,
-        大陵五,
-        毕宿五,
-        五车二,
-        参宿四,
-        天狼星,
-        北河三,
-        南河三,
-        轩辕十四,
-    ] {
-        let star_name = format!(",{}", fixed_star.to_swe_name());
+    for star in stars {
+        let star_name = format!(",{}", star.nomenclature);
         let (_, xx) = swe_fixstar2_ut(&star_name, jd_ut, &[Flag::SeflgSwieph])
-            .map_err(|e| Error::Function(format!("计算恒星星`{:?}`错误:{}", fixed_star, e)))?;
+            .map_err(|e| Error::Function(format!("计算恒星星`{}`错误:{}", star.name, e)))?;
 
         let star_long = xx[0];
         let xiu_degree = calc_xiu_degree(star_long, &distance_star_long)?;
+        let fixed_star = FixedStarName::from_swe_name(&star.nomenclature);
 
         fixed_star_long.push(FixedStar {
             fixed_star,
+            name: star.name,
+            nomenclature: star.nomenclature,
+            magnitude: star.magnitude,
             long: star_long,
             xiu: xiu_degree.0,
             xiu_degree: xiu_degree.1,
-            desc: fixed_star.desc(),
+            desc: fixed_star.map(|star| star.desc()),
         });
     }
     swe_close();
     Ok(fixed_star_long)
 }
 
+/// 恒星与行星、四轴、福点、阿拉伯点及宫头的合相
+/// * points: 行星、四轴、福点、阿拉伯点
+/// * cusps: 12宫头黄经
+pub(crate) fn calc_conjunctions(
+    fixed_stars: &[FixedStar],
+    points: &[&Planet],
+    cusps: &[f64],
+    config: &FixedStarConfig,
+) -> Vec<FixedStarConjunction> {
+    let points = points
+        .iter()
+        .map(|p| (FixedStarPoint::Planet(p.name), p.long, config.orb))
+        .chain(
+            cusps
+                .iter()
+                .enumerate()
+                .map(|(i, &cusp)| (FixedStarPoint::Cusp(i as u8 + 1), cusp, config.cusp_orb)),
+        );
+
+    let mut conjunctions = vec![];
+    for (point, long, orb) in points {
+        for fixed_star in fixed_stars {
+            let distance = included_angle(fixed_star.long, long);
+            if distance <= orb {
+                conjunctions.push(FixedStarConjunction {
+                    fixed_star: fixed_star.clone(),
+                    point,
+                    distance,
+                });
+            }
+        }
+    }
+    conjunctions
+}
+
+// 星体在各轴上时的赤经天顶，拱极星体没有东升、西落
+fn angular_ramcs(ra: f64, dec: f64, geo_lat: f64) -> Vec<(AngularEvent, f64)> {
+    let mut ramcs = vec![
+        (AngularEvent::Culminating, swe_degnorm(ra)),
+        (AngularEvent::AntiCulminating, swe_degnorm(ra + 180.0)),
+    ];
+
+    // 东升、西落时的时角
+    let x = -geo_lat.to_radians().tan() * dec.to_radians().tan();
+    if x.abs() <= 1.0 {
+        let semi_arc = x.acos().to_degrees();
+        ramcs.push((AngularEvent::Rising, swe_degnorm(ra - semi_arc)));
+        ramcs.push((AngularEvent::Setting, swe_degnorm(ra + semi_arc)));
+    }
+    ramcs
+}
+
+/// 恒星与行星的Paran：在出生地，恒星与行星在同一天内同时在轴上
+/// * planets: 行星，不含交点
+pub(crate) fn calc_parans(
+    jd_ut: f64,
+    fixed_stars: &[FixedStar],
+    planets: &[&Planet],
+    geo_lat: f64,
+    config: &FixedStarConfig,
+    ephe_path: &str,
+) -> Result<Vec<Paran>, Error> {
+    let mut parans = vec![];
+
+    swe_set_ephe_path(ephe_path);
+    for fixed_star in fixed_stars {
+        let star_name = format!(",{}", fixed_star.nomenclature);
+        let (_, xx) = swe_fixstar2_ut(
+            &star_name,
+            jd_ut,
+            &[Flag::SeflgSwieph, Flag::SeflgEquatorial],
+        )
+        .map_err(|e| Error::Function(format!("计算恒星星`{}`错误:{}", fixed_star.name, e)))?;
+        let star_ramcs = angular_ramcs(xx[0], xx[1], geo_lat);
+
+        for planet in planets {
+            let planet_ramcs = angular_ramcs(planet.ra, planet.dec, geo_lat);
+            for &(star_event, star_ramc) in &star_ramcs {
+                for &(planet_event, planet_ramc) in &planet_ramcs {
+                    let distance = included_angle(star_ramc, planet_ramc);
+                    if distance <= config.paran_orb {
+                        parans.push(Paran {
+                            fixed_star: fixed_star.clone(),
+                            star_event,
+                            planet: planet.name,
+                            planet_event,
+                            distance,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    swe_close();
+
+    Ok(parans)
+}
+
 #[cfg(test)]
 mod tests {
 
-    use super::calc_fixed_star_long;
+    use super::{
+        AngularEvent, FixedStarCatalogue, FixedStarConfig, FixedStarName, angular_ramcs,
+        calc_fixed_star_long, parse_catalogue, read_catalogue, select_stars,
+    };
+    use crate::Error;
+
+    const CATALOGUE: &str = "\
+# 注释
+Aldebaran  ,alTau,ICRS,04,35,55.23907,+16,30,33.4885,63.45,-188.94,54.26,48.94,0.86, 16,  629
+Rohini  ,alTau,ICRS,04,35,55.23907,+16,30,33.4885,63.45,-188.94,54.26,48.94,0.86, 16,  629
+Algol      ,bePer,ICRS,03,08,10.13245,+40,57,20.3280,2.99,-1.66,4,36.27,2.12, 40,  673
+          ,35Ari,ICRS,02,43,27.1116328306,+27,42,25.695377678,11.030,-8.806,13,11.4477,4.67,27,424
+";
+
+    #[test]
+    fn test_parse_catalogue() {
+        let entries = parse_catalogue(CATALOGUE);
+        assert_eq!(4, entries.len());
+
+        assert_eq!("Aldebaran", entries[0].name);
+        assert_eq!("alTau", entries[0].nomenclature);
+        assert_eq!(0.86, entries[0].magnitude);
+
+        // 没有传统名，用星名
+        assert_eq!("35Ari", entries[3].name);
+        assert_eq!(4.67, entries[3].magnitude);
+    }
+
+    #[test]
+    fn test_select_stars() {
+        let entries = parse_catalogue(CATALOGUE);
+        let names = |config: &FixedStarConfig| -> Result<Vec<String>, Error> {
+            Ok(select_stars(&entries, config)?
+                .iter()
+                .map(|e| e.name.clone())
+                .collect())
+        };
+
+        // 同一颗星只取第一条记录
+        let config = FixedStarConfig {
+            catalogue: FixedStarCatalogue::Sefstars,
+            ..Default::default()
+        };
+        assert_eq!(vec!["Aldebaran", "Algol", "35Ari"], names(&config).unwrap());
+
+        // 星等过滤
+        let config = FixedStarConfig {
+            catalogue: FixedStarCatalogue::Sefstars,
+            magnitude: Some(2.5),
+            ..Default::default()
+        };
+        assert_eq!(vec!["Aldebaran", "Algol"], names(&config).unwrap());
+
+        // 可用传统名、别名或星名指定
+        let config = FixedStarConfig {
+            catalogue: FixedStarCatalogue::Custom(vec!["rohini".to_string(), "bePer".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(vec!["Rohini", "Algol"], names(&config).unwrap());
+
+        let config = FixedStarConfig {
+            catalogue: FixedStarCatalogue::Custom(vec!["Sirius".to_string()]),
+            ..Default::default()
+        };
+        assert!(matches!(names(&config), Err(Error::InvalidFixedStar(_))));
+
+        // 内置恒星不使用恒星表
+        let stars = select_stars(&[], &FixedStarConfig::default()).unwrap();
+        assert_eq!(FixedStarName::builtin_stars().len(), stars.len());
+        assert_eq!("Spica", stars[0].name);
+        assert_eq!("alVir", stars[0].nomenclature);
+
+        let config = FixedStarConfig {
+            magnitude: Some(0.5),
+            ..Default::default()
+        };
+        let names: Vec<_> = select_stars(&[], &config)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(
+            vec!["Arcturus", "Capella", "Betelgeuse", "Sirius", "Procyon"],
+            names
+        );
+    }
+
+    // 内置恒星的传统名、星等与sefstars.txt相同
+    #[test]
+    fn test_builtin_catalogue_entry() {
+        dotenvy::dotenv().ok();
+        let ephe_path = std::env::var("EPHE_PATH")
+            .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+        let entries = read_catalogue(&ephe_path).unwrap();
+        let config = FixedStarConfig {
+            catalogue: FixedStarCatalogue::Custom(
+                FixedStarName::builtin_stars()
+                    .iter()
+                    .map(|star| star.to_swe_name().to_string())
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let expected = select_stars(&entries, &config).unwrap();
+        let builtin = select_stars(&[], &FixedStarConfig::default()).unwrap();
+        for (e0, e1) in expected.iter().zip(&builtin) {
+            assert_eq!(e0.name, e1.name);
+            assert_eq!(e0.nomenclature, e1.nomenclature);
+            assert_eq!(e0.magnitude, e1.magnitude);
+        }
+
+        // 同一目录的恒星表只解析一次
+        let cached = read_catalogue(&ephe_path).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&entries, &cached));
+    }
+
+    #[test]
+    fn test_angular_ramcs() {
+        // 赤纬为0，半弧为90度
+        let ramcs = angular_ramcs(100.0, 0.0, 30.0);
+        let ramc = |event| ramcs.iter().find(|(e, _)| *e == event).unwrap().1;
+        assert!((ramc(AngularEvent::Culminating) - 100.0).abs() < 1e-9);
+        assert!((ramc(AngularEvent::AntiCulminating) - 280.0).abs() < 1e-9);
+        assert!((ramc(AngularEvent::Rising) - 10.0).abs() < 1e-9);
+        assert!((ramc(AngularEvent::Setting) - 190.0).abs() < 1e-9);
+
+        // 拱极星不升不落
+        let ramcs = angular_ramcs(100.0, 70.0, 40.0);
+        assert_eq!(2, ramcs.len());
+    }
 
     #[test]
     fn test_calc_fixed_star_long_snapshot() {
@@ -148,7 +631,7 @@ mod tests {
 
         let jd_ut = 2451545.0; // J2000.0
 
-        let result = calc_fixed_star_long(jd_ut, &ephe_path).unwrap();
+        let result = calc_fixed_star_long(jd_ut, &FixedStarConfig::default(), &ephe_path).unwrap();
         insta::assert_yaml_snapshot!(result);
     }
 }
@@ -0,0 +1,104 @@
+use swe::swe_degnorm;
+
+use crate::{Aspect, AspectConfig, Error, Horoscope, Planet};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 谐波盘：星体的黄经乘以谐波数
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct HarmonicChart {
+    /// 谐波数
+    pub harmonic: u16,
+    /// 上升点
+    pub asc: Planet,
+    /// 中天
+    pub mc: Planet,
+    /// 下降点
+    pub dsc: Planet,
+    /// 天底
+    pub ic: Planet,
+    /// 行星
+    pub planets: Vec<Planet>,
+    /// 谐波盘中行星、四轴间的相位
+    pub aspects: Vec<Aspect>,
+}
+
+impl HarmonicChart {
+    /// 计算星盘的谐波盘
+    /// * horoscope: 星盘
+    /// * harmonic: 谐波数，不小于1，1即原星盘
+    /// * config: 谐波盘的相位配置
+    pub fn new(horoscope: &Horoscope, harmonic: u16, config: &AspectConfig) -> Result<Self, Error> {
+        if harmonic == 0 {
+            return Err(Error::InvalidHarmonic("谐波数不能为0".to_string()));
+        }
+
+        // 谐波盘中的位置与宫位、尊贵、行星状态无关，只保留速度状态和界外
+        let to_harmonic = |p: &Planet| -> Planet {
+            let mut planet = *p;
+            planet.long = harmonic_long(p.long, harmonic);
+            planet.speed = p.speed * f64::from(harmonic);
+            planet.dignities = None;
+            planet.house = None;
+            planet.mundane_position = None;
+            planet.condition = None;
+            planet
+        };
+
+        let asc = to_harmonic(&horoscope.asc);
+        let mc = to_harmonic(&horoscope.mc);
+        let dsc = to_harmonic(&horoscope.dsc);
+        let ic = to_harmonic(&horoscope.ic);
+        let planets: Vec<_> = horoscope.planets.iter().map(to_harmonic).collect();
+
+        let mut points: Vec<_> = planets.iter().collect();
+        points.extend([&asc, &mc, &dsc, &ic]);
+        let mut aspects = vec![];
+        for i in 0..points.len() {
+            for j in i..points.len() {
+                if let Some(aspect) = points[i].has_aspect_with_config(points[j], false, config) {
+                    aspects.push(aspect)
+                }
+            }
+        }
+
+        Ok(Self {
+            harmonic,
+            asc,
+            mc,
+            dsc,
+            ic,
+            planets,
+            aspects,
+        })
+    }
+}
+
+/// 黄经在谐波盘中的位置
+pub fn harmonic_long(long: f64, harmonic: u16) -> f64 {
+    swe_degnorm(long * f64::from(harmonic))
+}
+
+#[cfg(test)]
+mod tests {
+    use parameterized::parameterized;
+
+    use super::harmonic_long;
+
+    #[parameterized(case = {
+        (10.0, 1, 10.0),
+        (10.0, 4, 40.0),
+        (100.0, 4, 40.0),
+        (190.0, 2, 20.0),
+        (200.0, 9, 0.0),
+    })]
+    fn test_harmonic_long(case: (f64, u16, f64)) {
+        assert!((harmonic_long(case.0, case.1) - case.2).abs() < 1e-9);
+    }
+}
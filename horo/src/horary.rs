@@ -0,0 +1,313 @@
+use crate::{
+    Error, Horoscope, PlanetName, SolarPhase,
+    dignity::{SEVEN_PLANETS, domicile_ruler, exaltation_ruler, sign_of, triplicity_ruler},
+};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+// 上升点在星座的前3度，太早
+const EARLY_ASC_DEGREE: f64 = 3.0;
+// 上升点在星座的最后3度，太迟
+const LATE_ASC_DEGREE: f64 = 27.0;
+
+/// 卜卦盘判断前的告诫（Considerations before judgement）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Stricture {
+    /// 上升点太早：在星座的前3度，问题尚未成熟
+    EarlyAscendant,
+    /// 上升点太迟：在星座的最后3度，事情已成定局
+    LateAscendant,
+    /// 月亮空亡：事无所成
+    MoonVoidOfCourse,
+    /// 月亮在燃烧之路：天秤座15度至天蝎座15度
+    MoonInViaCombusta,
+    /// 土星在1宫：问卜者受损
+    SaturnInFirstHouse,
+    /// 土星在7宫：占星师的判断易出错
+    SaturnInSeventhHouse,
+    /// 上升点的主星焦伤：问卜者无力或有所隐瞒
+    AscendantRulerCombust,
+    /// 时主星与上升点不相合：时主星不是上升星座的庙主星、旺主星或三分主星，星盘不根本
+    PlanetaryHourDisagrees,
+}
+
+/// 一项告诫的判断结果
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct StrictureVerdict {
+    /// 告诫
+    pub stricture: Stricture,
+    /// 该项告诫成立：true，不成立：false
+    pub applies: bool,
+}
+
+/// 问卜者或所问之事的征象星
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Significator {
+    /// 宫位，1-12
+    pub house: u8,
+    /// 宫头黄经
+    pub cusp: f64,
+    /// 宫主星：宫头所在星座的庙主星
+    pub ruler: PlanetName,
+    /// 宫主星所在的宫位
+    pub ruler_house: Option<u8>,
+    /// 副征象星：问卜者为月亮，所问之事没有
+    pub co_significator: Option<PlanetName>,
+    /// 落在该宫内的七颗古典行星
+    pub planets_in_house: Vec<PlanetName>,
+}
+
+/// 卜卦盘
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Horary {
+    /// 起卦时间的星盘
+    pub horoscope: Horoscope,
+    /// 各项告诫的判断结果
+    pub verdicts: Vec<StrictureVerdict>,
+    /// 星盘根本（radical）：所有告诫都不成立
+    pub radical: bool,
+    /// 问卜者的征象星：1宫主星及月亮
+    pub querent: Significator,
+    /// 所问之事的征象星
+    pub quesited: Significator,
+}
+
+impl Horary {
+    /// 卜卦盘
+    /// * horoscope: 起卦时间的星盘
+    /// * quesited_house: 所问之事所在的宫位，2-12
+    pub fn new(horoscope: Horoscope, quesited_house: u8) -> Result<Self, Error> {
+        if !(2..=12).contains(&quesited_house) {
+            return Err(Error::InvalidHouse(format!(
+                "所问之事的宫位应在2-12之间，实际为{quesited_house}"
+            )));
+        }
+
+        let verdicts = strictures(&horoscope);
+        let radical = verdicts.iter().all(|verdict| !verdict.applies);
+        let querent = significator(&horoscope, 1);
+        let quesited = significator(&horoscope, quesited_house);
+
+        Ok(Self {
+            horoscope,
+            verdicts,
+            radical,
+            querent,
+            quesited,
+        })
+    }
+}
+
+// 判断各项告诫
+fn strictures(horoscope: &Horoscope) -> Vec<StrictureVerdict> {
+    let asc_degree = horoscope.asc.long % 30.0;
+    let asc_ruler = domicile_ruler(sign_of(horoscope.asc.long));
+
+    let planet_house = |name: PlanetName| {
+        horoscope
+            .planets
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.house)
+    };
+    let saturn_house = planet_house(PlanetName::Saturn);
+
+    // 太阳作为上升主星时没有焦伤
+    let asc_ruler_combust = horoscope
+        .planets
+        .iter()
+        .find(|p| p.name == asc_ruler)
+        .and_then(|p| p.condition)
+        .is_some_and(|condition| condition.solar_phase == SolarPhase::Combust);
+
+    [
+        (Stricture::EarlyAscendant, asc_degree < EARLY_ASC_DEGREE),
+        (Stricture::LateAscendant, asc_degree >= LATE_ASC_DEGREE),
+        (
+            Stricture::MoonVoidOfCourse,
+            horoscope
+                .moon_condition
+                .as_ref()
+                .is_some_and(|moon| moon.void_of_course),
+        ),
+        (
+            Stricture::MoonInViaCombusta,
+            horoscope
+                .moon_condition
+                .as_ref()
+                .is_some_and(|moon| moon.via_combusta),
+        ),
+        (Stricture::SaturnInFirstHouse, saturn_house == Some(1)),
+        (Stricture::SaturnInSeventhHouse, saturn_house == Some(7)),
+        (Stricture::AscendantRulerCombust, asc_ruler_combust),
+        (
+            Stricture::PlanetaryHourDisagrees,
+            !hour_ruler_agrees(
+                horoscope.planetary_hours,
+                horoscope.asc.long,
+                horoscope.is_diurnal,
+            ),
+        ),
+    ]
+    .into_iter()
+    .map(|(stricture, applies)| StrictureVerdict { stricture, applies })
+    .collect()
+}
+
+// 时主星与上升点相合：时主星是上升星座的庙主星、旺主星或三分主星
+fn hour_ruler_agrees(hour_ruler: PlanetName, asc_long: f64, is_diurnal: bool) -> bool {
+    let sign = sign_of(asc_long);
+    domicile_ruler(sign) == hour_ruler
+        || exaltation_ruler(sign) == Some(hour_ruler)
+        || triplicity_ruler(sign, is_diurnal) == hour_ruler
+}
+
+// 宫位的征象星
+fn significator(horoscope: &Horoscope, house: u8) -> Significator {
+    let cusp = horoscope.cusps[usize::from(house - 1)];
+    let ruler = domicile_ruler(sign_of(cusp));
+    let ruler_house = horoscope
+        .planets
+        .iter()
+        .find(|p| p.name == ruler)
+        .and_then(|p| p.house);
+
+    let planets_in_house = horoscope
+        .planets
+        .iter()
+        .filter(|p| SEVEN_PLANETS.contains(&p.name) && p.house == Some(house))
+        .map(|p| p.name)
+        .collect();
+
+    Significator {
+        house,
+        cusp,
+        ruler,
+        ruler_house,
+        co_significator: (house == 1).then_some(PlanetName::Moon),
+        planets_in_house,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_position::GeoPosition;
+    use horo_date_time::HoroDateTime;
+    use std::env;
+
+    use crate::{
+        Error, Horoscope, HoroscopeConfig, HouseName, PlanetConfig,
+        PlanetName::{self, *},
+    };
+
+    use super::{Horary, Stricture, hour_ruler_agrees};
+
+    fn ephe_path() -> String {
+        dotenvy::dotenv().ok();
+        env::var("EPHE_PATH")
+            .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...")
+    }
+
+    fn horoscope() -> Horoscope {
+        let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+        let geo = GeoPosition::new(
+            102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+            25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+        )
+        .unwrap();
+
+        Horoscope::new(
+            t,
+            geo,
+            HouseName::Alcabitus,
+            &PlanetConfig::default_configs(false),
+            &HoroscopeConfig::default(),
+            &ephe_path(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hour_ruler_agrees() {
+        // 白羊座：庙主星火星，旺主星太阳，三分主星白天太阳、夜间木星
+        assert!(hour_ruler_agrees(Mars, 10.0, true));
+        assert!(hour_ruler_agrees(Sun, 10.0, true));
+        assert!(!hour_ruler_agrees(Jupiter, 10.0, true));
+        assert!(hour_ruler_agrees(Jupiter, 10.0, false));
+        assert!(!hour_ruler_agrees(Saturn, 10.0, false));
+    }
+
+    #[test]
+    fn test_horary() {
+        let horoscope = horoscope();
+        let asc_long = horoscope.asc.long;
+        let cusp7 = horoscope.cusps[6];
+        let saturn_house = horoscope
+            .planets
+            .iter()
+            .find(|p| p.name == Saturn)
+            .and_then(|p| p.house);
+        let void_of_course = horoscope.moon_condition.as_ref().unwrap().void_of_course;
+
+        let horary = Horary::new(horoscope, 7).unwrap();
+
+        let verdict = |stricture: Stricture| {
+            horary
+                .verdicts
+                .iter()
+                .find(|verdict| verdict.stricture == stricture)
+                .unwrap()
+                .applies
+        };
+        assert_eq!(horary.verdicts.len(), 8);
+        assert_eq!(verdict(Stricture::EarlyAscendant), asc_long % 30.0 < 3.0);
+        assert_eq!(verdict(Stricture::LateAscendant), asc_long % 30.0 >= 27.0);
+        assert_eq!(verdict(Stricture::MoonVoidOfCourse), void_of_course);
+        assert_eq!(
+            verdict(Stricture::SaturnInSeventhHouse),
+            saturn_house == Some(7)
+        );
+        assert_eq!(
+            horary.radical,
+            horary.verdicts.iter().all(|verdict| !verdict.applies)
+        );
+
+        assert_eq!(horary.querent.house, 1);
+        assert_eq!(horary.querent.cusp, asc_long);
+        assert_eq!(horary.querent.co_significator, Some(Moon));
+        assert_eq!(horary.quesited.house, 7);
+        assert_eq!(horary.quesited.cusp, cusp7);
+        assert_eq!(horary.quesited.co_significator, None);
+
+        let ruler_of = |long: f64| -> PlanetName {
+            [
+                Mars, Venus, Mercury, Moon, Sun, Mercury, Venus, Mars, Jupiter, Saturn, Saturn,
+                Jupiter,
+            ][(long / 30.0) as usize]
+        };
+        assert_eq!(horary.querent.ruler, ruler_of(asc_long));
+        assert_eq!(horary.quesited.ruler, ruler_of(cusp7));
+    }
+
+    #[test]
+    fn test_invalid_quesited_house() {
+        for house in [0, 1, 13] {
+            assert!(matches!(
+                Horary::new(horoscope(), house),
+                Err(Error::InvalidHouse(_))
+            ));
+        }
+    }
+}
@@ -1,15 +1,31 @@
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 
 use crate::{
-    Aspect, Error, HouseName, Planet, PlanetConfig, PlanetName,
-    fixed_star::{FixedStar, calc_fixed_star_long},
-    utils::{calc_eps, mod180, newton_iteration},
+    Almuten, Aspect, Error, HoroscopeConfig, HouseName, HousePlacement, NodeType, Planet,
+    PlanetConfig, PlanetName, Zodiac,
+    condition::calc_conditions,
+    dignity::EssentialDignities,
+    division::{DivisionalPosition, calc_divisional_aspects},
+    fixed_star::{
+        FixedStar, FixedStarConjunction, Paran, calc_conjunctions, calc_fixed_star_long,
+        calc_parans,
+    },
+    lot::{Lot, calc_lots},
+    midpoint::{
+        DialPosition, Midpoint, MidpointTree, calc_midpoint_trees, calc_midpoints, dial_sort,
+    },
+    moon::MoonCondition,
+    planetary_hours::PlanetaryHours,
+    position::PositionMode,
+    reception::{Dispositors, MutualReception, calc_mutual_receptions, calc_receptions},
+    sect::SectAnalysis,
+    utils::calc_eps,
 };
 use geo_position::GeoPosition;
 use swe::{
-    Body, CalcFlag, Flag, HouseSystem, swe_azalt, swe_calc_ut, swe_close, swe_cotrans, swe_degnorm,
-    swe_houses, swe_set_ephe_path,
+    Body, CalcFlag, Flag, swe_azalt, swe_calc_ut, swe_close, swe_cotrans, swe_degnorm, swe_houses,
+    swe_set_ephe_path,
 };
 
 use horo_date_time::HoroDateTime;
@@ -42,22 +58,62 @@ pub struct Horoscope {
     pub ic: Planet,
     /// 福点
     pub part_of_fortune: Planet,
+    /// 阿拉伯点
+    pub lots: Vec<Lot>,
     //     @field:Schema(description = "七颗行星")
     pub planets: Vec<Planet>,
     //     @field:Schema(description = "白天盘:true,夜间盘:false")
     pub is_diurnal: bool,
+    /// 区分：行星是否在区分内、Hayz、Halb，区分内外的吉凶星，日心时没有此值
+    pub sect: Option<SectAnalysis>,
     //     @field:Schema(description = "日主星，值为行星id，与瑞士星历表同")
     pub planetary_day: PlanetName,
     //     @field:Schema(description = "时主星，值为行星id，与瑞士星历表同")
     pub planetary_hours: PlanetName,
+    /// 月亮的状态：月相、空亡、燃烧之路，日心时没有此值
+    pub moon_condition: Option<MoonCondition>,
     //     @field:Schema(description = "行星相位，仅包含四轴、行星间的相位")
     pub aspects: Vec<Aspect>,
+    /// 七颗古典行星间的互容，包括不成相位的
+    pub mutual_receptions: Vec<MutualReception>,
+    /// 七颗古典行星的定位星链及最终定位星
+    pub dispositors: Dispositors,
     // 映点
     pub antiscoins: Vec<Aspect>,
     // 反映点
     pub contraantiscias: Vec<Aspect>,
+    /// 平行，相位值为0
+    pub parallels: Vec<Aspect>,
+    /// 对等平行，相位值为180
+    pub contraparallels: Vec<Aspect>,
+    /// 行星及四轴的十二分部、九分部位置
+    pub divisional_positions: Vec<DivisionalPosition>,
+    /// 本命行星及四轴与十二分部的相位，p0为本命星体，p1为十二分部所属的星体
+    pub dodecatemorion_aspects: Vec<Aspect>,
+    /// 本命行星及四轴与九分部的相位，p0为本命星体，p1为九分部所属的星体
+    pub navamsa_aspects: Vec<Aspect>,
+    /// 行星、上升点、中天两两的中点
+    pub midpoints: Vec<Midpoint>,
+    /// 行星、上升点、中天的中点树，度盘及容许度可配置
+    pub midpoint_trees: Vec<MidpointTree>,
+    /// 行星、上升点、中天按中点树的度盘排序
+    pub dial_positions: Vec<DialPosition>,
     // 恒星
     pub fixed_stars: Vec<FixedStar>,
+    /// 恒星与行星、四轴、福点、阿拉伯点及宫头的合相
+    pub fixed_star_conjunctions: Vec<FixedStarConjunction>,
+    /// 恒星与行星同时在轴上（Paran）
+    pub parans: Vec<Paran>,
+    /// 全盘胜利星及各宫头的胜利星
+    pub almuten: Almuten,
+    /// 黄道
+    pub zodiac: Zodiac,
+    /// 行星位置的计算方式：地心、站心或日心
+    pub position_mode: PositionMode,
+    /// 月交点：平均交点或真实交点
+    pub node_type: NodeType,
+    /// 岁差，回归黄道为0，恒星黄道经度 = 回归黄道经度 - 岁差
+    pub ayanamsa: f64,
 
     /// 黄赤倾角
     #[cfg_attr(feature = "serde", serde(skip_serializing))]
@@ -70,12 +126,29 @@ impl Horoscope {
         geo: GeoPosition,
         house_name: HouseName,
         planets_config: &[PlanetConfig],
+        config: &HoroscopeConfig,
         ephe_path: &str,
     ) -> Result<Self, Error> {
         // 计算宫位
         let (cusps, ascmc) = swe_houses(date.jd_ut1, geo.lat, geo.long, house_name.into())
             .map_err(|_| Error::Function("swe_houses()调用失败".to_owned()))?;
 
+        // 岁差，以下黄经均为所选黄道的经度，计算赤经、赤纬、地平坐标时需换回回归黄道
+        let ayanamsa = config.zodiac.ayanamsa(date.jd_ut1, ephe_path);
+        let cusps: Vec<_> = match house_name {
+            // 整宫制的宫头是所选黄道中星座的起点，不能由回归黄道的宫头减去岁差得到
+            HouseName::WholeSign => {
+                let asc_sign = (swe_degnorm(ascmc[0] - ayanamsa) / 30.0).floor();
+                (0..12)
+                    .map(|i| swe_degnorm((asc_sign + i as f64) * 30.0))
+                    .collect()
+            }
+            _ => cusps[1..13]
+                .iter()
+                .map(|cusp| swe_degnorm(cusp - ayanamsa))
+                .collect(),
+        };
+
         // 计算四轴
         // 计算四轴的赤经，赤纬
         // 先计算黄赤倾角
@@ -88,9 +161,9 @@ impl Horoscope {
             .find(|p| p.name == PlanetName::ASC)
             .unwrap_or(&default_planet_config);
         let asc_equator = swe_cotrans(ascmc[0], 0.0, 1.0, -eps);
-        let asc = Planet::new(
+        let mut asc = Planet::new(
             PlanetName::ASC,
-            ascmc[0],
+            swe_degnorm(ascmc[0] - ayanamsa),
             0.0,
             0.0,
             asc_equator[0],
@@ -104,9 +177,9 @@ impl Horoscope {
             .find(|p| p.name == PlanetName::MC)
             .unwrap_or(&default_planet_config);
         let mc_equator = swe_cotrans(ascmc[1], 0.0, 1.0, -eps);
-        let mc = Planet::new(
+        let mut mc = Planet::new(
             PlanetName::MC,
-            ascmc[1],
+            swe_degnorm(ascmc[1] - ayanamsa),
             0.0,
             0.0,
             mc_equator[0],
@@ -121,9 +194,9 @@ impl Horoscope {
             .unwrap_or(&default_planet_config);
         let dsc_long = swe_degnorm(ascmc[0] + 180.0);
         let dsc_equator = swe_cotrans(dsc_long, 0.0, 1.0, -eps);
-        let dsc = Planet::new(
+        let mut dsc = Planet::new(
             PlanetName::DSC,
-            dsc_long,
+            swe_degnorm(dsc_long - ayanamsa),
             0.0,
             0.0,
             dsc_equator[0],
@@ -138,9 +211,9 @@ impl Horoscope {
             .unwrap_or(&default_planet_config);
         let ic_long = swe_degnorm(ascmc[1] + 180.0);
         let ic_equator = swe_cotrans(ic_long, 0.0, 1.0, -eps);
-        let ic = Planet::new(
+        let mut ic = Planet::new(
             PlanetName::IC,
-            ic_long,
+            swe_degnorm(ic_long - ayanamsa),
             0.0,
             0.0,
             ic_equator[0],
@@ -149,10 +222,41 @@ impl Horoscope {
         );
 
         // 计算行星
-        let planets = calc_planets(date.jd_ut1, planets_config, ephe_path)?;
+        let mut planets = calc_planets(
+            date.jd_ut1,
+            planets_config,
+            config.node_type,
+            ayanamsa,
+            &geo,
+            config.position_mode,
+            ephe_path,
+        )?;
 
-        // 星盘昼夜
-        let sun = planets.iter().find(|p| p.name == PlanetName::Sun).unwrap();
+        // 星盘昼夜、福点、胜利星与四轴一样以地球为准，日心时星盘中没有太阳，另按地心计算太阳、月亮
+        let heliocentric = config.position_mode == PositionMode::Heliocentric;
+        let geocentric_planets = if heliocentric {
+            calc_planets(
+                date.jd_ut1,
+                planets_config,
+                config.node_type,
+                ayanamsa,
+                &geo,
+                PositionMode::Geocentric,
+                ephe_path,
+            )?
+        } else {
+            vec![]
+        };
+        let find_planet = |name: PlanetName| {
+            let points = if heliocentric {
+                &geocentric_planets
+            } else {
+                &planets
+            };
+            *points.iter().find(|p| p.name == name).unwrap()
+        };
+        let sun = find_planet(PlanetName::Sun);
+        let moon = find_planet(PlanetName::Moon);
 
         // 获取太阳地平坐标，以海平面高度为准，因地理位置数组的第三个参数设置为0
         let azalt = swe_azalt(
@@ -161,13 +265,12 @@ impl Horoscope {
             &[geo.long, geo.lat, 0.0],
             0.0,
             0.0,
-            &[sun.long, sun.lat, 0.0],
+            &[swe_degnorm(sun.long + ayanamsa), sun.lat, 0.0],
         );
 
         let is_diurnal = azalt[1] >= 0.0;
 
         // 计算福点
-        let moon = planets.iter().find(|p| p.name == PlanetName::Moon).unwrap();
         let default_planet_config = PlanetConfig::default_config(&PlanetName::PartOfFortune);
         let planet_config = planets_config
             .iter()
@@ -176,14 +279,15 @@ impl Horoscope {
         let part_of_fortune_long = if is_diurnal {
             // 昼生
             // 福点=上升点+月亮−太阳
-            swe_degnorm(ascmc[0] + moon.long - sun.long)
+            swe_degnorm(asc.long + moon.long - sun.long)
         } else {
             // 夜生
             // 福点=上升点+太阳−月亮
-            swe_degnorm(ascmc[0] + sun.long - moon.long)
+            swe_degnorm(asc.long + sun.long - moon.long)
         };
-        let part_of_fortune_equator = swe_cotrans(part_of_fortune_long, 0.0, 1.0, -eps);
-        let part_of_fortune = Planet::new(
+        let part_of_fortune_equator =
+            swe_cotrans(swe_degnorm(part_of_fortune_long + ayanamsa), 0.0, 1.0, -eps);
+        let mut part_of_fortune = Planet::new(
             PlanetName::PartOfFortune,
             part_of_fortune_long,
             0.0,
@@ -193,125 +297,249 @@ impl Horoscope {
             planet_config,
         );
 
-        // 计算时主星
-        //月、火、水、木、金、土、日
-        // 一、二、三、四、五、六、日
-        // 1  2   3  4   5  6   0
-        // 迦勒底序
-        let chaldean_order = [
-            PlanetName::Sun,
-            PlanetName::Moon,
-            PlanetName::Mars,
-            PlanetName::Mercury,
-            PlanetName::Jupiter,
-            PlanetName::Venus,
-            PlanetName::Saturn,
-        ];
-
-        let sun_on_asc_time = sun_on_asc(&date, &geo, ephe_path)?;
-        let sun_on_dsc_time = sun_on_dsc(&date, &geo, ephe_path)?;
-        // 计算第二天太阳在asc的时刻，以大约中午时刻迭代，即sunOnDscTime+1.25天
-        let sun_on_asc_time_next = sun_on_asc(&sun_on_asc_time.plus_days(1.25)?, &geo, ephe_path)?;
-
-        // 计算星期
-        // 儒略历，公元前4713年1月1日，是星期一
-        // 此日，jd = 0
-        let t = HoroDateTime::new(
-            sun_on_asc_time.year,
-            sun_on_asc_time.month,
-            sun_on_asc_time.day,
-            12,
-            0,
-            0,
-            0.0,
+        // 计算阿拉伯点，日心时不计算
+        let mut lot_points: Vec<_> = planets.iter().collect();
+        lot_points.extend([&asc, &mc, &dsc, &ic, &part_of_fortune]);
+        let mut lots = if heliocentric {
+            vec![]
+        } else {
+            calc_lots(
+                &config.lots,
+                &lot_points,
+                &cusps,
+                is_diurnal,
+                eps,
+                ayanamsa,
+                planets_config,
+            )?
+        };
+
+        // 计算胜利星
+        let almuten = Almuten::new(
+            &[
+                (PlanetName::Sun, sun.long),
+                (PlanetName::Moon, moon.long),
+                (PlanetName::ASC, asc.long),
+                (PlanetName::MC, mc.long),
+                (PlanetName::PartOfFortune, part_of_fortune_long),
+            ],
+            &cusps,
+            is_diurnal,
+        );
+
+        // 计算月亮的状态，日心时不计算
+        let moon_condition = if heliocentric {
+            None
+        } else {
+            Some(MoonCondition::new(
+                &date,
+                &geo,
+                ayanamsa,
+                config.position_mode,
+                ephe_path,
+            )?)
+        };
+
+        // 计算行星的必然尊贵，及是否界外，日心时不计算
+        if !heliocentric {
+            for planet in planets.iter_mut() {
+                planet.dignities = EssentialDignities::new(
+                    planet.name,
+                    planet.long,
+                    is_diurnal,
+                    config.dignity_scoring,
+                );
+                planet.out_of_bounds = planet.dec.abs() > eps;
+            }
+        }
+
+        // 计算行星状态，日心时星盘中没有太阳，不计算
+        calc_conditions(
+            &mut planets,
+            &date,
+            &geo,
+            &config.conditions,
+            config.position_mode,
+            ephe_path,
         )?;
-        // jd:0,1,2,3 ,4 ,5 ,6
-        //    7,8,9,10,11,12,13
-        //    1,2,3,4 ,5 ,6, 0
-        // jd % 7 得到0, 6，对应1到7，因此需要加1
-        // 加1后，得到1到7，因此需要%7，因为7对应0，是星期日
-        let n = (t.jd_utc as u32 % 7 + 1) % 7;
-
-        // 日主星
-        let planetary_day = chaldean_order[n as usize].clone();
-
-        // 时主星序，土、木、火、日、金、水、月
-        let planetary_hours_list = [
-            PlanetName::Saturn,
-            PlanetName::Jupiter,
-            PlanetName::Mars,
-            PlanetName::Sun,
-            PlanetName::Venus,
-            PlanetName::Mercury,
-            PlanetName::Moon,
-        ];
-        // 第一个时主星
-        let first_planetary_hours_index = planetary_hours_list
-            .iter()
-            .position(|p| *p == planetary_day)
-            .unwrap();
-        let planetary_hours = if date.jd_utc < sun_on_dsc_time.jd_utc {
-            let m = (12.0 * (date.jd_utc - sun_on_asc_time.jd_utc)
-                / (sun_on_dsc_time.jd_utc - sun_on_asc_time.jd_utc)) as usize;
-            planetary_hours_list[(first_planetary_hours_index + m) % 7]
+
+        // 计算区分，日心时不计算
+        let sect = if heliocentric {
+            None
         } else {
-            let m = (12.0 * (date.jd_utc - sun_on_dsc_time.jd_utc)
-                / (sun_on_asc_time_next.jd_utc - sun_on_dsc_time.jd_utc))
-                as usize;
-            planetary_hours_list[(first_planetary_hours_index + m + 12) % 7]
+            Some(SectAnalysis::new(
+                &date, &geo, is_diurnal, &planets, ayanamsa,
+            ))
         };
 
+        // 计算星体所在宫位，象限宫制还计算Placidus世俗位置，日心时行星不计算
+        let armc = mc.ra;
+        for planet in planets
+            .iter_mut()
+            .filter(|_| !heliocentric)
+            .chain([&mut asc, &mut mc, &mut dsc, &mut ic, &mut part_of_fortune])
+            .chain(lots.iter_mut().map(|lot| &mut lot.planet))
+        {
+            let placement = HousePlacement::new(planet, house_name, &cusps, armc, geo.lat);
+            planet.house = Some(placement.house);
+            planet.mundane_position = placement.mundane_position;
+        }
+
+        // 计算日主星、时主星
+        let planetary_hours_of_day = PlanetaryHours::new(&date, &geo, ephe_path)?;
+        let planetary_day = planetary_hours_of_day.planetary_day;
+        let planetary_hours = planetary_hours_of_day.ruler_at(&date);
+
         // 计算相位和映点
         let mut aspects: Vec<Aspect> = vec![];
         let mut antiscoins: Vec<Aspect> = vec![];
         let mut contraantiscias: Vec<Aspect> = vec![];
 
+        // 日心时行星不与四轴、福点成相位、映点、平行
         let mut asm_and_planets: Vec<_> = planets.iter().collect();
-        asm_and_planets.push(&asc);
-        asm_and_planets.push(&mc);
-        asm_and_planets.push(&dsc);
-        asm_and_planets.push(&ic);
-        asm_and_planets.push(&part_of_fortune);
+        if !heliocentric {
+            asm_and_planets.push(&asc);
+            asm_and_planets.push(&mc);
+            asm_and_planets.push(&dsc);
+            asm_and_planets.push(&ic);
+            asm_and_planets.push(&part_of_fortune);
+        }
+        asm_and_planets.extend(lots.iter().map(|lot| &lot.planet));
+        let tropical_planets: Vec<_> = asm_and_planets
+            .iter()
+            .map(|p| tropical(p, ayanamsa))
+            .collect();
         for i in 0..asm_and_planets.len() {
             for j in i..asm_and_planets.len() {
-                let aspect = asm_and_planets[i].has_aspect(asm_and_planets[j], false);
-                if let Some(aspect) = aspect {
+                let aspect = asm_and_planets[i].has_aspect_with_config(
+                    asm_and_planets[j],
+                    false,
+                    &config.aspects,
+                );
+                if let Some(mut aspect) = aspect {
+                    aspect.receptions =
+                        calc_receptions(asm_and_planets[i], asm_and_planets[j], is_diurnal);
                     aspects.push(aspect)
                 }
 
-                let antiscoin = asm_and_planets[i].has_antiscoin(asm_and_planets[j]);
+                let antiscoin = tropical_planets[i].has_antiscoin(&tropical_planets[j]);
                 if let Some(aspect) = antiscoin {
                     antiscoins.push(aspect)
                 }
 
-                let contraantiscia = asm_and_planets[i].has_contraantiscia(asm_and_planets[j]);
+                let contraantiscia = tropical_planets[i].has_contraantiscia(&tropical_planets[j]);
                 if let Some(aspect) = contraantiscia {
                     contraantiscias.push(aspect)
                 }
             }
         }
 
+        // 计算互容、定位星
+        let mutual_receptions = calc_mutual_receptions(&planets, is_diurnal);
+        let dispositors = Dispositors::new(&planets);
+
+        // 计算平行、对等平行
+        let mut parallels: Vec<Aspect> = vec![];
+        let mut contraparallels: Vec<Aspect> = vec![];
+        for (i, p0) in asm_and_planets.iter().enumerate() {
+            for p1 in &asm_and_planets[i + 1..] {
+                if skip_parallel(p0, p1) {
+                    continue;
+                }
+
+                if let Some(aspect) = p0.has_parallel(p1, config.parallels.orb) {
+                    parallels.push(aspect)
+                }
+                if let Some(aspect) = p0.has_contraparallel(p1, config.parallels.orb) {
+                    contraparallels.push(aspect)
+                }
+            }
+        }
+
+        // 计算十二分部、九分部，及其与本命行星、四轴的相位，日心时不含四轴
+        let mut divisional_points: Vec<_> = planets.iter().collect();
+        if !heliocentric {
+            divisional_points.extend([&asc, &mc, &dsc, &ic]);
+        }
+        let divisional_positions = divisional_points
+            .iter()
+            .map(|p| DivisionalPosition::new(p.name, p.long))
+            .collect();
+        let (dodecatemorion_aspects, navamsa_aspects) =
+            calc_divisional_aspects(&divisional_points, &config.aspects);
+
+        // 计算行星、上升点、中天的中点、中点树，及在度盘上的排序，日心时不含上升点、中天
+        let mut midpoint_points: Vec<_> = planets.iter().collect();
+        if !heliocentric {
+            midpoint_points.extend([&asc, &mc]);
+        }
+        let midpoints = calc_midpoints(&midpoint_points);
+        let midpoint_trees = calc_midpoint_trees(&midpoint_points, &midpoints, &config.midpoints);
+        let dial_positions = dial_sort(&midpoint_points, config.midpoints.dial);
+
         // 计算恒星
-        let fixed_stars = calc_fixed_star_long(date.jd_ut1, ephe_path)?;
+        let mut fixed_stars = calc_fixed_star_long(date.jd_ut1, &config.fixed_stars, ephe_path)?;
+        for fixed_star in fixed_stars.iter_mut() {
+            fixed_star.long = swe_degnorm(fixed_star.long - ayanamsa);
+        }
+        let fixed_star_conjunctions =
+            calc_conjunctions(&fixed_stars, &asm_and_planets, &cusps, &config.fixed_stars);
+        let paran_planets: Vec<_> = planets
+            .iter()
+            .filter(|p| !matches!(p.name, PlanetName::NorthNode | PlanetName::SouthNode))
+            .collect();
+        // 日心时行星不在地平上升降，不计算Paran
+        let parans = if heliocentric {
+            vec![]
+        } else {
+            calc_parans(
+                date.jd_ut1,
+                &fixed_stars,
+                &paran_planets,
+                geo.lat,
+                &config.fixed_stars,
+                ephe_path,
+            )?
+        };
 
         Ok(Self {
             date,
             geo,
             house_name,
-            cusps: cusps[1..13].to_vec(),
+            cusps,
             asc,
             mc,
             dsc,
             ic,
             part_of_fortune,
+            lots,
             planets,
             is_diurnal,
+            sect,
             planetary_day,
             planetary_hours,
+            moon_condition,
             aspects,
+            mutual_receptions,
+            dispositors,
             antiscoins,
             contraantiscias,
+            parallels,
+            contraparallels,
+            divisional_positions,
+            dodecatemorion_aspects,
+            navamsa_aspects,
+            midpoints,
+            midpoint_trees,
+            dial_positions,
             fixed_stars,
+            fixed_star_conjunctions,
+            parans,
+            almuten,
+            zodiac: config.zodiac,
+            position_mode: config.position_mode,
+            node_type: config.node_type,
+            ayanamsa,
             eps,
         })
     }
@@ -351,9 +579,17 @@ pub struct HoroscopeComparison {
     /// 福点
     pub original_part_of_fortune: Planet,
     pub comparison_part_of_fortune: Planet,
+    /// 原盘阿拉伯点
+    pub original_lots: Vec<Lot>,
+    /// 比较盘阿拉伯点
+    pub comparison_lots: Vec<Lot>,
     /// 七颗行星
     pub original_planets: Vec<Planet>,
     pub comparison_planets: Vec<Planet>,
+    /// 原盘星体落在比较盘的宫位
+    pub original_in_comparison_houses: Vec<HousePlacement>,
+    /// 比较盘星体落在原盘的宫位
+    pub comparison_in_original_houses: Vec<HousePlacement>,
 
     /// 行星相位，仅包含四轴、行星间的相位
     pub aspects: Vec<Aspect>,
@@ -361,6 +597,10 @@ pub struct HoroscopeComparison {
     pub antiscoins: Vec<Aspect>,
     /// 反映点
     pub contraantiscias: Vec<Aspect>,
+    /// 平行，相位值为0
+    pub parallels: Vec<Aspect>,
+    /// 对等平行，相位值为180
+    pub contraparallels: Vec<Aspect>,
 }
 
 impl HoroscopeComparison {
@@ -371,21 +611,29 @@ impl HoroscopeComparison {
         process_geo: GeoPosition,
         house_name: HouseName,
         planets_config: &[PlanetConfig],
+        config: &HoroscopeConfig,
         ephe_path: &str,
     ) -> Result<Self, Error> {
         // 计算原星盘
-        let horo = Horoscope::new(date, geo, house_name, planets_config, ephe_path)?;
+        let horo = Horoscope::new(date, geo, house_name, planets_config, config, ephe_path)?;
 
         let horo_compare = Horoscope::new(
             date_compare,
             process_geo,
             house_name,
             planets_config,
+            config,
             ephe_path,
         )?;
 
         // 计算相位和映点
-        let (aspects, antiscoins, contraantiscias) = Self::calculate_aspects(&horo, &horo_compare);
+        let (aspects, antiscoins, contraantiscias) =
+            Self::calculate_aspects(&horo, &horo_compare, config);
+        // 计算平行、对等平行
+        let (parallels, contraparallels) = Self::calculate_parallels(&horo, &horo_compare, config);
+        // 计算星体落在对方星盘的宫位
+        let original_in_comparison_houses = Self::calculate_house_placements(&horo, &horo_compare);
+        let comparison_in_original_houses = Self::calculate_house_placements(&horo_compare, &horo);
 
         Ok(Self {
             original_date: date,
@@ -406,53 +654,60 @@ impl HoroscopeComparison {
             // 福点
             original_part_of_fortune: horo.part_of_fortune,
             comparison_part_of_fortune: horo_compare.part_of_fortune,
+            // 阿拉伯点
+            original_lots: horo.lots,
+            comparison_lots: horo_compare.lots,
             // 行星
             original_planets: horo.planets,
             comparison_planets: horo_compare.planets,
+            original_in_comparison_houses,
+            comparison_in_original_houses,
             aspects,
             antiscoins,
             contraantiscias,
+            parallels,
+            contraparallels,
         })
     }
 
-    fn calculate_aspects(
+    pub(crate) fn calculate_aspects(
         horo: &Horoscope,
         horo_compare: &Horoscope,
+        config: &HoroscopeConfig,
     ) -> (Vec<Aspect>, Vec<Aspect>, Vec<Aspect>) {
         let mut aspects: Vec<Aspect> = vec![];
         let mut antiscoins: Vec<Aspect> = vec![];
         let mut contraantiscias: Vec<Aspect> = vec![];
 
-        let mut asm_and_planets: Vec<_> = horo.planets.iter().collect();
-        asm_and_planets.push(&horo.asc);
-        asm_and_planets.push(&horo.mc);
-        asm_and_planets.push(&horo.dsc);
-        asm_and_planets.push(&horo.ic);
-        // 福点
-        asm_and_planets.push(&horo.part_of_fortune);
-
-        let mut asm_and_planets_compare: Vec<_> = horo_compare.planets.iter().collect();
-        asm_and_planets_compare.push(&horo_compare.asc);
-        asm_and_planets_compare.push(&horo_compare.mc);
-        asm_and_planets_compare.push(&horo_compare.dsc);
-        asm_and_planets_compare.push(&horo_compare.ic);
-        // 福点
-        asm_and_planets_compare.push(&horo_compare.part_of_fortune);
+        let asm_and_planets = Self::aspect_points(horo);
+        let asm_and_planets_compare = Self::aspect_points(horo_compare);
+        let tropical_planets: Vec<_> = asm_and_planets
+            .iter()
+            .map(|p| tropical(p, horo.ayanamsa))
+            .collect();
+        let tropical_planets_compare: Vec<_> = asm_and_planets_compare
+            .iter()
+            .map(|p| tropical(p, horo_compare.ayanamsa))
+            .collect();
 
         for i in 0..asm_and_planets.len() {
             for j in 0..asm_and_planets_compare.len() {
-                let aspect = asm_and_planets[i].has_aspect(asm_and_planets_compare[j], true);
+                let aspect = asm_and_planets[i].has_aspect_with_config(
+                    asm_and_planets_compare[j],
+                    true,
+                    &config.aspects,
+                );
                 if let Some(aspect) = aspect {
                     aspects.push(aspect)
                 }
 
-                let antiscoin = asm_and_planets[i].has_antiscoin(asm_and_planets_compare[j]);
+                let antiscoin = tropical_planets[i].has_antiscoin(&tropical_planets_compare[j]);
                 if let Some(aspect) = antiscoin {
                     antiscoins.push(aspect)
                 }
 
                 let contraantiscia =
-                    asm_and_planets[i].has_contraantiscia(asm_and_planets_compare[j]);
+                    tropical_planets[i].has_contraantiscia(&tropical_planets_compare[j]);
                 if let Some(aspect) = contraantiscia {
                     contraantiscias.push(aspect)
                 }
@@ -461,18 +716,101 @@ impl HoroscopeComparison {
 
         (aspects, antiscoins, contraantiscias)
     }
+
+    pub(crate) fn calculate_parallels(
+        horo: &Horoscope,
+        horo_compare: &Horoscope,
+        config: &HoroscopeConfig,
+    ) -> (Vec<Aspect>, Vec<Aspect>) {
+        let mut parallels: Vec<Aspect> = vec![];
+        let mut contraparallels: Vec<Aspect> = vec![];
+
+        let asm_and_planets_compare = Self::aspect_points(horo_compare);
+        for p0 in Self::aspect_points(horo) {
+            for p1 in &asm_and_planets_compare {
+                if let Some(aspect) = p0.has_parallel(p1, config.parallels.orb) {
+                    parallels.push(aspect)
+                }
+                if let Some(aspect) = p0.has_contraparallel(p1, config.parallels.orb) {
+                    contraparallels.push(aspect)
+                }
+            }
+        }
+
+        (parallels, contraparallels)
+    }
+
+    // horo的星体落在other的宫位，日心时不计算
+    fn calculate_house_placements(horo: &Horoscope, other: &Horoscope) -> Vec<HousePlacement> {
+        if horo.position_mode == PositionMode::Heliocentric {
+            return vec![];
+        }
+        Self::aspect_points(horo)
+            .into_iter()
+            .map(|p| {
+                HousePlacement::new(
+                    p,
+                    other.house_name,
+                    &other.cusps,
+                    other.mc.ra,
+                    other.geo.lat,
+                )
+            })
+            .collect()
+    }
+
+    // 参与相位计算的点：行星、四轴、福点、阿拉伯点，日心时只有行星
+    fn aspect_points(horo: &Horoscope) -> Vec<&Planet> {
+        let mut points: Vec<_> = horo.planets.iter().collect();
+        if horo.position_mode == PositionMode::Heliocentric {
+            return points;
+        }
+        points.push(&horo.asc);
+        points.push(&horo.mc);
+        points.push(&horo.dsc);
+        points.push(&horo.ic);
+        // 福点
+        points.push(&horo.part_of_fortune);
+        // 阿拉伯点
+        points.extend(horo.lots.iter().map(|lot| &lot.planet));
+        points
+    }
+}
+
+// 四轴之间、南北交点之间的赤纬关系由计算方法决定，不计算平行、对等平行
+fn skip_parallel(p0: &Planet, p1: &Planet) -> bool {
+    let ascmc = [
+        PlanetName::ASC,
+        PlanetName::MC,
+        PlanetName::DSC,
+        PlanetName::IC,
+    ];
+    let nodes = [PlanetName::NorthNode, PlanetName::SouthNode];
+    (ascmc.contains(&p0.name) && ascmc.contains(&p1.name))
+        || (nodes.contains(&p0.name) && nodes.contains(&p1.name))
+}
+
+// 映点、反映点以回归黄道的冬夏至轴对称，计算前将黄经换回回归黄道
+fn tropical(planet: &Planet, ayanamsa: f64) -> Planet {
+    let mut planet = *planet;
+    planet.long = swe_degnorm(planet.long + ayanamsa);
+    planet
 }
 
 //   计算行星
 fn calc_planets(
     jd_ut: f64,
     planets_config: &[PlanetConfig],
+    node_type: NodeType,
+    ayanamsa: f64,
+    geo: &GeoPosition,
+    position_mode: PositionMode,
     ephe_path: &str,
 ) -> Result<Vec<Planet>, Error> {
     swe_set_ephe_path(ephe_path);
 
     let mut planets = vec![];
-    let planet_names = [
+    let mut planet_names = vec![
         PlanetName::Sun,
         PlanetName::Moon,
         PlanetName::Mercury,
@@ -480,8 +818,20 @@ fn calc_planets(
         PlanetName::Mars,
         PlanetName::Jupiter,
         PlanetName::Saturn,
-        PlanetName::NorthNode,
     ];
+    // 天王星、海王星、冥王星只在行星配置中设置时计算
+    planet_names.extend(
+        [PlanetName::Uranus, PlanetName::Neptune, PlanetName::Pluto]
+            .into_iter()
+            .filter(|name| planets_config.iter().any(|p| p.name == *name)),
+    );
+    planet_names.push(PlanetName::NorthNode);
+    // 日心时太阳是中心，星盘中没有太阳，以地心的太阳求地球的位置；
+    // 月亮绕地球运行，日心位置与地球几乎相同，不计算
+    let heliocentric = position_mode == PositionMode::Heliocentric;
+    if heliocentric {
+        planet_names.retain(|name| *name != PlanetName::Moon);
+    }
     for planet_name in planet_names {
         let body = match planet_name {
             PlanetName::Sun => Body::SeSun,
@@ -491,12 +841,28 @@ fn calc_planets(
             PlanetName::Mars => Body::SeMars,
             PlanetName::Jupiter => Body::SeJupiter,
             PlanetName::Saturn => Body::SeSaturn,
-            _ => Body::SeMeanNode,
+            PlanetName::Uranus => Body::SeUranus,
+            PlanetName::Neptune => Body::SeNeptune,
+            PlanetName::Pluto => Body::SePluto,
+            _ => match node_type {
+                NodeType::Mean => Body::SeMeanNode,
+                NodeType::True => Body::SeTrueNode,
+            },
         };
-        let xx = swe_calc_ut(jd_ut, body, &[Flag::SeflgSpeed])
+        // 地心、站心或日心
+        position_mode.set_topo(geo);
+        let flags: Vec<_> = [Flag::SeflgSpeed]
+            .into_iter()
+            .chain(position_mode.flag(body))
+            .collect();
+        let xx = swe_calc_ut(jd_ut, body, &flags)
             .map_err(|e| Error::Function(format!("计算行星错误:{e}")))?;
         //计算赤经和赤纬
-        let yy = swe_calc_ut(jd_ut, body, &[Flag::SeflgEquatorial])
+        let flags: Vec<_> = [Flag::SeflgEquatorial]
+            .into_iter()
+            .chain(position_mode.flag(body))
+            .collect();
+        let yy = swe_calc_ut(jd_ut, body, &flags)
             .map_err(|e| Error::Function(format!("计算行星错误:{e}")))?;
 
         swe_close();
@@ -506,9 +872,29 @@ fn calc_planets(
             .iter()
             .find(|p| p.name == planet_name)
             .unwrap_or(&default_planet_config);
+        // 所选黄道的经度
+        let long = swe_degnorm(xx[0] - ayanamsa);
+        if heliocentric && planet_name == PlanetName::Sun {
+            let default_planet_config = PlanetConfig::default_config(&PlanetName::Earth);
+            let planet_config = planets_config
+                .iter()
+                .find(|p| p.name == PlanetName::Earth)
+                .unwrap_or(&default_planet_config);
+            let p = Planet::new(
+                PlanetName::Earth,
+                swe_degnorm(long + 180.0),
+                -xx[1],
+                xx[3],
+                swe_degnorm(yy[0] + 180.0),
+                -yy[1],
+                planet_config,
+            );
+            planets.push(p);
+            continue;
+        }
         let p = Planet::new(
             planet_name.clone(),
-            xx[0],
+            long,
             xx[1],
             xx[3],
             yy[0],
@@ -519,7 +905,7 @@ fn calc_planets(
         if planet_name == PlanetName::NorthNode {
             let p = Planet::new(
                 PlanetName::SouthNode,
-                swe_degnorm(xx[0] + 180.0),
+                swe_degnorm(long + 180.0),
                 xx[1],
                 xx[3],
                 swe_degnorm(yy[0] + 180.0),
@@ -532,81 +918,3 @@ fn calc_planets(
 
     Ok(planets)
 }
-
-/// 计算给定时刻之前，太阳在东方地平线上的时刻
-fn sun_on_asc(t: &HoroDateTime, geo: &GeoPosition, ephe_path: &str) -> Result<HoroDateTime, Error> {
-    swe_set_ephe_path(ephe_path);
-    let xx = swe_calc_ut(t.jd_ut1, Body::SeSun, &[])
-        .map_err(|e| Error::Function(format!("函数sun_on_asc计算太阳位置错误:{e}")))?;
-    swe_close();
-
-    let sun_long = xx[0];
-
-    let (_, ascmc) = swe_houses(t.jd_ut1, geo.lat, geo.long, HouseSystem::B)
-        .map_err(|_e| Error::Function("函数sun_on_asc()，计算asc错误".to_string()))?;
-
-    let asc_long = ascmc[0];
-
-    // 为牛顿迭代设置初值，太阳在东方地平线时刻大约等于jd0
-    let d = swe_degnorm(asc_long - sun_long);
-    // 太阳周日运动，1天走过360度，走1度需要1/360天，走d度，需要1/360*d天
-    // 因此jd0 = jd_utc - d/360.0
-    let jd0 = t.jd_ut1 - d / 360.0;
-
-    let jd = newton_iteration(jd0, |jd| {
-        swe_set_ephe_path(ephe_path);
-        let xx = swe_calc_ut(jd, Body::SeSun, &[]).map_err(|e| {
-            Error::Function(format!("函数sun_on_asc()，牛顿迭代计算太阳位置错误:{e}"))
-        })?;
-        let (_, ascmc) = swe_houses(jd, geo.lat, geo.long, HouseSystem::B)
-            .map_err(|_e| Error::Function("函数sun_on_asc()，牛顿迭代计算asc错误".to_string()))?;
-        swe_close();
-
-        // 将结果转换到[0, 360)，再将结果转换到[-180, 180]
-        // 这样可以确保曲线上太阳与asc黄经度数相等的点是连续的，
-        Ok(mod180(swe_degnorm(ascmc[0] - xx[0])))
-    })?;
-
-    let date = HoroDateTime::from_jd_ut1_zone(jd, t.tz)?;
-    Ok(date)
-}
-
-/// 计算太阳在西方地平线上的时刻
-/// 如果太阳在地平线上，则计算之后的时刻
-/// 如果太阳在地平线下，则计算之前的时刻
-fn sun_on_dsc(t: &HoroDateTime, geo: &GeoPosition, ephe_path: &str) -> Result<HoroDateTime, Error> {
-    swe_set_ephe_path(ephe_path);
-    let xx = swe_calc_ut(t.jd_ut1, Body::SeSun, &[])
-        .map_err(|e| Error::Function(format!("函数sun_on_dsc计算太阳位置错误:{e}")))?;
-    swe_close();
-
-    let sun_long = xx[0];
-
-    let (_, ascmc) = swe_houses(t.jd_ut1, geo.lat, geo.long, HouseSystem::B)
-        .map_err(|_e| Error::Function("函数sun_on_dsc()，计算asc错误".to_string()))?;
-
-    let asc_long = ascmc[0];
-
-    let d = swe_degnorm(asc_long - sun_long);
-    // 假定白天长度为0.5天
-    let jd0 = t.jd_ut1 - d / 360.0 + 0.5;
-
-    let jd = newton_iteration(jd0, |jd| {
-        swe_set_ephe_path(ephe_path);
-
-        let xx = swe_calc_ut(jd, Body::SeSun, &[]).map_err(|e| {
-            Error::Function(format!("函数sun_on_dsc()，牛顿迭代计算太阳位置错误:{e}"))
-        })?;
-        let (_, ascmc) = swe_houses(jd, geo.lat, geo.long, HouseSystem::B)
-            .map_err(|_e| Error::Function("函数sun_on_dsc()，牛顿迭代计算asc错误".to_string()))?;
-
-        swe_close();
-
-        // 将结果转换到[0, 360)，再将结果转换到[-180, 180]
-        // 这样可以确保曲线上太阳与asc黄经度数相等的点是连续的，
-        Ok(mod180(swe_degnorm(swe_degnorm(ascmc[0] + 180.0) - xx[0])))
-    })?;
-
-    let date = HoroDateTime::from_jd_ut1_zone(jd, t.tz)?;
-    Ok(date)
-}
@@ -7,12 +7,20 @@ use swe::{
 };
 
 use crate::{
-    Horoscope,
+    AlmutenPoint, AlmutenPointName, AspectConfig, AspectType, Ayanamsa, ConditionConfig,
+    DignityScoring, DignityType, Error, EssentialDignities, FixedStarCatalogue, FixedStarConfig,
+    FixedStarName, FixedStarPoint, HarmonicChart, Horoscope, HoroscopeConfig, LotConfig, LotName,
+    MidpointConfig, MoonPhase, MotionDirection, NodeType, ParallelConfig,
     PlanetName::{self, *},
+    PositionMode, Sect, SolarOrientation, SolarPhase,
     config::PlanetConfig,
+    dignity::{dignity_ruler, domicile_ruler, sign_of},
+    division::{dodecatemorion, navamsa},
     house::HouseName,
+    midpoint::{Dial, midpoint},
     planet::PlanetSpeedState::*,
     utils::calc_eps,
+    zodiac::Zodiac,
 };
 
 #[test]
@@ -40,6 +48,7 @@ fn test_horoscope_new() {
         geo.clone(),
         house.clone(),
         &planet_configs,
+        &HoroscopeConfig::default(),
         &ephe_path,
     );
     assert!(horo.is_ok());
@@ -245,9 +254,17 @@ fn test_diurnal() {
         geo.clone(),
         house.clone(),
         &planet_configs,
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    );
+    let horo_nocturnal = Horoscope::new(
+        nocturnal,
+        geo,
+        house.clone(),
+        &planet_configs,
+        &HoroscopeConfig::default(),
         &ephe_path,
     );
-    let horo_nocturnal = Horoscope::new(nocturnal, geo, house.clone(), &planet_configs, &ephe_path);
 
     assert!(horo_diurnal.is_ok());
     assert!(horo_nocturnal.is_ok());
@@ -270,8 +287,22 @@ fn test_diurnal() {
     assert!(geo.is_ok());
     let geo = geo.unwrap();
 
-    let h0 = Horoscope::new(t0, geo.clone(), house.clone(), &planet_configs, &ephe_path);
-    let h1 = Horoscope::new(t1, geo, house, &planet_configs, &ephe_path);
+    let h0 = Horoscope::new(
+        t0,
+        geo.clone(),
+        house.clone(),
+        &planet_configs,
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    );
+    let h1 = Horoscope::new(
+        t1,
+        geo,
+        house,
+        &planet_configs,
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    );
 
     assert!(h0.is_ok());
     assert!(h1.is_ok());
@@ -317,6 +348,7 @@ fn test_planetary_day() {
             geo.clone(),
             house.clone(),
             &planet_configs,
+            &HoroscopeConfig::default(),
             &ephe_path,
         )
         .unwrap();
@@ -325,6 +357,7 @@ fn test_planetary_day() {
             geo.clone(),
             house.clone(),
             &planet_configs,
+            &HoroscopeConfig::default(),
             &ephe_path,
         )
         .unwrap();
@@ -361,6 +394,7 @@ fn test_planetary_hours() {
             geo.clone(),
             HouseName::Alcabitus,
             &planet_configs,
+            &HoroscopeConfig::default(),
             &ephe_path,
         )
         .unwrap()
@@ -371,7 +405,15 @@ fn test_planetary_hours() {
         let t = t0
             .plus_days((t1.jd_utc - t0.jd_utc) * f64::from(i) / 12.0)
             .unwrap();
-        let h = Horoscope::new(t, geo, HouseName::Alcabitus, &planet_configs, &ephe_path).unwrap();
+        let h = Horoscope::new(
+            t,
+            geo,
+            HouseName::Alcabitus,
+            &planet_configs,
+            &HoroscopeConfig::default(),
+            &ephe_path,
+        )
+        .unwrap();
         assert_eq!(
             planetary_hours_list[((1 + i) % 7) as usize],
             h.planetary_hours,
@@ -387,6 +429,7 @@ fn test_planetary_hours() {
             geo.clone(),
             HouseName::Alcabitus,
             &planet_configs,
+            &HoroscopeConfig::default(),
             &ephe_path,
         )
         .unwrap()
@@ -404,6 +447,7 @@ fn test_planetary_hours() {
             geo.clone(),
             HouseName::Alcabitus,
             &planet_configs,
+            &HoroscopeConfig::default(),
             &ephe_path,
         )
         .unwrap();
@@ -418,9 +462,16 @@ fn test_planetary_hours() {
 
     assert_eq!(
         Sun,
-        Horoscope::new(t2, geo, HouseName::Alcabitus, &planet_configs, &ephe_path)
-            .unwrap()
-            .planetary_hours,
+        Horoscope::new(
+            t2,
+            geo,
+            HouseName::Alcabitus,
+            &planet_configs,
+            &HoroscopeConfig::default(),
+            &ephe_path
+        )
+        .unwrap()
+        .planetary_hours,
         "日出前的行星时"
     )
 }
@@ -446,7 +497,14 @@ fn test_daytime_fortunes() {
 
     let diurnal = diurnal.unwrap();
 
-    let horo_diurnal = Horoscope::new(diurnal, geo, house, &planet_configs, &ephe_path);
+    let horo_diurnal = Horoscope::new(
+        diurnal,
+        geo,
+        house,
+        &planet_configs,
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    );
 
     let horo_diurnal = horo_diurnal.unwrap();
 
@@ -517,7 +575,14 @@ fn test_nocturnal_fortunes() {
 
     let nocturnal = nocturnal.unwrap();
 
-    let horo_nocturnal = Horoscope::new(nocturnal, geo, house.clone(), &planet_configs, &ephe_path);
+    let horo_nocturnal = Horoscope::new(
+        nocturnal,
+        geo,
+        house.clone(),
+        &planet_configs,
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    );
 
     let horo_nocturnal = horo_nocturnal.unwrap();
 
@@ -566,3 +631,1535 @@ fn test_nocturnal_fortunes() {
         "夜间盘的福点速度是“均”"
     );
 }
+
+// 必然尊贵
+#[test]
+fn test_dignities() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let planet_configs = PlanetConfig::default_all_configs();
+
+    for scoring in [DignityScoring::Lilly, DignityScoring::DignitiesOnly] {
+        let config = HoroscopeConfig {
+            dignity_scoring: scoring,
+            ..Default::default()
+        };
+        let horo = Horoscope::new(
+            t,
+            geo,
+            HouseName::Alcabitus,
+            &planet_configs,
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+
+        for planet in &horo.planets {
+            let expected =
+                EssentialDignities::new(planet.name, planet.long, horo.is_diurnal, scoring);
+            assert_eq!(expected, planet.dignities, "{:?}的必然尊贵", planet.name);
+        }
+
+        // 七颗行星都有必然尊贵
+        assert_eq!(
+            7,
+            horo.planets
+                .iter()
+                .filter(|p| p.dignities.is_some())
+                .count()
+        );
+
+        // 四轴、福点没有必然尊贵
+        assert!(horo.asc.dignities.is_none());
+        assert!(horo.mc.dignities.is_none());
+        assert!(horo.dsc.dignities.is_none());
+        assert!(horo.ic.dignities.is_none());
+        assert!(horo.part_of_fortune.dignities.is_none());
+    }
+}
+
+// 胜利星
+#[test]
+fn test_almuten() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    let sun = horo.planets.iter().find(|p| p.name == Sun).unwrap();
+    let moon = horo.planets.iter().find(|p| p.name == Moon).unwrap();
+    let points = [
+        (Sun, sun.long),
+        (Moon, moon.long),
+        (ASC, horo.asc.long),
+        (MC, horo.mc.long),
+        (PartOfFortune, horo.part_of_fortune.long),
+    ];
+
+    assert_eq!(points.len(), horo.almuten.figuris_points.len());
+    for ((name, long), point) in points.iter().zip(&horo.almuten.figuris_points) {
+        let expected = AlmutenPoint::new(AlmutenPointName::Planet(*name), *long, horo.is_diurnal);
+        assert_eq!(&expected, point, "{:?}的胜利星", name);
+    }
+
+    assert_eq!(12, horo.almuten.cusps.len());
+    for (i, point) in horo.almuten.cusps.iter().enumerate() {
+        let expected = AlmutenPoint::new(
+            AlmutenPointName::Cusp(i as u8 + 1),
+            horo.cusps[i],
+            horo.is_diurnal,
+        );
+        assert_eq!(&expected, point, "第{}宫宫头的胜利星", i + 1);
+    }
+
+    // 全盘胜利星是总分最高的行星
+    let max = horo
+        .almuten
+        .figuris_scores
+        .iter()
+        .map(|s| s.score)
+        .max()
+        .unwrap();
+    assert!(!horo.almuten.figuris.is_empty());
+    for planet in &horo.almuten.figuris {
+        let score = horo
+            .almuten
+            .figuris_scores
+            .iter()
+            .find(|s| s.planet == *planet)
+            .unwrap();
+        assert_eq!(max, score.score);
+    }
+}
+
+// 天王星、海王星、冥王星
+#[test]
+fn test_outer_planets() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+
+    // 默认不计算
+    let horo = Horoscope::new(
+        t.clone(),
+        geo.clone(),
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+    assert_eq!(9, horo.planets.len());
+    assert!(
+        !horo
+            .planets
+            .iter()
+            .any(|p| matches!(p.name, Uranus | Neptune | Pluto))
+    );
+
+    let horo_with_outer_planets = Horoscope::new(
+        t.clone(),
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_configs(true),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+    assert_eq!(12, horo_with_outer_planets.planets.len());
+
+    for (planet_name, body) in [
+        (Uranus, Body::SeUranus),
+        (Neptune, Body::SeNeptune),
+        (Pluto, Body::SePluto),
+    ] {
+        let p = horo_with_outer_planets
+            .planets
+            .iter()
+            .find(|p| p.name == planet_name);
+        assert!(p.is_some(), "{:?}", planet_name);
+        let p = p.unwrap();
+
+        swe_set_ephe_path(&ephe_path);
+        let xx = swe_calc_ut(t.jd_ut1, body, &[Flag::SeflgSpeed]).unwrap();
+        let yy = swe_calc_ut(t.jd_ut1, body, &[Flag::SeflgEquatorial]).unwrap();
+        swe_close();
+
+        let config = PlanetConfig::default_config(&planet_name);
+        assert_eq!(xx[0], p.long, "{:?}", planet_name);
+        assert_eq!(xx[1], p.lat, "{:?}黄纬", planet_name);
+        assert_eq!(xx[3], p.speed, "{:?}黄道上每日速度", planet_name);
+        assert_eq!(yy[0], p.ra, "{:?}赤经", planet_name);
+        assert_eq!(yy[1], p.dec, "{:?}赤纬", planet_name);
+        assert_eq!(config.orb, p.orb, "{:?}容许度", planet_name);
+        assert!(p.dignities.is_none(), "{:?}没有必然尊贵", planet_name);
+    }
+
+    // 其它行星不变
+    for planet in &horo.planets {
+        let p = horo_with_outer_planets
+            .planets
+            .iter()
+            .find(|p| p.name == planet.name)
+            .unwrap();
+        assert_eq!(planet.long, p.long, "{:?}", planet.name);
+    }
+
+    // 不含外行星的相位不变
+    let aspects: Vec<_> = horo
+        .aspects
+        .iter()
+        .map(|a| (a.aspect_value, a.p0, a.p1))
+        .collect();
+    let aspects_with_outer_planets: Vec<_> = horo_with_outer_planets
+        .aspects
+        .iter()
+        .map(|a| (a.aspect_value, a.p0, a.p1))
+        .collect();
+    for aspect in &aspects {
+        assert!(aspects_with_outer_planets.contains(aspect));
+    }
+    for aspect in &aspects_with_outer_planets {
+        let has_outer_planets = [aspect.1, aspect.2]
+            .iter()
+            .any(|name| matches!(name, Uranus | Neptune | Pluto));
+        assert_eq!(!has_outer_planets, aspects.contains(aspect));
+    }
+}
+
+// 真交点
+#[test]
+fn test_true_node() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let config = HoroscopeConfig {
+        node_type: NodeType::True,
+        ..Default::default()
+    };
+    let horo = Horoscope::new(
+        t.clone(),
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+
+    swe_set_ephe_path(&ephe_path);
+    let xx = swe_calc_ut(t.jd_ut1, Body::SeTrueNode, &[Flag::SeflgSpeed]).unwrap();
+    let yy = swe_calc_ut(t.jd_ut1, Body::SeTrueNode, &[Flag::SeflgEquatorial]).unwrap();
+    swe_close();
+
+    let north_node = horo.planets.iter().find(|p| p.name == NorthNode).unwrap();
+    let south_node = horo.planets.iter().find(|p| p.name == SouthNode).unwrap();
+
+    assert_eq!(xx[0], north_node.long, "黄经，北交点");
+    assert_eq!(xx[3], north_node.speed, "黄道上每日速度, 北交点");
+    assert_eq!(yy[0], north_node.ra, "赤经, 北交点");
+    assert_eq!(yy[1], north_node.dec, "赤纬, 北交点");
+
+    assert_eq!(swe_degnorm(xx[0] + 180.0), south_node.long, "黃经，南交点");
+    assert_eq!(swe_degnorm(yy[0] + 180.0), south_node.ra, "赤经, 南交点");
+    assert_eq!(-yy[1], south_node.dec, "赤纬, 南交点");
+}
+
+// 恒星黄道
+#[test]
+fn test_sidereal_zodiac() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let planet_configs = PlanetConfig::default_all_configs();
+
+    let tropical = Horoscope::new(
+        t.clone(),
+        geo.clone(),
+        HouseName::Alcabitus,
+        &planet_configs,
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+    assert_eq!(Zodiac::Tropical, tropical.zodiac);
+    assert_eq!(0.0, tropical.ayanamsa);
+
+    let zodiac = Zodiac::Sidereal(Ayanamsa::Lahiri);
+    let config = HoroscopeConfig {
+        zodiac,
+        ..Default::default()
+    };
+    let sidereal = Horoscope::new(
+        t.clone(),
+        geo,
+        HouseName::Alcabitus,
+        &planet_configs,
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+
+    let ayanamsa = zodiac.ayanamsa(t.jd_ut1, &ephe_path);
+    assert_eq!(zodiac, sidereal.zodiac);
+    assert_eq!(ayanamsa, sidereal.ayanamsa);
+    assert!(ayanamsa > 24.0 && ayanamsa < 24.2, "ayanamsa={ayanamsa}");
+
+    let assert_shift = |tropical_long: f64, sidereal_long: f64, name: &str| {
+        let expected = swe_degnorm(tropical_long - ayanamsa);
+        assert!(
+            (expected - sidereal_long).abs() < 1e-9,
+            "{name}: {expected} != {sidereal_long}"
+        );
+    };
+
+    for i in 0..12 {
+        assert_shift(tropical.cusps[i], sidereal.cusps[i], "宫头");
+    }
+
+    for (p0, p1) in [
+        (&tropical.asc, &sidereal.asc),
+        (&tropical.mc, &sidereal.mc),
+        (&tropical.dsc, &sidereal.dsc),
+        (&tropical.ic, &sidereal.ic),
+        (&tropical.part_of_fortune, &sidereal.part_of_fortune),
+    ]
+    .into_iter()
+    .chain(tropical.planets.iter().zip(&sidereal.planets))
+    {
+        assert_eq!(p0.name, p1.name);
+        assert_shift(p0.long, p1.long, &format!("{:?}", p0.name));
+        // 赤经、赤纬与黄道无关
+        assert!((p0.ra - p1.ra).abs() < 1e-9, "{:?}赤经", p0.name);
+        assert!((p0.dec - p1.dec).abs() < 1e-9, "{:?}赤纬", p0.name);
+        assert_eq!(p0.lat, p1.lat, "{:?}黄纬", p0.name);
+    }
+
+    for (s0, s1) in tropical.fixed_stars.iter().zip(&sidereal.fixed_stars) {
+        assert_shift(s0.long, s1.long, "恒星");
+    }
+
+    assert_eq!(tropical.is_diurnal, sidereal.is_diurnal);
+    assert_eq!(tropical.planetary_hours, sidereal.planetary_hours);
+
+    // 必然尊贵按恒星黄道计算
+    for planet in &sidereal.planets {
+        let expected = EssentialDignities::new(
+            planet.name,
+            planet.long,
+            sidereal.is_diurnal,
+            DignityScoring::default(),
+        );
+        assert_eq!(expected, planet.dignities, "{:?}的必然尊贵", planet.name);
+    }
+}
+
+// 恒星黄道的整宫制，宫头是恒星黄道星座的起点
+#[test]
+fn test_sidereal_whole_sign() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let config = HoroscopeConfig {
+        zodiac: Zodiac::Sidereal(Ayanamsa::Lahiri),
+        ..Default::default()
+    };
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::WholeSign,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+
+    // 第1宫是恒星黄道中上升点所在的星座
+    assert_eq!(
+        (horo.asc.long / 30.0).floor() * 30.0,
+        horo.cusps[0],
+        "asc={}",
+        horo.asc.long
+    );
+    for (i, cusp) in horo.cusps.iter().enumerate() {
+        assert_eq!(0.0, cusp % 30.0, "第{}宫宫头：{cusp}", i + 1);
+        assert_eq!(
+            swe_degnorm(horo.cusps[0] + 30.0 * i as f64),
+            *cusp,
+            "第{}宫宫头",
+            i + 1
+        );
+    }
+
+    // 行星的宫位按恒星黄道的星座计算
+    for planet in &horo.planets {
+        let expected = (swe_degnorm(planet.long - horo.cusps[0]) / 30.0).floor() as u8 + 1;
+        assert_eq!(Some(expected), planet.house, "{:?}", planet.name);
+    }
+}
+
+// 启用次要相位
+#[test]
+fn test_minor_aspects() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+
+    let horo = Horoscope::new(
+        t.clone(),
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+    assert!(
+        horo.aspects
+            .iter()
+            .all(|aspect| AspectType::ptolemaic().contains(&aspect.aspect_type)),
+        "默认只有托勒密相位"
+    );
+
+    let config = HoroscopeConfig {
+        aspects: AspectConfig::all(),
+        ..Default::default()
+    };
+    let horo_all = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+
+    for aspect in &horo_all.aspects {
+        assert_eq!(aspect.aspect_type.angle(), aspect.aspect_value);
+    }
+    assert!(horo_all.aspects.len() >= horo.aspects.len());
+    assert!(
+        horo_all
+            .aspects
+            .iter()
+            .any(|aspect| AspectType::minor().contains(&aspect.aspect_type)),
+        "应有次要相位"
+    );
+}
+
+// 阿拉伯点
+#[test]
+fn test_lots() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+
+    let horo = Horoscope::new(
+        t.clone(),
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+    assert!(horo.lots.is_empty(), "默认不计算阿拉伯点");
+
+    let mut lots = LotConfig::catalogue();
+    // 自定义点：上升+水星−金星，昼夜不换
+    lots.push(LotConfig::new(LotName::Custom(0), Mercury, Venus, false));
+    let config = HoroscopeConfig {
+        lots,
+        ..Default::default()
+    };
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+    assert_eq!(horo.lots.len(), 13);
+
+    let long_of = |name: PlanetName| {
+        horo.planets
+            .iter()
+            .chain([&horo.asc, &horo.part_of_fortune])
+            .chain(horo.lots.iter().map(|lot| &lot.planet))
+            .find(|p| p.name == name)
+            .unwrap()
+            .long
+    };
+    let asc = horo.asc.long;
+    let (sun, moon) = (long_of(Sun), long_of(Moon));
+
+    // 精神点：昼，上升+日−月；夜，上升+月−日
+    let spirit = long_of(PlanetName::Lot(LotName::Spirit));
+    let expected = if horo.is_diurnal {
+        swe_degnorm(asc + sun - moon)
+    } else {
+        swe_degnorm(asc + moon - sun)
+    };
+    assert!((spirit - expected).abs() < 1e-9);
+
+    let custom = long_of(PlanetName::Lot(LotName::Custom(0)));
+    assert!((custom - swe_degnorm(asc + long_of(Mercury) - long_of(Venus))).abs() < 1e-9);
+
+    for lot in &horo.lots {
+        assert_eq!(lot.sign, (lot.planet.long / 30.0).floor() as u8);
+        assert!((1..=12).contains(&lot.house));
+        let start = horo.cusps[usize::from(lot.house) - 1];
+        let end = horo.cusps[usize::from(lot.house) % 12];
+        assert!(swe_degnorm(lot.planet.long - start) < swe_degnorm(end - start));
+    }
+
+    // 阿拉伯点参与相位计算
+    let lot_names: Vec<_> = horo.lots.iter().map(|lot| lot.planet.name).collect();
+    assert!(
+        horo.aspects
+            .iter()
+            .any(|aspect| lot_names.contains(&aspect.p0) || lot_names.contains(&aspect.p1)),
+        "应有阿拉伯点的相位"
+    );
+}
+
+// 平行、对等平行及界外
+#[test]
+fn test_parallels() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+
+    let config = HoroscopeConfig {
+        parallels: ParallelConfig { orb: 1.5 },
+        ..Default::default()
+    };
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+
+    for p in &horo.planets {
+        assert_eq!(p.out_of_bounds, p.dec.abs() > horo.eps, "{:?}界外", p.name);
+    }
+
+    let dec_of = |name: PlanetName| {
+        horo.planets
+            .iter()
+            .chain([
+                &horo.asc,
+                &horo.mc,
+                &horo.dsc,
+                &horo.ic,
+                &horo.part_of_fortune,
+            ])
+            .find(|p| p.name == name)
+            .unwrap()
+            .dec
+    };
+
+    for aspect in &horo.parallels {
+        assert_ne!(aspect.p0, aspect.p1);
+        assert_eq!(0, aspect.aspect_value);
+        let (dec0, dec1) = (dec_of(aspect.p0), dec_of(aspect.p1));
+        assert!(dec0 * dec1 >= 0.0);
+        assert!((dec0 - dec1).abs() <= 1.5);
+        assert!((aspect.d - (dec0 - dec1).abs()).abs() < 1e-9);
+    }
+
+    for aspect in &horo.contraparallels {
+        assert_eq!(180, aspect.aspect_value);
+        let (dec0, dec1) = (dec_of(aspect.p0), dec_of(aspect.p1));
+        assert!(dec0 * dec1 < 0.0);
+        assert!((dec0 + dec1).abs() <= 1.5);
+    }
+
+    // 下降点、天底的赤纬与上升点、中天相反，南北交点亦然，不计入对等平行
+    assert!(horo.contraparallels.iter().all(|aspect| {
+        !matches!(
+            (aspect.p0, aspect.p1),
+            (ASC, DSC) | (MC, IC) | (NorthNode, SouthNode)
+        )
+    }));
+}
+
+// 星体所在宫位及世俗位置
+#[test]
+fn test_house_placement() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Placidus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    for p in &horo.planets {
+        let house = p.house.unwrap();
+        let start = horo.cusps[usize::from(house) - 1];
+        let end = horo.cusps[usize::from(house) % 12];
+        assert!(
+            swe_degnorm(p.long - start) < swe_degnorm(end - start),
+            "{:?}在{}宫",
+            p.name,
+            house
+        );
+        let position = p.mundane_position.unwrap();
+        assert!(
+            (1.0..13.0).contains(&position),
+            "{:?}世俗位置{}",
+            p.name,
+            position
+        );
+    }
+
+    assert_eq!(horo.asc.house, Some(1));
+    assert_eq!(horo.mc.house, Some(10));
+    assert_eq!(horo.dsc.house, Some(7));
+    assert_eq!(horo.ic.house, Some(4));
+    assert!((horo.mc.mundane_position.unwrap() - 10.0).abs() < 1e-6);
+    assert!((horo.ic.mundane_position.unwrap() - 4.0).abs() < 1e-6);
+    assert!((horo.dsc.mundane_position.unwrap() - 7.0).abs() < 1e-6);
+
+    // 太阳黄纬近似0，世俗位置与按宫头黄经划分的宫位一致
+    let sun = horo.planets.iter().find(|p| p.name == Sun).unwrap();
+    assert_eq!(
+        sun.mundane_position.unwrap().floor() as u8,
+        sun.house.unwrap()
+    );
+
+    // 整宫制没有世俗位置，宫位按星座划分
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::WholeSign,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+    let asc_sign = (horo.asc.long / 30.0).floor() as u8;
+    for p in &horo.planets {
+        let sign = (p.long / 30.0).floor() as u8;
+        assert_eq!(
+            p.house,
+            Some((sign + 12 - asc_sign) % 12 + 1),
+            "{:?}",
+            p.name
+        );
+        assert!(p.mundane_position.is_none());
+    }
+}
+
+// 行星状态
+#[test]
+fn test_planet_conditions() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    // 2024-4-10，水星逆行
+    let t = HoroDateTime::new(2024, 4, 10, 8, 0, 0, 8.0).unwrap();
+
+    // 不搜索留的时间，按速度估算
+    let config = HoroscopeConfig {
+        conditions: ConditionConfig {
+            stations: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+    for p in &horo.planets {
+        if let Some(condition) = p.condition {
+            assert!(condition.previous_station.is_none(), "{:?}", p.name);
+            assert!(condition.next_station.is_none(), "{:?}", p.name);
+        }
+    }
+    let mercury = horo.planets.iter().find(|p| p.name == Mercury).unwrap();
+    assert_eq!(
+        mercury.condition.unwrap().direction,
+        MotionDirection::Retrograde
+    );
+
+    // 默认搜索前后最近一次留的时间
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    let sun = horo.planets.iter().find(|p| p.name == Sun).unwrap();
+    assert!(sun.condition.is_none());
+
+    for p in horo.planets.iter().filter(|p| p.name != Sun) {
+        if matches!(p.name, NorthNode | SouthNode) {
+            assert!(p.condition.is_none(), "{:?}", p.name);
+            continue;
+        }
+        let condition = p.condition.unwrap();
+
+        if condition.direction != MotionDirection::Stationary {
+            assert_eq!(
+                condition.direction == MotionDirection::Retrograde,
+                p.speed < 0.0,
+                "{:?}",
+                p.name
+            );
+        }
+
+        let d = swe_degnorm(sun.long - p.long);
+        assert!((condition.sun_distance - d.min(360.0 - d)).abs() < 1e-9);
+        assert_eq!(
+            condition.orientation == SolarOrientation::Oriental,
+            d < 180.0,
+            "{:?}",
+            p.name
+        );
+
+        if p.name == Moon {
+            assert!(condition.previous_station.is_none());
+            assert!(condition.next_station.is_none());
+        } else {
+            let previous = condition.previous_station.unwrap();
+            let next = condition.next_station.unwrap();
+            assert!(previous.jd_utc < t.jd_utc && t.jd_utc < next.jd_utc);
+        }
+    }
+
+    let mercury = horo.planets.iter().find(|p| p.name == Mercury).unwrap();
+    let condition = mercury.condition.unwrap();
+    assert_eq!(condition.direction, MotionDirection::Retrograde);
+    // 2024-4-1 22:14（UTC）留逆
+    let previous = condition.previous_station.unwrap();
+    assert_eq!((previous.year, previous.month, previous.day), (2024, 4, 2));
+    // 水星逆行靠近下合，在日光下
+    assert_ne!(condition.solar_phase, SolarPhase::Free);
+}
+
+#[test]
+fn test_moon_condition() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+    // 2024-3-25 07:00（UTC）望月
+    let t = HoroDateTime::new(2024, 3, 25, 16, 0, 0, 8.0).unwrap();
+
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    let sun = horo.planets.iter().find(|p| p.name == Sun).unwrap();
+    let moon = horo.planets.iter().find(|p| p.name == Moon).unwrap();
+    let condition = horo.moon_condition.unwrap();
+
+    assert!((condition.phase_angle - swe_degnorm(moon.long - sun.long)).abs() < 1e-6);
+    assert_eq!(condition.phase, MoonPhase::Full);
+    assert_eq!(condition.via_combusta, (195.0..225.0).contains(&moon.long));
+
+    // 月亮约2.5天换一个星座
+    let next_ingress = condition.next_ingress.jd_utc;
+    assert!(t.jd_utc < next_ingress && next_ingress < t.jd_utc + 3.0);
+
+    let last_aspect = condition.last_aspect.unwrap();
+    let next_aspect = condition.next_aspect.unwrap();
+    assert!(last_aspect.date.jd_utc < t.jd_utc);
+    assert!(t.jd_utc < next_aspect.date.jd_utc);
+    // 望月时刚与太阳对冲
+    assert_eq!(last_aspect.planet, Sun);
+    assert_eq!(last_aspect.aspect_type, AspectType::Opposition);
+
+    assert_eq!(
+        condition.void_of_course,
+        next_aspect.date.jd_utc > next_ingress
+    );
+    assert_eq!(condition.void_of_course, condition.void_since.is_some());
+    // 空亡开始于最后一个相位或进入当前星座时，不早于最后一个相位
+    if let Some(void_since) = condition.void_since {
+        assert!(last_aspect.date.jd_utc <= void_since.jd_utc + 1e-6);
+        assert!(void_since.jd_utc <= t.jd_utc);
+    }
+
+    // 恒星黄道中，燃烧之路仍按回归黄道的月亮黄经判断
+    let config = HoroscopeConfig {
+        zodiac: Zodiac::Sidereal(Ayanamsa::Lahiri),
+        ..Default::default()
+    };
+    let sidereal = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+    let sidereal_moon = sidereal.planets.iter().find(|p| p.name == Moon).unwrap();
+    let sidereal_condition = sidereal.moon_condition.unwrap();
+    assert_eq!(
+        sidereal_condition.via_combusta,
+        (195.0..225.0).contains(&swe_degnorm(sidereal_moon.long + sidereal.ayanamsa))
+    );
+    assert_eq!(sidereal_condition.via_combusta, condition.via_combusta);
+}
+
+#[test]
+fn test_fixed_star_conjunctions() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    let config = HoroscopeConfig {
+        fixed_stars: FixedStarConfig {
+            catalogue: FixedStarCatalogue::Sefstars,
+            magnitude: Some(2.0),
+            orb: 2.0,
+            ..Default::default()
+        },
+        lots: LotConfig::catalogue(),
+        ..Default::default()
+    };
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+
+    assert!(!horo.fixed_stars.is_empty());
+    assert!(horo.fixed_stars.iter().all(|s| s.magnitude <= 2.0));
+    // 内置的恒星保留描述
+    let spica = horo.fixed_stars.iter().find(|s| s.name == "Spica").unwrap();
+    assert_eq!(Some(FixedStarName::角宿一), spica.fixed_star);
+    assert_eq!(Some(FixedStarName::角宿一.desc()), spica.desc);
+
+    // 合相与逐一比较的结果相同
+    let mut points: Vec<_> = horo
+        .planets
+        .iter()
+        .chain([
+            &horo.asc,
+            &horo.mc,
+            &horo.dsc,
+            &horo.ic,
+            &horo.part_of_fortune,
+        ])
+        .chain(horo.lots.iter().map(|lot| &lot.planet))
+        .map(|p| (FixedStarPoint::Planet(p.name), p.long, 2.0))
+        .collect();
+    points.extend(
+        horo.cusps
+            .iter()
+            .enumerate()
+            .map(|(i, &cusp)| (FixedStarPoint::Cusp(i as u8 + 1), cusp, 1.0)),
+    );
+    let mut expected = 0;
+    for (point, long, orb) in points {
+        for star in &horo.fixed_stars {
+            let d = swe_degnorm(star.long - long);
+            let d = d.min(360.0 - d);
+            let conjunction = horo
+                .fixed_star_conjunctions
+                .iter()
+                .find(|c| c.point == point && c.fixed_star.nomenclature == star.nomenclature);
+            if d <= orb {
+                expected += 1;
+                let conjunction = conjunction.unwrap();
+                assert!((conjunction.distance - d).abs() < 1e-9);
+                assert_eq!(star.xiu, conjunction.fixed_star.xiu);
+            } else {
+                assert!(conjunction.is_none(), "{:?} {}", point, star.name);
+            }
+        }
+    }
+    assert_eq!(expected, horo.fixed_star_conjunctions.len());
+
+    for paran in &horo.parans {
+        assert!(paran.distance <= 1.0);
+        assert!(!matches!(paran.planet, NorthNode | SouthNode));
+    }
+
+    // 恒星表中没有的恒星
+    let config = HoroscopeConfig {
+        fixed_stars: FixedStarConfig {
+            catalogue: FixedStarCatalogue::Custom(vec!["NoSuchStar".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &config,
+        &ephe_path,
+    );
+    assert!(matches!(horo, Err(Error::InvalidFixedStar(_))));
+}
+
+#[test]
+fn test_position_mode() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    // 2024-4-10，水星逆行
+    let t = HoroDateTime::new(2024, 4, 10, 8, 0, 0, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    let horo = |position_mode| {
+        let config = HoroscopeConfig {
+            position_mode,
+            lots: LotConfig::catalogue(),
+            ..Default::default()
+        };
+        Horoscope::new(
+            t,
+            geo,
+            HouseName::Alcabitus,
+            &PlanetConfig::default_all_configs(),
+            &config,
+            &ephe_path,
+        )
+        .unwrap()
+    };
+    let planet = |horo: &Horoscope, name| *horo.planets.iter().find(|p| p.name == name).unwrap();
+
+    let geocentric = horo(PositionMode::Geocentric);
+    let topocentric = horo(PositionMode::Topocentric(1900.0));
+    let heliocentric = horo(PositionMode::Heliocentric);
+    assert_eq!(PositionMode::Topocentric(1900.0), topocentric.position_mode);
+
+    // 站心月亮的视差不超过约1度
+    let moon0 = planet(&geocentric, Moon);
+    let moon1 = planet(&topocentric, Moon);
+    let d = swe_degnorm(moon0.long - moon1.long);
+    let d = d.min(360.0 - d);
+    assert!(d > 0.01 && d < 1.1, "月亮站心视差：{d}");
+
+    // 日心：星盘中没有太阳、月亮，有地球，行星没有逆行
+    assert!(heliocentric.planets.iter().all(|p| p.name != Sun));
+    assert!(heliocentric.planets.iter().all(|p| p.name != Moon));
+    assert!(geocentric.planets.iter().all(|p| p.name != Earth));
+    let sun = planet(&geocentric, Sun);
+    let earth = planet(&heliocentric, Earth);
+    assert!((swe_degnorm(earth.long - sun.long) - 180.0).abs() < 1e-9);
+    assert!((earth.lat + sun.lat).abs() < 1e-9);
+    assert!((earth.dec + sun.dec).abs() < 1e-9);
+    assert!(
+        heliocentric
+            .aspects
+            .iter()
+            .all(|aspect| aspect.p0 != Sun && aspect.p1 != Sun)
+    );
+    assert!(planet(&geocentric, Mercury).speed < 0.0);
+    for name in [Mercury, Venus, Mars, Jupiter, Saturn] {
+        let p = planet(&heliocentric, name);
+        assert!(p.speed > 0.0, "{:?}", name);
+        // 日心时不计算行星状态
+        assert!(p.condition.is_none(), "{:?}", name);
+    }
+
+    // 日心时不计算月亮状态、区分及阿拉伯点
+    assert!(geocentric.moon_condition.is_some());
+    assert!(geocentric.sect.is_some());
+    assert!(!geocentric.lots.is_empty());
+    assert!(heliocentric.moon_condition.is_none());
+    assert!(heliocentric.sect.is_none());
+    assert!(heliocentric.lots.is_empty());
+
+    // 日心时不计算行星的必然尊贵、界外、所在宫位及与恒星的Paran
+    assert!(geocentric.planets.iter().any(|p| p.dignities.is_some()));
+    assert!(geocentric.planets.iter().all(|p| p.house.is_some()));
+    for p in &heliocentric.planets {
+        assert!(p.dignities.is_none(), "{:?}", p.name);
+        assert!(!p.out_of_bounds, "{:?}", p.name);
+        assert!(p.house.is_none(), "{:?}", p.name);
+        assert!(p.mundane_position.is_none(), "{:?}", p.name);
+    }
+    assert!(heliocentric.asc.house.is_some());
+    assert!(heliocentric.parans.is_empty());
+
+    // 日心时行星不与四轴、福点成相位、映点、平行
+    let angle_points = [ASC, MC, DSC, IC, PartOfFortune];
+    assert!(
+        geocentric.aspects.iter().any(|aspect| {
+            angle_points.contains(&aspect.p0) || angle_points.contains(&aspect.p1)
+        })
+    );
+    for aspects in [
+        &heliocentric.aspects,
+        &heliocentric.antiscoins,
+        &heliocentric.contraantiscias,
+        &heliocentric.parallels,
+        &heliocentric.contraparallels,
+    ] {
+        assert!(aspects.iter().all(|aspect| {
+            !angle_points.contains(&aspect.p0) && !angle_points.contains(&aspect.p1)
+        }));
+    }
+
+    // 星盘昼夜、福点与四轴一样按地心计算
+    assert_eq!(geocentric.is_diurnal, heliocentric.is_diurnal);
+    assert!((geocentric.part_of_fortune.long - heliocentric.part_of_fortune.long).abs() < 1e-9);
+    let mars0 = planet(&geocentric, Mars);
+    let mars2 = planet(&heliocentric, Mars);
+    assert!((mars0.long - mars2.long).abs() > 1.0);
+    assert!((mars0.ra - mars2.ra).abs() > 1.0);
+}
+
+#[test]
+fn test_divisional_positions() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    let points: Vec<_> = horo
+        .planets
+        .iter()
+        .chain([&horo.asc, &horo.mc, &horo.dsc, &horo.ic])
+        .collect();
+    assert_eq!(points.len(), horo.divisional_positions.len());
+    for (p, position) in points.iter().zip(&horo.divisional_positions) {
+        assert_eq!(p.name, position.name);
+        assert_eq!(dodecatemorion(p.long), position.dodecatemorion);
+        assert_eq!(navamsa(p.long), position.navamsa);
+    }
+
+    let long = |name| points.iter().find(|p| p.name == name).unwrap().long;
+    let position = |name| {
+        *horo
+            .divisional_positions
+            .iter()
+            .find(|p| p.name == name)
+            .unwrap()
+    };
+    for aspect in &horo.dodecatemorion_aspects {
+        let d = swe_degnorm(long(aspect.p0) - position(aspect.p1).dodecatemorion);
+        let d = d.min(360.0 - d);
+        assert!((d - f64::from(aspect.aspect_value)).abs() <= aspect.d + 1e-9);
+    }
+    for aspect in &horo.navamsa_aspects {
+        let d = swe_degnorm(long(aspect.p0) - position(aspect.p1).navamsa);
+        let d = d.min(360.0 - d);
+        assert!((d - f64::from(aspect.aspect_value)).abs() <= aspect.d + 1e-9);
+    }
+}
+
+#[test]
+fn test_sect() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    // 白天盘、夜间盘
+    for (t, sect) in [
+        (
+            HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap(),
+            Sect::Diurnal,
+        ),
+        (
+            HoroDateTime::new(2021, 9, 14, 22, 30, 20, 8.0).unwrap(),
+            Sect::Nocturnal,
+        ),
+    ] {
+        let horo = Horoscope::new(
+            t,
+            geo,
+            HouseName::Alcabitus,
+            &PlanetConfig::default_configs(true),
+            &HoroscopeConfig::default(),
+            &ephe_path,
+        )
+        .unwrap();
+
+        let analysis = horo.sect.as_ref().unwrap();
+        assert_eq!(sect, analysis.sect);
+        assert_eq!(sect == Sect::Diurnal, horo.is_diurnal);
+        if horo.is_diurnal {
+            assert_eq!(Jupiter, analysis.benefic_of_sect);
+            assert_eq!(Saturn, analysis.malefic_of_sect);
+            assert_eq!(Venus, analysis.benefic_contrary_to_sect);
+            assert_eq!(Mars, analysis.malefic_contrary_to_sect);
+        } else {
+            assert_eq!(Venus, analysis.benefic_of_sect);
+            assert_eq!(Mars, analysis.malefic_of_sect);
+            assert_eq!(Jupiter, analysis.benefic_contrary_to_sect);
+            assert_eq!(Saturn, analysis.malefic_contrary_to_sect);
+        }
+
+        // 只有七颗古典行星
+        let names: Vec<_> = analysis.planets.iter().map(|p| p.name).collect();
+        assert_eq!(
+            vec![Sun, Moon, Mercury, Venus, Mars, Jupiter, Saturn],
+            names
+        );
+
+        for p in &analysis.planets {
+            assert_eq!(p.sect == sect, p.in_sect, "{:?}", p.name);
+
+            let planet = horo
+                .planets
+                .iter()
+                .find(|planet| planet.name == p.name)
+                .unwrap();
+            assert_eq!(
+                ((planet.long / 30.0) as u8).is_multiple_of(2),
+                p.masculine_sign,
+                "{:?}",
+                p.name
+            );
+
+            if p.hayz {
+                assert!(p.in_sect, "{:?}", p.name);
+                assert!(p.halb, "{:?}", p.name);
+            }
+            assert_eq!(
+                p.in_sect && p.halb && p.masculine_sign == (p.sect == Sect::Diurnal),
+                p.hayz,
+                "{:?}",
+                p.name
+            );
+        }
+
+        // 太阳在地平线上即白天盘
+        let sun = analysis.planets.iter().find(|p| p.name == Sun).unwrap();
+        assert_eq!(horo.is_diurnal, sun.above_horizon);
+        assert!(sun.in_sect);
+        assert_eq!(horo.is_diurnal, sun.halb);
+    }
+
+    // 夜间盘，满月在子夜前后过中天，月亮在巨蟹座，在地平线上、落阴性星座，为Hayz
+    let horo = Horoscope::new(
+        HoroDateTime::new(2023, 1, 8, 0, 0, 0, 8.0).unwrap(),
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_configs(true),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+    let analysis = horo.sect.as_ref().unwrap();
+    assert_eq!(Sect::Nocturnal, analysis.sect);
+    let moon = analysis.planets.iter().find(|p| p.name == Moon).unwrap();
+    assert_eq!(Sect::Nocturnal, moon.sect);
+    assert!(moon.above_horizon);
+    assert!(!moon.masculine_sign);
+    assert!(moon.halb);
+    assert!(moon.hayz);
+}
+
+#[test]
+fn test_receptions() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_configs(true),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    let long_of = |name: PlanetName| horo.planets.iter().find(|p| p.name == name).unwrap().long;
+    let seven_planets = [Sun, Moon, Mercury, Venus, Mars, Jupiter, Saturn];
+    let dignities = [
+        DignityType::Domicile,
+        DignityType::Exaltation,
+        DignityType::Triplicity,
+        DignityType::Term,
+        DignityType::Face,
+    ];
+    let received_dignities = |receiver: PlanetName, received: PlanetName| -> Vec<DignityType> {
+        dignities
+            .into_iter()
+            .filter(|&dignity| {
+                dignity_ruler(dignity, long_of(received), horo.is_diurnal) == Some(receiver)
+            })
+            .collect()
+    };
+
+    // 相位上的接纳
+    for aspect in &horo.aspects {
+        if !seven_planets.contains(&aspect.p0)
+            || !seven_planets.contains(&aspect.p1)
+            || aspect.p0 == aspect.p1
+        {
+            assert!(aspect.receptions.is_empty());
+            continue;
+        }
+
+        let p0_receives_p1 = received_dignities(aspect.p0, aspect.p1);
+        let p1_receives_p0 = received_dignities(aspect.p1, aspect.p0);
+        let mutual = !p0_receives_p1.is_empty() && !p1_receives_p0.is_empty();
+        assert_eq!(
+            usize::from(!p0_receives_p1.is_empty()) + usize::from(!p1_receives_p0.is_empty()),
+            aspect.receptions.len()
+        );
+        for reception in &aspect.receptions {
+            assert_eq!(mutual, reception.mutual);
+            assert_eq!(
+                received_dignities(reception.receiver, reception.received),
+                reception.dignities
+            );
+        }
+    }
+
+    // 互容，包括不成相位的
+    let mut count = 0;
+    for (i, &p0) in seven_planets.iter().enumerate() {
+        for &p1 in &seven_planets[i + 1..] {
+            if !received_dignities(p0, p1).is_empty() && !received_dignities(p1, p0).is_empty() {
+                count += 1;
+                let mutual_reception = horo
+                    .mutual_receptions
+                    .iter()
+                    .find(|r| r.p0 == p0 && r.p1 == p1)
+                    .unwrap();
+                assert_eq!(received_dignities(p0, p1), mutual_reception.p0_receives_p1);
+                assert_eq!(received_dignities(p1, p0), mutual_reception.p1_receives_p0);
+            }
+        }
+    }
+    assert_eq!(count, horo.mutual_receptions.len());
+
+    // 定位星链：后一颗行星是前一颗行星所在星座的庙主星
+    assert_eq!(7, horo.dispositors.chains.len());
+    for chain in &horo.dispositors.chains {
+        assert_eq!(chain.planet, chain.chain[0]);
+        for pair in chain.chain.windows(2) {
+            assert_eq!(pair[1], domicile_ruler(sign_of(long_of(pair[0]))));
+        }
+    }
+    if let Some(final_dispositor) = horo.dispositors.final_dispositor {
+        assert_eq!(
+            final_dispositor,
+            domicile_ruler(sign_of(long_of(final_dispositor)))
+        );
+        assert!(
+            horo.dispositors
+                .chains
+                .iter()
+                .all(|chain| chain.chain.last() == Some(&final_dispositor))
+        );
+    }
+
+    swe_close();
+}
+
+#[test]
+fn test_midpoints() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    for dial in [Dial::Degree360, Dial::Degree90, Dial::Degree45] {
+        let config = HoroscopeConfig {
+            midpoints: MidpointConfig { dial, orb: 1.5 },
+            ..Default::default()
+        };
+        let horo = Horoscope::new(
+            t,
+            geo,
+            HouseName::Alcabitus,
+            &PlanetConfig::default_configs(false),
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+
+        let mut points: Vec<_> = horo.planets.iter().collect();
+        points.extend([&horo.asc, &horo.mc]);
+        let n = points.len();
+        assert_eq!(n * (n - 1) / 2, horo.midpoints.len());
+
+        for m in &horo.midpoints {
+            let p0 = points.iter().find(|p| p.name == m.p0).unwrap();
+            let p1 = points.iter().find(|p| p.name == m.p1).unwrap();
+            assert!((m.long - midpoint(p0.long, p1.long)).abs() < 1e-9);
+        }
+
+        // 中点树中的每个中点都在容许度内，且按距离排序
+        for tree in &horo.midpoint_trees {
+            let planet = points.iter().find(|p| p.name == tree.planet).unwrap();
+            for contact in &tree.midpoints {
+                assert_ne!(tree.planet, contact.p0);
+                assert_ne!(tree.planet, contact.p1);
+                let m = horo
+                    .midpoints
+                    .iter()
+                    .find(|m| m.p0 == contact.p0 && m.p1 == contact.p1)
+                    .unwrap();
+                assert!((dial.distance(planet.long, m.long) - contact.d).abs() < 1e-9);
+                assert!(contact.d <= 1.5);
+            }
+            assert!(tree.midpoints.windows(2).all(|w| w[0].d <= w[1].d));
+        }
+
+        assert_eq!(n, horo.dial_positions.len());
+        assert!(
+            horo.dial_positions
+                .windows(2)
+                .all(|w| w[0].dial_long <= w[1].dial_long)
+        );
+        assert!(
+            horo.dial_positions
+                .iter()
+                .all(|p| p.dial_long < dial.degrees())
+        );
+    }
+}
+
+#[test]
+fn test_harmonic_chart() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    let horo = Horoscope::new(
+        t,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_configs(false),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    let config = AspectConfig::default();
+
+    // 1次谐波即原星盘，谐波盘不计福点
+    let chart = HarmonicChart::new(&horo, 1, &config).unwrap();
+    let part_of_fortune_aspects = horo
+        .aspects
+        .iter()
+        .filter(|aspect| aspect.p0 == PartOfFortune || aspect.p1 == PartOfFortune)
+        .count();
+    assert_eq!(
+        horo.aspects.len(),
+        chart.aspects.len() + part_of_fortune_aspects
+    );
+    for (p, h) in horo.planets.iter().zip(&chart.planets) {
+        assert_eq!(p.long, h.long);
+    }
+
+    let chart = HarmonicChart::new(&horo, 5, &config).unwrap();
+    assert_eq!(5, chart.harmonic);
+    assert_eq!(horo.planets.len(), chart.planets.len());
+    for (p, h) in horo.planets.iter().zip(&chart.planets) {
+        assert_eq!(p.name, h.name);
+        assert!((swe_degnorm(p.long * 5.0) - h.long).abs() < 1e-9);
+        assert!((p.speed * 5.0 - h.speed).abs() < 1e-9);
+        assert!(h.house.is_none());
+    }
+    assert!((swe_degnorm(horo.asc.long * 5.0) - chart.asc.long).abs() < 1e-9);
+    assert!((swe_degnorm(horo.mc.long * 5.0) - chart.mc.long).abs() < 1e-9);
+
+    // 相位按谐波盘中的位置重新计算
+    let mut points: Vec<_> = chart.planets.iter().collect();
+    points.extend([&chart.asc, &chart.mc, &chart.dsc, &chart.ic]);
+    let mut count = 0;
+    for i in 0..points.len() {
+        for j in i..points.len() {
+            if points[i]
+                .has_aspect_with_config(points[j], false, &config)
+                .is_some()
+            {
+                count += 1;
+            }
+        }
+    }
+    assert_eq!(count, chart.aspects.len());
+
+    assert!(matches!(
+        HarmonicChart::new(&horo, 0, &config),
+        Err(Error::InvalidHarmonic(_))
+    ));
+
+    swe_close();
+}
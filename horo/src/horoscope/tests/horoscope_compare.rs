@@ -7,7 +7,7 @@ use swe::{
 };
 
 use crate::{
-    HoroscopeComparison, PlanetName::*, config::PlanetConfig, house::HouseName,
+    HoroscopeComparison, HoroscopeConfig, PlanetName::*, config::PlanetConfig, house::HouseName,
     planet::PlanetSpeedState::*, utils::calc_eps,
 };
 
@@ -43,6 +43,7 @@ fn çtest_horoscope_compare_new() {
         process_geo,
         house,
         &planet_configs,
+        &HoroscopeConfig::default(),
         &ephe_path,
     )
     .unwrap();
@@ -469,3 +470,172 @@ fn çtest_horoscope_compare_new() {
     // 相位
     assert_eq!(38, horo.aspects.len());
 }
+
+// 天王星、海王星、冥王星
+#[test]
+fn test_horoscope_compare_outer_planets() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let native_date = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let compare_date = HoroDateTime::new(2023, 12, 26, 20, 14, 20, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    let horo = HoroscopeComparison::new(
+        native_date,
+        compare_date,
+        geo.clone(),
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_configs(true),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    for planet_name in [Uranus, Neptune, Pluto] {
+        assert!(
+            horo.original_planets.iter().any(|p| p.name == planet_name),
+            "原盘{:?}",
+            planet_name
+        );
+        assert!(
+            horo.comparison_planets
+                .iter()
+                .any(|p| p.name == planet_name),
+            "比较盘{:?}",
+            planet_name
+        );
+    }
+}
+
+// 比较盘的平行、对等平行，p0为原盘星体，p1为比较盘星体
+#[test]
+fn test_horoscope_compare_parallels() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let native_date = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let compare_date = HoroDateTime::new(2023, 12, 26, 20, 14, 20, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    let horo = HoroscopeComparison::new(
+        native_date,
+        compare_date,
+        geo,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    let original_dec = |name| {
+        horo.original_planets
+            .iter()
+            .chain([&horo.original_asc, &horo.original_mc])
+            .chain([&horo.original_dsc, &horo.original_ic])
+            .chain([&horo.original_part_of_fortune])
+            .find(|p| p.name == name)
+            .unwrap()
+            .dec
+    };
+    let comparison_dec = |name| {
+        horo.comparison_planets
+            .iter()
+            .chain([&horo.comparison_asc, &horo.comparison_mc])
+            .chain([&horo.comparison_dsc, &horo.comparison_ic])
+            .chain([&horo.comparison_part_of_fortune])
+            .find(|p| p.name == name)
+            .unwrap()
+            .dec
+    };
+
+    // 默认容许度1度
+    for aspect in &horo.parallels {
+        let (dec0, dec1) = (original_dec(aspect.p0), comparison_dec(aspect.p1));
+        assert!(dec0 * dec1 >= 0.0);
+        assert!((aspect.d - (dec0 - dec1).abs()).abs() < 1e-9);
+        assert!(aspect.d <= 1.0);
+    }
+    for aspect in &horo.contraparallels {
+        let (dec0, dec1) = (original_dec(aspect.p0), comparison_dec(aspect.p1));
+        assert!(dec0 * dec1 < 0.0);
+        assert!((aspect.d - (dec0 + dec1).abs()).abs() < 1e-9);
+        assert!(aspect.d <= 1.0);
+    }
+}
+
+// 星体落在对方星盘的宫位
+#[test]
+fn test_horoscope_compare_house_placements() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let native_date = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+    let compare_date = HoroDateTime::new(2023, 12, 26, 20, 14, 20, 8.0).unwrap();
+    let geo = GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap();
+
+    let horo = HoroscopeComparison::new(
+        native_date,
+        compare_date,
+        geo,
+        geo,
+        HouseName::Alcabitus,
+        &PlanetConfig::default_all_configs(),
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    // 行星、四轴、福点
+    assert_eq!(
+        horo.original_in_comparison_houses.len(),
+        horo.original_planets.len() + 5
+    );
+    assert_eq!(
+        horo.comparison_in_original_houses.len(),
+        horo.comparison_planets.len() + 5
+    );
+
+    for placement in &horo.comparison_in_original_houses {
+        let Some(p) = horo
+            .comparison_planets
+            .iter()
+            .find(|p| p.name == placement.name)
+        else {
+            continue;
+        };
+        let house = placement.house;
+        let start = horo.houses_cusps[usize::from(house) - 1];
+        let end = horo.houses_cusps[usize::from(house) % 12];
+        assert!(swe_degnorm(p.long - start) < swe_degnorm(end - start));
+        assert!(placement.mundane_position.is_some());
+    }
+
+    // 原盘上升点落在比较盘的宫位
+    let asc = horo
+        .original_in_comparison_houses
+        .iter()
+        .find(|placement| placement.name == ASC)
+        .unwrap();
+    let start = horo.comparison_cusps[usize::from(asc.house) - 1];
+    let end = horo.comparison_cusps[usize::from(asc.house) % 12];
+    assert!(swe_degnorm(horo.original_asc.long - start) < swe_degnorm(end - start));
+}
@@ -1,2 +1,38 @@
+use std::env;
+
+use geo_position::GeoPosition;
+use horo_date_time::HoroDateTime;
+
+use crate::{Horoscope, HoroscopeConfig, config::PlanetConfig, house::HouseName};
+
 mod horoscope;
 mod horoscope_compare;
+
+// 星历表路径，由EPHE_PATH环境变量设置
+pub(crate) fn get_ephe_path() -> String {
+    dotenvy::dotenv().ok();
+    env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...")
+}
+
+// 出生地：昆明
+pub(crate) fn native_geo() -> GeoPosition {
+    GeoPosition::new(
+        102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+        25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+    )
+    .unwrap()
+}
+
+// 行运、推运等模块测试用的本命盘：Alcabitus宫制，不含天王星、海王星、冥王星
+pub(crate) fn natal_chart(date: HoroDateTime, config: &HoroscopeConfig) -> Horoscope {
+    Horoscope::new(
+        date,
+        native_geo(),
+        HouseName::Alcabitus,
+        &PlanetConfig::default_configs(false),
+        config,
+        &get_ephe_path(),
+    )
+    .unwrap()
+}
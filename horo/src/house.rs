@@ -1,6 +1,8 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use swe::HouseSystem;
+use swe::{HouseSystem, swe_degnorm};
+
+use crate::{Planet, PlanetName};
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
 
@@ -12,6 +14,17 @@ pub enum HouseName {
     Placidus,
     Regiomontanus,
     WholeSign,
+    Koch,
+    Campanus,
+    Porphyry,
+    /// 等宫制，从上升点起
+    Equal,
+    /// 等宫制，从中天起
+    EqualMC,
+    Morinus,
+    Topocentric,
+    /// 子午线宫制（Axial Rotation）
+    Meridian,
 }
 
 impl HouseName {
@@ -21,8 +34,34 @@ impl HouseName {
             HouseName::Placidus,
             HouseName::Regiomontanus,
             HouseName::WholeSign,
+            HouseName::Koch,
+            HouseName::Campanus,
+            HouseName::Porphyry,
+            HouseName::Equal,
+            HouseName::EqualMC,
+            HouseName::Morinus,
+            HouseName::Topocentric,
+            HouseName::Meridian,
         ]
     }
+
+    /// 是否象限宫制：上升点是第1宫宫头，中天是第10宫宫头
+    pub fn is_quadrant(&self) -> bool {
+        match self {
+            HouseName::Alcabitus
+            | HouseName::Placidus
+            | HouseName::Regiomontanus
+            | HouseName::Koch
+            | HouseName::Campanus
+            | HouseName::Porphyry
+            | HouseName::Topocentric => true,
+            HouseName::WholeSign
+            | HouseName::Equal
+            | HouseName::EqualMC
+            | HouseName::Morinus
+            | HouseName::Meridian => false,
+        }
+    }
 }
 
 impl From<HouseName> for HouseSystem {
@@ -32,24 +71,157 @@ impl From<HouseName> for HouseSystem {
             HouseName::Placidus => HouseSystem::P,
             HouseName::WholeSign => HouseSystem::W,
             HouseName::Regiomontanus => HouseSystem::R,
+            HouseName::Koch => HouseSystem::K,
+            HouseName::Campanus => HouseSystem::C,
+            HouseName::Porphyry => HouseSystem::O,
+            HouseName::Equal => HouseSystem::E,
+            HouseName::EqualMC => HouseSystem::D,
+            HouseName::Morinus => HouseSystem::M,
+            HouseName::Topocentric => HouseSystem::T,
+            HouseName::Meridian => HouseSystem::X,
         }
     }
 }
 
+/// 黄经所在的宫位，1-12
+/// * cusps: 12宫头黄经
+pub(crate) fn house_of(long: f64, cusps: &[f64]) -> u8 {
+    (0..12)
+        .find(|&i| {
+            let start = cusps[i];
+            let end = cusps[(i + 1) % 12];
+            swe_degnorm(long - start) < swe_degnorm(end - start)
+        })
+        .map_or(1, |i| i as u8 + 1)
+}
+
+/// 星体在星盘中的宫位
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct HousePlacement {
+    /// 星体
+    pub name: PlanetName,
+    /// 所在宫位，1-12
+    pub house: u8,
+    /// Placidus世俗位置，整数部分是宫位，小数部分是在该宫内走过的比例，
+    /// 只有象限宫制才有此值，拱极星体没有此值
+    pub mundane_position: Option<f64>,
+}
+
+impl HousePlacement {
+    /// 计算星体在星盘中的宫位
+    /// * cusps: 12宫头黄经
+    /// * armc: 中天的赤经
+    /// * geo_lat: 地理纬度
+    pub(crate) fn new(
+        planet: &Planet,
+        house_name: HouseName,
+        cusps: &[f64],
+        armc: f64,
+        geo_lat: f64,
+    ) -> Self {
+        let mundane_position = if house_name.is_quadrant() {
+            mundane_position(planet.ra, planet.dec, armc, geo_lat)
+        } else {
+            None
+        };
+
+        Self {
+            name: planet.name,
+            house: house_of(planet.long, cusps),
+            mundane_position,
+        }
+    }
+}
+
+/// Placidus世俗位置：按星体的半弧三等分各象限，返回值在[1, 13)
+/// 中天是10，上升点是1，天底是4，下降点是7
+/// 拱极星体没有半弧，返回None
+/// * ra: 星体的赤经
+/// * dec: 星体的赤纬
+/// * armc: 中天的赤经
+/// * geo_lat: 地理纬度
+pub(crate) fn mundane_position(ra: f64, dec: f64, armc: f64, geo_lat: f64) -> Option<f64> {
+    // 斜升差
+    let sin_ad = dec.to_radians().tan() * geo_lat.to_radians().tan();
+    if !(-1.0..=1.0).contains(&sin_ad) {
+        return None;
+    }
+    let ad = sin_ad.asin().to_degrees();
+    // 昼半弧、夜半弧
+    let dsa = 90.0 + ad;
+    let nsa = 90.0 - ad;
+
+    // 时角，中天为0，向西增加
+    let ha = swe_degnorm(armc - ra);
+    let position = if ha <= dsa {
+        // 地平线上，中天至下降点：10宫至7宫
+        10.0 - 3.0 * ha / dsa
+    } else if ha <= dsa + 2.0 * nsa {
+        // 地平线下，下降点经天底至上升点：7宫至1宫
+        7.0 - 3.0 * (ha - dsa) / nsa
+    } else {
+        // 地平线上，上升点至中天：13（即1）宫至10宫
+        10.0 + 3.0 * (360.0 - ha) / dsa
+    };
+
+    if position >= 13.0 {
+        Some(position - 12.0)
+    } else {
+        Some(position)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use swe::HouseSystem;
+    use swe::{HouseSystem, swe_degnorm};
 
     use crate::HouseName;
 
+    use super::{house_of, mundane_position};
+
     #[test]
     fn test_all_house_names() {
         let house_names = HouseName::all_house_names();
-        assert_eq!(house_names.len(), 4);
+        assert_eq!(house_names.len(), 12);
         assert!(matches!(house_names[0], HouseName::Alcabitus));
         assert!(matches!(house_names[1], HouseName::Placidus));
         assert!(matches!(house_names[2], HouseName::Regiomontanus));
         assert!(matches!(house_names[3], HouseName::WholeSign));
+        assert!(matches!(house_names[4], HouseName::Koch));
+        assert!(matches!(house_names[5], HouseName::Campanus));
+        assert!(matches!(house_names[6], HouseName::Porphyry));
+        assert!(matches!(house_names[7], HouseName::Equal));
+        assert!(matches!(house_names[8], HouseName::EqualMC));
+        assert!(matches!(house_names[9], HouseName::Morinus));
+        assert!(matches!(house_names[10], HouseName::Topocentric));
+        assert!(matches!(house_names[11], HouseName::Meridian));
+    }
+
+    #[test]
+    fn test_is_quadrant() {
+        for house_name in [
+            HouseName::Alcabitus,
+            HouseName::Placidus,
+            HouseName::Regiomontanus,
+            HouseName::Koch,
+            HouseName::Campanus,
+            HouseName::Porphyry,
+            HouseName::Topocentric,
+        ] {
+            assert!(house_name.is_quadrant(), "{:?}", house_name);
+        }
+
+        for house_name in [
+            HouseName::WholeSign,
+            HouseName::Equal,
+            HouseName::EqualMC,
+            HouseName::Morinus,
+            HouseName::Meridian,
+        ] {
+            assert!(!house_name.is_quadrant(), "{:?}", house_name);
+        }
     }
 
     #[test]
@@ -65,5 +237,79 @@ mod tests {
 
         let house_system: HouseSystem = HouseName::WholeSign.into();
         assert!(matches!(house_system, HouseSystem::W));
+
+        let house_system: HouseSystem = HouseName::Koch.into();
+        assert!(matches!(house_system, HouseSystem::K));
+
+        let house_system: HouseSystem = HouseName::Campanus.into();
+        assert!(matches!(house_system, HouseSystem::C));
+
+        let house_system: HouseSystem = HouseName::Porphyry.into();
+        assert!(matches!(house_system, HouseSystem::O));
+
+        let house_system: HouseSystem = HouseName::Equal.into();
+        assert!(matches!(house_system, HouseSystem::E));
+
+        let house_system: HouseSystem = HouseName::EqualMC.into();
+        assert!(matches!(house_system, HouseSystem::D));
+
+        let house_system: HouseSystem = HouseName::Morinus.into();
+        assert!(matches!(house_system, HouseSystem::M));
+
+        let house_system: HouseSystem = HouseName::Topocentric.into();
+        assert!(matches!(house_system, HouseSystem::T));
+
+        let house_system: HouseSystem = HouseName::Meridian.into();
+        assert!(matches!(house_system, HouseSystem::X));
+    }
+
+    #[test]
+    fn test_house_of() {
+        let cusps: Vec<_> = (0..12)
+            .map(|i| swe_degnorm(f64::from(i) * 30.0 + 350.0))
+            .collect();
+
+        assert_eq!(house_of(350.0, &cusps), 1);
+        assert_eq!(house_of(0.0, &cusps), 1);
+        assert_eq!(house_of(19.9, &cusps), 1);
+        assert_eq!(house_of(20.0, &cusps), 2);
+        assert_eq!(house_of(349.9, &cusps), 12);
+    }
+
+    #[test]
+    fn test_mundane_position() {
+        // 赤道上，赤纬0度，半弧都是90度
+        let armc = 100.0;
+        let cases = [
+            (100.0, 10.0),
+            (70.0, 9.0),
+            (10.0, 7.0),
+            (280.0, 4.0),
+            (190.0, 1.0),
+            (130.0, 11.0),
+        ];
+        for (ra, expected) in cases {
+            let position = mundane_position(ra, 0.0, armc, 0.0).unwrap();
+            assert!((position - expected).abs() < 1e-9, "赤经{ra}: {position}");
+        }
+
+        // 北纬40度，赤纬20度，昼半弧大于90度
+        let lat: f64 = 40.0;
+        let dec: f64 = 20.0;
+        let dsa = 90.0
+            + (dec.to_radians().tan() * lat.to_radians().tan())
+                .asin()
+                .to_degrees();
+        let position = mundane_position(armc - dsa / 2.0, dec, armc, lat).unwrap();
+        assert!((position - 8.5).abs() < 1e-9, "{position}");
+        let position = mundane_position(armc + dsa / 2.0, dec, armc, lat).unwrap();
+        assert!((position - 11.5).abs() < 1e-9, "{position}");
+        // 夜半弧 = 180 - 昼半弧
+        let nsa = 180.0 - dsa;
+        let position = mundane_position(armc - dsa - nsa / 3.0, dec, armc, lat).unwrap();
+        assert!((position - 6.0).abs() < 1e-9, "{position}");
+
+        // 拱极星体
+        assert!(mundane_position(0.0, 30.0, armc, 70.0).is_none());
     }
 }
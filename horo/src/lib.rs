@@ -1,25 +1,72 @@
-pub use aspect::Aspect;
-pub use config::PlanetConfig;
-pub use direction::{Direction, Promittor, direction_process};
+pub use almuten::{Almuten, AlmutenPoint, AlmutenPointName, AlmutenScore, PlanetScore};
+pub use aspect::{Aspect, AspectConfig, AspectSetting, AspectType, OrbRule, ParallelConfig};
+pub use condition::{
+    ConditionConfig, MotionDirection, PlanetCondition, SolarOrientation, SolarPhase,
+};
+pub use config::{HoroscopeConfig, PlanetConfig};
+pub use dignity::{DignityScoring, DignityType, EssentialDignities};
+pub use direction::{Direction, DirectionKey, Promittor, direction_process};
+pub use division::{DivisionalPosition, dodecatemorion, navamsa};
 pub use error::*;
 pub use firdaria::{FirdariaPeriod, FirdariaSubPeriod, firdaria_process};
+pub use fixed_star::{
+    AngularEvent, FixedStar, FixedStarCatalogue, FixedStarConfig, FixedStarConjunction,
+    FixedStarName, FixedStarPoint, Paran,
+};
+pub use harmonic::{HarmonicChart, harmonic_long};
+pub use horary::{Horary, Significator, Stricture, StrictureVerdict};
 pub use horoscope::{Horoscope, HoroscopeComparison};
-pub use house::HouseName;
+pub use house::{HouseName, HousePlacement};
+pub use lot::{Lot, LotConfig, LotName};
+pub use midpoint::{
+    Dial, DialPosition, Midpoint, MidpointConfig, MidpointContact, MidpointTree, dial_sort,
+    midpoint,
+};
+pub use moon::{MoonAspect, MoonCondition, MoonPhase};
 pub use planet::*;
+pub use planetary_hours::{PlanetaryHour, PlanetaryHours, planetary_hours};
+pub use position::PositionMode;
 pub use profection::Profection;
+pub use progression::{
+    McProgression, Progression, ProgressionType, minor_progression, progressed_aspects,
+    progression, secondary_progression, tertiary_progression,
+};
+pub use qizheng::NodeType;
 pub use quadrant_process::{QuadrantProcess, quadrant_process, quadrant_process_longitude};
+pub use reception::{DispositorChain, Dispositors, MutualReception, Reception};
 pub use return_horoscop::{ReturnHoroscop, lunar_return, solar_return};
+pub use sect::{PlanetSect, Sect, SectAnalysis};
+pub use solar_arc::{SolarArc, SolarArcDirection, SolarArcKey};
+pub use transit::{Transit, TransitConfig, TransitPoint, transits};
+pub use zodiac::{Ayanamsa, Zodiac};
 
+mod almuten;
 mod aspect;
+mod condition;
 mod config;
+mod dignity;
 mod direction;
+mod division;
 mod error;
 mod firdaria;
 mod fixed_star;
+mod harmonic;
+mod horary;
 mod horoscope;
 mod house;
+mod lot;
+mod midpoint;
+mod moon;
 mod planet;
+mod planetary_hours;
+mod position;
 mod profection;
+mod progression;
 mod quadrant_process;
+mod reception;
 mod return_horoscop;
+mod sect;
+mod solar_arc;
+mod transit;
 mod utils;
+mod zodiac;
@@ -0,0 +1,381 @@
+use swe::{swe_cotrans, swe_degnorm};
+
+use crate::{
+    Error, Planet, PlanetConfig, PlanetName,
+    dignity::{domicile_ruler, sign_of},
+    house::house_of,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 阿拉伯点的名称
+#[derive(PartialEq, Eq, Clone, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum LotName {
+    /// 精神点
+    Spirit,
+    /// 爱欲点
+    Eros,
+    /// 必然点
+    Necessity,
+    /// 勇气点
+    Courage,
+    /// 胜利点
+    Victory,
+    /// 复仇点
+    Nemesis,
+    /// 基础点
+    Basis,
+    /// 婚姻点
+    Marriage,
+    /// 父亲点
+    Father,
+    /// 母亲点
+    Mother,
+    /// 子女点
+    Children,
+    /// 兄弟点
+    Siblings,
+    /// 自定义的点，编号由调用者指定
+    Custom(u8),
+}
+
+impl LotName {
+    /// 内置的阿拉伯点
+    pub fn builtin_lots() -> [LotName; 12] {
+        [
+            LotName::Spirit,
+            LotName::Eros,
+            LotName::Necessity,
+            LotName::Courage,
+            LotName::Victory,
+            LotName::Nemesis,
+            LotName::Basis,
+            LotName::Marriage,
+            LotName::Father,
+            LotName::Mother,
+            LotName::Children,
+            LotName::Siblings,
+        ]
+    }
+}
+
+/// 阿拉伯点的公式
+/// 白天盘：上升点 + a − b
+/// 夜间盘：reverse_at_night为true时，上升点 + b − a，否则与白天盘相同
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct LotConfig {
+    /// 阿拉伯点的名称
+    pub name: LotName,
+    /// 公式中加上的点，可以是四轴、行星、福点或其它阿拉伯点
+    pub a: PlanetName,
+    /// 公式中减去的点，可以是四轴、行星、福点或其它阿拉伯点
+    pub b: PlanetName,
+    /// 夜间盘是否互换a、b
+    pub reverse_at_night: bool,
+}
+
+impl LotConfig {
+    pub fn new(name: LotName, a: PlanetName, b: PlanetName, reverse_at_night: bool) -> Self {
+        Self {
+            name,
+            a,
+            b,
+            reverse_at_night,
+        }
+    }
+
+    /// 内置阿拉伯点的公式，自定义的点返回None
+    pub fn builtin(name: LotName) -> Option<Self> {
+        use PlanetName::*;
+
+        let spirit = PlanetName::Lot(LotName::Spirit);
+        let config = match name {
+            // 精神点：昼，上升+日−月
+            LotName::Spirit => LotConfig::new(name, Sun, Moon, true),
+            // 爱欲点：昼，上升+金星−精神点
+            LotName::Eros => LotConfig::new(name, Venus, spirit, true),
+            // 必然点：昼，上升+福点−水星
+            LotName::Necessity => LotConfig::new(name, PartOfFortune, Mercury, true),
+            // 勇气点：昼，上升+福点−火星
+            LotName::Courage => LotConfig::new(name, PartOfFortune, Mars, true),
+            // 胜利点：昼，上升+木星−精神点
+            LotName::Victory => LotConfig::new(name, Jupiter, spirit, true),
+            // 复仇点：昼，上升+福点−土星
+            LotName::Nemesis => LotConfig::new(name, PartOfFortune, Saturn, true),
+            // 基础点：昼，上升+福点−精神点
+            LotName::Basis => LotConfig::new(name, PartOfFortune, spirit, true),
+            // 婚姻点：上升+金星−土星，昼夜不换
+            LotName::Marriage => LotConfig::new(name, Venus, Saturn, false),
+            // 父亲点：昼，上升+土星−日
+            LotName::Father => LotConfig::new(name, Saturn, Sun, true),
+            // 母亲点：昼，上升+月−金星
+            LotName::Mother => LotConfig::new(name, Moon, Venus, true),
+            // 子女点：昼，上升+土星−木星
+            LotName::Children => LotConfig::new(name, Saturn, Jupiter, true),
+            // 兄弟点：上升+木星−土星，昼夜不换
+            LotName::Siblings => LotConfig::new(name, Jupiter, Saturn, false),
+            LotName::Custom(_) => return None,
+        };
+        Some(config)
+    }
+
+    /// 内置的全部阿拉伯点
+    pub fn catalogue() -> Vec<Self> {
+        LotName::builtin_lots()
+            .into_iter()
+            .filter_map(Self::builtin)
+            .collect()
+    }
+}
+
+/// 阿拉伯点
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Lot {
+    /// 阿拉伯点，name为PlanetName::Lot
+    pub planet: Planet,
+    /// 所在星座，0: 白羊，1: 金牛，……，11: 双鱼
+    pub sign: u8,
+    /// 所在宫位，1-12
+    pub house: u8,
+    /// 所在星座的庙主星
+    pub ruler: PlanetName,
+}
+
+/// 计算阿拉伯点
+/// * lots_config: 阿拉伯点的公式，按顺序计算，后面的点可以引用前面的点
+/// * points: 星盘中已计算的点：四轴、行星、福点
+/// * cusps: 12宫头黄经
+/// * is_diurnal: 白天盘：true，夜间盘：false
+/// * eps: 黄赤倾角
+/// * ayanamsa: 岁差，计算赤经、赤纬时换回回归黄道
+pub(crate) fn calc_lots(
+    lots_config: &[LotConfig],
+    points: &[&Planet],
+    cusps: &[f64],
+    is_diurnal: bool,
+    eps: f64,
+    ayanamsa: f64,
+    planets_config: &[PlanetConfig],
+) -> Result<Vec<Lot>, Error> {
+    let mut known: Vec<_> = points.iter().map(|p| (p.name, p.long)).collect();
+    let mut lots = vec![];
+
+    for config in lots_config {
+        let long = lot_long(config, &known, is_diurnal)?;
+        let name = PlanetName::Lot(config.name);
+        known.push((name, long));
+
+        let default_planet_config = PlanetConfig::default_config(&name);
+        let planet_config = planets_config
+            .iter()
+            .find(|p| p.name == name)
+            .unwrap_or(&default_planet_config);
+        let equator = swe_cotrans(swe_degnorm(long + ayanamsa), 0.0, 1.0, -eps);
+        let planet = Planet::new(name, long, 0.0, 0.0, equator[0], equator[1], planet_config);
+
+        let sign = sign_of(long);
+        lots.push(Lot {
+            planet,
+            sign: sign as u8,
+            house: house_of(long, cusps),
+            ruler: domicile_ruler(sign),
+        });
+    }
+
+    Ok(lots)
+}
+
+/// 按公式计算阿拉伯点的黄经
+fn lot_long(
+    config: &LotConfig,
+    known: &[(PlanetName, f64)],
+    is_diurnal: bool,
+) -> Result<f64, Error> {
+    let asc = term_long(PlanetName::ASC, known, is_diurnal)?;
+    let a = term_long(config.a, known, is_diurnal)?;
+    let b = term_long(config.b, known, is_diurnal)?;
+
+    let (a, b) = if !is_diurnal && config.reverse_at_night {
+        (b, a)
+    } else {
+        (a, b)
+    };
+
+    Ok(swe_degnorm(asc + a - b))
+}
+
+/// 公式中某点的黄经，未计算的内置阿拉伯点按内置公式计算
+fn term_long(
+    name: PlanetName,
+    known: &[(PlanetName, f64)],
+    is_diurnal: bool,
+) -> Result<f64, Error> {
+    if let Some(&(_, long)) = known.iter().find(|(n, _)| *n == name) {
+        return Ok(long);
+    }
+
+    match name {
+        PlanetName::Lot(lot_name) => match LotConfig::builtin(lot_name) {
+            Some(config) => lot_long(&config, known, is_diurnal),
+            None => Err(Error::InvalidLot(format!(
+                "阿拉伯点公式中的{lot_name:?}需在引用它的点之前计算"
+            ))),
+        },
+        _ => Err(Error::InvalidLot(format!(
+            "阿拉伯点公式中的{name:?}不在星盘中"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parameterized::parameterized;
+
+    use crate::PlanetName::*;
+
+    use super::*;
+
+    fn known() -> Vec<(PlanetName, f64)> {
+        vec![
+            (ASC, 10.0),
+            (Sun, 100.0),
+            (Moon, 40.0),
+            (Venus, 130.0),
+            (Saturn, 300.0),
+            // 昼：10+40−100
+            (PartOfFortune, 310.0),
+        ]
+    }
+
+    #[test]
+    fn test_catalogue() {
+        let catalogue = LotConfig::catalogue();
+        assert_eq!(catalogue.len(), 12);
+        assert!(
+            catalogue
+                .iter()
+                .zip(LotName::builtin_lots())
+                .all(|(config, name)| config.name == name)
+        );
+        assert_eq!(LotConfig::builtin(LotName::Custom(0)), None);
+    }
+
+    #[parameterized(args = {
+        // 精神点，昼：10+100−40
+        (LotName::Spirit, true, 70.0),
+        // 精神点，夜：10+40−100
+        (LotName::Spirit, false, 310.0),
+        // 婚姻点，昼夜不换：10+130−300
+        (LotName::Marriage, true, 200.0),
+        (LotName::Marriage, false, 200.0),
+        // 爱欲点，昼：10+130−精神点(70)
+        (LotName::Eros, true, 70.0),
+        // 复仇点，夜：10+300−310
+        (LotName::Nemesis, false, 0.0),
+    })]
+    fn test_lot_long(args: (LotName, bool, f64)) {
+        let (name, is_diurnal, expected) = args;
+        let config = LotConfig::builtin(name).unwrap();
+        let long = lot_long(&config, &known(), is_diurnal).unwrap();
+        assert!((long - expected).abs() < 1e-9, "{name:?}={long}");
+    }
+
+    #[test]
+    fn test_custom_lot() {
+        let mut known = known();
+        known.push((PlanetName::Lot(LotName::Spirit), 0.0));
+
+        // 已计算的点优先于内置公式
+        let eros = LotConfig::builtin(LotName::Eros).unwrap();
+        assert_eq!(lot_long(&eros, &known, true).unwrap(), 140.0);
+
+        // 自定义点：上升+金星−月亮
+        let custom = LotConfig::new(LotName::Custom(1), Venus, Moon, false);
+        assert_eq!(lot_long(&custom, &known, false).unwrap(), 100.0);
+
+        // 引用未计算的自定义点
+        let custom = LotConfig::new(
+            LotName::Custom(2),
+            PlanetName::Lot(LotName::Custom(3)),
+            Moon,
+            false,
+        );
+        assert!(matches!(
+            lot_long(&custom, &known, true),
+            Err(Error::InvalidLot(_))
+        ));
+
+        // 引用星盘中没有的行星
+        let custom = LotConfig::new(LotName::Custom(2), Uranus, Moon, false);
+        assert!(matches!(
+            lot_long(&custom, &known, true),
+            Err(Error::InvalidLot(_))
+        ));
+    }
+
+    #[test]
+    fn test_calc_lots() {
+        let planets: Vec<_> = known()
+            .into_iter()
+            .map(|(name, long)| {
+                Planet::new(
+                    name,
+                    long,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    &PlanetConfig::default_config(&name),
+                )
+            })
+            .collect();
+        let points: Vec<_> = planets.iter().collect();
+        let cusps: Vec<_> = (0..12).map(|i| f64::from(i) * 30.0 + 10.0).collect();
+
+        let lots = calc_lots(
+            &[
+                LotConfig::builtin(LotName::Spirit).unwrap(),
+                LotConfig::new(
+                    LotName::Custom(0),
+                    PlanetName::Lot(LotName::Spirit),
+                    Sun,
+                    false,
+                ),
+            ],
+            &points,
+            &cusps,
+            true,
+            23.44,
+            0.0,
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(lots.len(), 2);
+
+        // 精神点：70度，双子10度，第3宫，庙主星水星
+        assert_eq!(lots[0].planet.name, PlanetName::Lot(LotName::Spirit));
+        assert_eq!(lots[0].planet.long, 70.0);
+        assert_eq!(lots[0].planet.orb, 0);
+        assert_eq!(lots[0].sign, 2);
+        assert_eq!(lots[0].house, 3);
+        assert_eq!(lots[0].ruler, Mercury);
+
+        // 自定义点引用精神点：10+70−100=340，双鱼10度，第12宫，庙主星木星
+        assert_eq!(lots[1].planet.name, PlanetName::Lot(LotName::Custom(0)));
+        assert_eq!(lots[1].planet.long, 340.0);
+        assert_eq!(lots[1].sign, 11);
+        assert_eq!(lots[1].house, 12);
+        assert_eq!(lots[1].ruler, Jupiter);
+    }
+}
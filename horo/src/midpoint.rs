@@ -0,0 +1,263 @@
+use swe::swe_degnorm;
+
+use crate::{Planet, PlanetName, utils::mod180};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 度盘
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Dial {
+    /// 360度盘
+    Degree360,
+    /// 90度盘：合、刑、冲重合
+    #[default]
+    Degree90,
+    /// 45度盘：合、刑、冲、半刑、八分之三相重合
+    Degree45,
+}
+
+impl Dial {
+    /// 度盘的度数
+    pub fn degrees(&self) -> f64 {
+        match self {
+            Dial::Degree360 => 360.0,
+            Dial::Degree90 => 90.0,
+            Dial::Degree45 => 45.0,
+        }
+    }
+
+    /// 黄经在度盘上的位置，[0, 度盘的度数)
+    pub fn position(&self, long: f64) -> f64 {
+        swe_degnorm(long) % self.degrees()
+    }
+
+    /// 两个黄经在度盘上的距离，[0, 度盘的度数/2]
+    pub fn distance(&self, long0: f64, long1: f64) -> f64 {
+        let degrees = self.degrees();
+        let d = swe_degnorm(long0 - long1) % degrees;
+        d.min(degrees - d)
+    }
+}
+
+/// 中点的配置
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct MidpointConfig {
+    /// 中点树的度盘，星体与中点在此度盘上的距离不超过容许度即在中点上
+    pub dial: Dial,
+    /// 中点树的容许度，单位：度
+    pub orb: f64,
+}
+
+/// 默认用90度盘，容许度1.5度
+impl Default for MidpointConfig {
+    fn default() -> Self {
+        Self {
+            dial: Dial::Degree90,
+            orb: 1.5,
+        }
+    }
+}
+
+/// 两个星体的中点：p0/p1
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Midpoint {
+    pub p0: PlanetName,
+    pub p1: PlanetName,
+    /// 较近的中点的黄经
+    pub long: f64,
+}
+
+impl Midpoint {
+    pub fn new(p0: &Planet, p1: &Planet) -> Self {
+        Self {
+            p0: p0.name,
+            p1: p1.name,
+            long: midpoint(p0.long, p1.long),
+        }
+    }
+}
+
+/// 星体所在的中点：planet = p0/p1
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct MidpointContact {
+    pub p0: PlanetName,
+    pub p1: PlanetName,
+    /// 星体与中点在度盘上的距离，单位：度
+    pub d: f64,
+}
+
+/// 中点树：星体及其所在的全部中点，按距离从近到远排列
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct MidpointTree {
+    /// 星体
+    pub planet: PlanetName,
+    /// 星体所在的中点
+    pub midpoints: Vec<MidpointContact>,
+}
+
+/// 星体在度盘上的位置
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct DialPosition {
+    /// 星体
+    pub name: PlanetName,
+    /// 黄经
+    pub long: f64,
+    /// 在度盘上的位置
+    pub dial_long: f64,
+}
+
+/// 两个黄经较近的中点
+pub fn midpoint(long0: f64, long1: f64) -> f64 {
+    swe_degnorm(long0 + mod180(long1 - long0) / 2.0)
+}
+
+/// 星体间两两的中点
+pub(crate) fn calc_midpoints(points: &[&Planet]) -> Vec<Midpoint> {
+    points
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &p0)| points[i + 1..].iter().map(move |&p1| Midpoint::new(p0, p1)))
+        .collect()
+}
+
+/// 各星体的中点树，不计星体自身参与的中点，没有中点的星体不返回
+pub(crate) fn calc_midpoint_trees(
+    points: &[&Planet],
+    midpoints: &[Midpoint],
+    config: &MidpointConfig,
+) -> Vec<MidpointTree> {
+    points
+        .iter()
+        .filter_map(|planet| {
+            let mut contacts: Vec<_> = midpoints
+                .iter()
+                .filter(|m| m.p0 != planet.name && m.p1 != planet.name)
+                .filter_map(|m| {
+                    let d = config.dial.distance(planet.long, m.long);
+                    (d <= config.orb).then_some(MidpointContact {
+                        p0: m.p0,
+                        p1: m.p1,
+                        d,
+                    })
+                })
+                .collect();
+            if contacts.is_empty() {
+                return None;
+            }
+            contacts.sort_by(|a, b| a.d.total_cmp(&b.d));
+            Some(MidpointTree {
+                planet: planet.name,
+                midpoints: contacts,
+            })
+        })
+        .collect()
+}
+
+/// 按星体在度盘上的位置从小到大排序
+pub fn dial_sort(points: &[&Planet], dial: Dial) -> Vec<DialPosition> {
+    let mut positions: Vec<_> = points
+        .iter()
+        .map(|p| DialPosition {
+            name: p.name,
+            long: p.long,
+            dial_long: dial.position(p.long),
+        })
+        .collect();
+    positions.sort_by(|a, b| a.dial_long.total_cmp(&b.dial_long));
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use parameterized::parameterized;
+
+    use crate::{PlanetName::*, planet::tests::planet};
+
+    use super::{Dial, MidpointConfig, calc_midpoint_trees, calc_midpoints, dial_sort, midpoint};
+
+    #[parameterized(case = {
+        (10.0, 30.0, 20.0),
+        (30.0, 10.0, 20.0),
+        (350.0, 20.0, 5.0),
+        (20.0, 350.0, 5.0),
+        (100.0, 300.0, 20.0),
+    })]
+    fn test_midpoint(case: (f64, f64, f64)) {
+        assert!((midpoint(case.0, case.1) - case.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dial() {
+        assert_eq!(Dial::Degree90.position(100.0), 10.0);
+        assert_eq!(Dial::Degree45.position(100.0), 10.0);
+        assert_eq!(Dial::Degree360.position(370.0), 10.0);
+
+        // 刑、冲在90度盘上重合
+        assert!(Dial::Degree90.distance(10.0, 190.0).abs() < 1e-9);
+        assert!((Dial::Degree90.distance(10.0, 101.0) - 1.0).abs() < 1e-9);
+        assert!((Dial::Degree90.distance(10.0, 99.0) - 1.0).abs() < 1e-9);
+        // 半刑在45度盘上重合
+        assert!(Dial::Degree45.distance(10.0, 55.0).abs() < 1e-9);
+        assert!((Dial::Degree360.distance(10.0, 190.0) - 180.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_midpoint_trees() {
+        let sun = planet(Sun, 10.0);
+        let moon = planet(Moon, 50.0);
+        // 日/月=30度，火星与之成刑
+        let mars = planet(Mars, 121.0);
+        let points = [&sun, &moon, &mars];
+
+        let midpoints = calc_midpoints(&points);
+        assert_eq!(midpoints.len(), 3);
+        assert_eq!((midpoints[0].p0, midpoints[0].p1), (Sun, Moon));
+        assert!((midpoints[0].long - 30.0).abs() < 1e-9);
+
+        let trees = calc_midpoint_trees(&points, &midpoints, &MidpointConfig::default());
+        assert_eq!(trees.len(), 1);
+        assert_eq!(trees[0].planet, Mars);
+        assert_eq!(trees[0].midpoints.len(), 1);
+        assert_eq!(
+            (trees[0].midpoints[0].p0, trees[0].midpoints[0].p1),
+            (Sun, Moon)
+        );
+        assert!((trees[0].midpoints[0].d - 1.0).abs() < 1e-9);
+
+        // 360度盘上，刑不算在中点上
+        let config = MidpointConfig {
+            dial: Dial::Degree360,
+            orb: 1.5,
+        };
+        assert!(calc_midpoint_trees(&points, &midpoints, &config).is_empty());
+    }
+
+    #[test]
+    fn test_dial_sort() {
+        let sun = planet(Sun, 100.0);
+        let moon = planet(Moon, 5.0);
+        let mars = planet(Mars, 200.0);
+        let positions = dial_sort(&[&sun, &moon, &mars], Dial::Degree90);
+        assert_eq!(
+            positions.iter().map(|p| p.name).collect::<Vec<_>>(),
+            vec![Moon, Sun, Mars]
+        );
+        assert_eq!(positions[2].dial_long, 20.0);
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{
+    AspectType, Error, PlanetName,
+    position::PositionMode,
+    utils::{mod180, newton_iteration},
+};
+use geo_position::GeoPosition;
+use horo_date_time::HoroDateTime;
+use swe::{Body, swe_calc_ut, swe_close, swe_degnorm, swe_set_ephe_path};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+// 计算月亮空亡时，与月亮成相位的其余六颗古典行星
+const ASPECT_PLANETS: [(PlanetName, Body); 6] = [
+    (PlanetName::Sun, Body::SeSun),
+    (PlanetName::Mercury, Body::SeMercury),
+    (PlanetName::Venus, Body::SeVenus),
+    (PlanetName::Mars, Body::SeMars),
+    (PlanetName::Jupiter, Body::SeJupiter),
+    (PlanetName::Saturn, Body::SeSaturn),
+];
+
+// 月亮逆时针到行星的度数与托勒密相位
+const ASPECT_ANGLES: [(f64, AspectType); 8] = [
+    (0.0, AspectType::Conjunction),
+    (60.0, AspectType::Sextile),
+    (90.0, AspectType::Square),
+    (120.0, AspectType::Trine),
+    (180.0, AspectType::Opposition),
+    (240.0, AspectType::Trine),
+    (270.0, AspectType::Square),
+    (300.0, AspectType::Sextile),
+];
+
+// 月亮与行星的相对速度的近似值，单位：度/天，用作牛顿迭代的初值
+const MOON_RELATIVE_SPEED: f64 = 12.0;
+
+/// 月相，按月亮与太阳的黄经差每45度划分
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum MoonPhase {
+    /// 新月，0-45度
+    New,
+    /// 娥眉月，45-90度
+    Crescent,
+    /// 上弦月，90-135度
+    FirstQuarter,
+    /// 盈凸月，135-180度
+    Gibbous,
+    /// 满月，180-225度
+    Full,
+    /// 亏凸月，225-270度
+    Disseminating,
+    /// 下弦月，270-315度
+    LastQuarter,
+    /// 残月，315-360度
+    Balsamic,
+}
+
+impl MoonPhase {
+    /// 由月亮与太阳的黄经差得到月相
+    pub fn from_angle(phase_angle: f64) -> Self {
+        match (swe_degnorm(phase_angle) / 45.0) as u8 {
+            0 => MoonPhase::New,
+            1 => MoonPhase::Crescent,
+            2 => MoonPhase::FirstQuarter,
+            3 => MoonPhase::Gibbous,
+            4 => MoonPhase::Full,
+            5 => MoonPhase::Disseminating,
+            6 => MoonPhase::LastQuarter,
+            _ => MoonPhase::Balsamic,
+        }
+    }
+}
+
+/// 月亮与行星形成的正相位
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct MoonAspect {
+    /// 与月亮成相位的行星
+    pub planet: PlanetName,
+    /// 相位类型
+    pub aspect_type: AspectType,
+    /// 形成正相位的时间
+    pub date: HoroDateTime,
+}
+
+/// 月亮的状态
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct MoonCondition {
+    /// 月相角：月亮黄经 - 太阳黄经，[0, 360)
+    pub phase_angle: f64,
+    /// 月相
+    pub phase: MoonPhase,
+    /// 月亮空亡：离开所在星座前，不再与七颗古典行星形成托勒密相位
+    pub void_of_course: bool,
+    /// 月亮开始空亡的时间，即最后一个相位与月亮进入当前星座两者中较晚的时间，不空亡时没有此值
+    pub void_since: Option<HoroDateTime>,
+    /// 月亮进入下一星座的时间
+    pub next_ingress: HoroDateTime,
+    /// 燃烧之路：月亮在回归黄道的天秤座15度至天蝎座15度之间
+    pub via_combusta: bool,
+    /// 月亮最后形成的相位
+    pub last_aspect: Option<MoonAspect>,
+    /// 月亮下一个将形成的相位
+    pub next_aspect: Option<MoonAspect>,
+}
+
+impl MoonCondition {
+    /// 计算月亮的状态
+    /// * ayanamsa: 岁差，回归黄道为0
+    /// * position_mode: 地心或站心，日心时月相、月亮空亡等没有意义，不计算月亮的状态
+    pub(crate) fn new(
+        date: &HoroDateTime,
+        geo: &GeoPosition,
+        ayanamsa: f64,
+        position_mode: PositionMode,
+        ephe_path: &str,
+    ) -> Result<Self, Error> {
+        swe_set_ephe_path(ephe_path);
+        position_mode.set_topo(geo);
+        let moon_long =
+            swe_degnorm(calc_long(date.jd_ut1, Body::SeMoon, position_mode)? - ayanamsa);
+        let sun_long = swe_degnorm(calc_long(date.jd_ut1, Body::SeSun, position_mode)? - ayanamsa);
+        let last_ingress = moon_ingress(date.jd_ut1, moon_long, ayanamsa, position_mode, false)?;
+        let next_ingress = moon_ingress(date.jd_ut1, moon_long, ayanamsa, position_mode, true)?;
+        let last_aspect = moon_aspect(date.jd_ut1, position_mode, false)?;
+        let next_aspect = moon_aspect(date.jd_ut1, position_mode, true)?;
+        swe_close();
+
+        let phase_angle = swe_degnorm(moon_long - sun_long);
+
+        let void_of_course = is_void_of_course(next_aspect.map(|(jd, _, _)| jd), next_ingress);
+        let void_since = if void_of_course {
+            let jd = void_start(last_aspect.map(|(jd, _, _)| jd), last_ingress);
+            Some(HoroDateTime::from_jd_ut1_zone(jd, date.tz)?)
+        } else {
+            None
+        };
+
+        let to_moon_aspect = |(jd, planet, aspect_type): (f64, PlanetName, AspectType)| {
+            HoroDateTime::from_jd_ut1_zone(jd, date.tz).map(|date| MoonAspect {
+                planet,
+                aspect_type,
+                date,
+            })
+        };
+        let last_aspect = last_aspect.map(to_moon_aspect).transpose()?;
+        let next_aspect = next_aspect.map(to_moon_aspect).transpose()?;
+
+        Ok(Self {
+            phase_angle,
+            phase: MoonPhase::from_angle(phase_angle),
+            void_of_course,
+            void_since,
+            next_ingress: HoroDateTime::from_jd_ut1_zone(next_ingress, date.tz)?,
+            via_combusta: is_via_combusta(swe_degnorm(moon_long + ayanamsa)),
+            last_aspect,
+            next_aspect,
+        })
+    }
+}
+
+// 月亮空亡：进入下一星座前不再形成正相位
+fn is_void_of_course(next_aspect_jd: Option<f64>, next_ingress: f64) -> bool {
+    next_aspect_jd.is_none_or(|jd| jd > next_ingress)
+}
+
+// 月亮开始空亡的时间：在当前星座内的最后一个相位，若进入当前星座后还没有相位，则为进入当前星座的时间
+fn void_start(last_aspect_jd: Option<f64>, last_ingress: f64) -> f64 {
+    last_aspect_jd.map_or(last_ingress, |jd| jd.max(last_ingress))
+}
+
+// 燃烧之路以回归黄道的天秤座15度至天蝎座15度界定
+fn is_via_combusta(tropical_long: f64) -> bool {
+    (195.0..225.0).contains(&tropical_long)
+}
+
+fn calc_long(jd: f64, body: Body, position_mode: PositionMode) -> Result<f64, Error> {
+    let flags: Vec<_> = position_mode.flag(body).into_iter().collect();
+    let xx = swe_calc_ut(jd, body, &flags)
+        .map_err(|e| Error::Function(format!("计算月亮状态错误:{e}")))?;
+    Ok(xx[0])
+}
+
+// 月亮进入星座的时间，岁差在几天内的变化可忽略
+// * forward: true，进入下一星座的时间；false，进入当前星座的时间
+fn moon_ingress(
+    jd: f64,
+    moon_long: f64,
+    ayanamsa: f64,
+    position_mode: PositionMode,
+    forward: bool,
+) -> Result<f64, Error> {
+    let sign_start = (moon_long / 30.0).floor() * 30.0;
+    let (boundary, jd0) = if forward {
+        let boundary = sign_start + 30.0;
+        (boundary, jd + (boundary - moon_long) / 13.0)
+    } else {
+        (sign_start, jd - (moon_long - sign_start) / 13.0)
+    };
+    newton_iteration(jd0, |t| {
+        Ok(mod180(
+            calc_long(t, Body::SeMoon, position_mode)? - ayanamsa - boundary,
+        ))
+    })
+}
+
+/// 月亮与七颗古典行星的托勒密正相位
+/// * forward: true，之后最近的相位；false，之前最近的相位
+///
+/// 返回值：正相位的儒略日（ut1）、行星、相位类型
+fn moon_aspect(
+    jd: f64,
+    position_mode: PositionMode,
+    forward: bool,
+) -> Result<Option<(f64, PlanetName, AspectType)>, Error> {
+    let mut result: Option<(f64, PlanetName, AspectType)> = None;
+
+    for (planet, body) in ASPECT_PLANETS {
+        // 月亮比行星快，月亮逆时针到行星的度数随时间增加
+        let d = swe_degnorm(
+            calc_long(jd, Body::SeMoon, position_mode)? - calc_long(jd, body, position_mode)?,
+        );
+        let (delta, angle, aspect_type) = ASPECT_ANGLES
+            .iter()
+            .map(|&(angle, aspect_type)| {
+                let delta = if forward {
+                    swe_degnorm(angle - d)
+                } else {
+                    swe_degnorm(d - angle)
+                };
+                (delta, angle, aspect_type)
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap();
+
+        let jd0 = if forward {
+            jd + delta / MOON_RELATIVE_SPEED
+        } else {
+            jd - delta / MOON_RELATIVE_SPEED
+        };
+        let t = newton_iteration(jd0, |t| {
+            Ok(mod180(
+                calc_long(t, Body::SeMoon, position_mode)?
+                    - calc_long(t, body, position_mode)?
+                    - angle,
+            ))
+        })?;
+
+        let is_closer = result.is_none_or(|(t0, _, _)| if forward { t < t0 } else { t > t0 });
+        if is_closer {
+            result = Some((t, planet, aspect_type));
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use parameterized::parameterized;
+
+    use super::{MoonPhase, is_via_combusta, is_void_of_course, void_start};
+
+    #[parameterized(phase = {
+        (0.0, MoonPhase::New),
+        (44.9, MoonPhase::New),
+        (45.0, MoonPhase::Crescent),
+        (90.0, MoonPhase::FirstQuarter),
+        (179.9, MoonPhase::Gibbous),
+        (180.0, MoonPhase::Full),
+        (225.0, MoonPhase::Disseminating),
+        (270.0, MoonPhase::LastQuarter),
+        (315.0, MoonPhase::Balsamic),
+        (359.9, MoonPhase::Balsamic),
+        (360.0, MoonPhase::New),
+    })]
+    fn test_moon_phase(phase: (f64, MoonPhase)) {
+        assert_eq!(MoonPhase::from_angle(phase.0), phase.1);
+    }
+
+    #[parameterized(args = {
+        (None, 2460395.0, true),
+        (Some(2460394.5), 2460395.0, false),
+        (Some(2460395.5), 2460395.0, true),
+    })]
+    fn test_void_of_course(args: (Option<f64>, f64, bool)) {
+        assert_eq!(is_void_of_course(args.0, args.1), args.2);
+    }
+
+    // 最后一个相位在进入当前星座之前时，从进入当前星座时开始空亡
+    #[parameterized(args = {
+        (Some(2460394.5), 2460394.0, 2460394.5),
+        (Some(2460393.5), 2460394.0, 2460394.0),
+        (None, 2460394.0, 2460394.0),
+    })]
+    fn test_void_start(args: (Option<f64>, f64, f64)) {
+        assert_eq!(void_start(args.0, args.1), args.2);
+    }
+
+    #[parameterized(args = {
+        (194.9, false),
+        (195.0, true),
+        (210.0, true),
+        (224.9, true),
+        (225.0, false),
+        (15.0, false),
+    })]
+    fn test_via_combusta(args: (f64, bool)) {
+        assert_eq!(is_via_combusta(args.0), args.1);
+    }
+}
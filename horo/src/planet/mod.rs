@@ -1,16 +1,19 @@
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "swagger")]
 use utoipa::ToSchema;
 
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 
 use swe::swe_degnorm;
 
-use crate::aspect::Aspect;
+use crate::aspect::{Aspect, AspectConfig, AspectType};
+use crate::condition::PlanetCondition;
 use crate::config::PlanetConfig;
+use crate::dignity::EssentialDignities;
+use crate::lot::LotName;
 use crate::utils::included_angle;
 use PlanetSpeedState::*;
 
@@ -24,7 +27,7 @@ pub enum PlanetSpeedState {
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Copy)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "swagger", derive(ToSchema))]
 pub enum PlanetName {
     ASC,
@@ -38,10 +41,18 @@ pub enum PlanetName {
     Mars,
     Jupiter,
     Saturn,
+    // 天王星、海王星、冥王星，只有在行星配置中设置时才计算
+    Uranus,
+    Neptune,
+    Pluto,
+    // 地球，只有日心星盘中有，位置与地心的太阳相对
+    Earth,
     // MeanNode,
     NorthNode,
     SouthNode,
     PartOfFortune,
+    // 阿拉伯点，只有在星盘配置中设置时才计算
+    Lot(LotName),
 }
 
 impl PlanetName {
@@ -114,6 +125,17 @@ pub struct Planet {
     // private val maxSpeed :Double,
     /// 行星速度状态：快、平均、慢
     pub speed_state: PlanetSpeedState,
+    /// 必然尊贵，只有七颗古典行星有此值
+    pub dignities: Option<EssentialDignities>,
+    /// 界外：赤纬的绝对值大于黄赤交角
+    pub out_of_bounds: bool,
+    /// 所在宫位，1-12，只有星盘中的星体有此值
+    pub house: Option<u8>,
+    /// Placidus世俗位置，整数部分是宫位，小数部分是在该宫内走过的比例，
+    /// 只有象限宫制星盘中的星体有此值
+    pub mundane_position: Option<f64>,
+    /// 行星状态：顺逆、留、与太阳的相位状态及东西方，只有星盘中除太阳外的行星有此值
+    pub condition: Option<PlanetCondition>,
 }
 
 impl Planet {
@@ -146,6 +168,11 @@ impl Planet {
             dec,
             orb: config.orb,
             speed_state,
+            dignities: None,
+            out_of_bounds: false,
+            house: None,
+            mundane_position: None,
+            condition: None,
         }
     }
 
@@ -160,14 +187,26 @@ impl Planet {
      * 有相位：true
      */
     pub fn has_aspect(&self, p: &Planet, compare: bool) -> Option<Aspect> {
-        let aspect = p0_has_aspect_p1(self, p, compare);
-
-        let aspect = if let Some(aspect) = aspect {
-            aspect
-        } else {
-            return None;
-        };
+        self.has_aspect_with_config(p, compare, &AspectConfig::default())
+    }
 
+    /**
+     * 按相位配置，这颗行星与给定的行星有相位否
+     * 比较盘中，self为本盘星体
+     * 多个相位都在容许度内时，取最精确的相位
+     * @param p
+     * 另一颗行星
+     * @param compare
+     * 比较盘，此值为True
+     * @param config
+     * 相位配置：启用的相位及容许度
+     */
+    pub fn has_aspect_with_config(
+        &self,
+        p: &Planet,
+        compare: bool,
+        config: &AspectConfig,
+    ) -> Option<Aspect> {
         // 计算行星所落星座有无相位
         // d = self行星所落星座-p行星所落星座，此值是p行星逆时针到self行星的度数
         // d可能的值<0, 0<=d<=180, 180<d<360
@@ -182,11 +221,21 @@ impl Planet {
         if d > 180 {
             d = 360 - d
         }
-        if d as u8 == aspect.aspect_value {
-            Some(aspect)
-        } else {
-            None
-        }
+
+        config
+            .aspects
+            .iter()
+            .filter_map(|setting| {
+                let orb = config.orb(setting, self, p);
+                let aspect = p0_has_aspect_p1(self, p, setting.aspect_type, orb, compare)?;
+                // 相位度数是30的倍数时，行星所落星座也须成此相位
+                if aspect.aspect_value % 30 == 0 && d as u8 != aspect.aspect_value {
+                    None
+                } else {
+                    Some(aspect)
+                }
+            })
+            .min_by(|a, b| a.d.total_cmp(&b.d))
     }
 
     /**
@@ -257,6 +306,48 @@ impl Planet {
             Some(Aspect::new(0, false, d, self.name.clone(), p.name.clone()))
         }
     }
+
+    /**
+     * 这颗行星与给定的行星是否平行：赤纬相等，且同在天赤道以北或以南
+     * 平行视同合相，相位值为0
+     * @param p
+     * 另一颗行星
+     * @param orb
+     * 容许度，单位：度
+     */
+    pub fn has_parallel(&self, p: &Planet, orb: f64) -> Option<Aspect> {
+        if self.dec * p.dec < 0.0 {
+            return None;
+        }
+
+        let d = (self.dec - p.dec).abs();
+        if d > orb {
+            None
+        } else {
+            Some(Aspect::new(0, false, d, self.name, p.name))
+        }
+    }
+
+    /**
+     * 这颗行星与给定的行星是否对等平行：赤纬绝对值相等，一在天赤道以北，一在天赤道以南
+     * 对等平行视同冲，相位值为180
+     * @param p
+     * 另一颗行星
+     * @param orb
+     * 容许度，单位：度
+     */
+    pub fn has_contraparallel(&self, p: &Planet, orb: f64) -> Option<Aspect> {
+        if self.dec * p.dec >= 0.0 {
+            return None;
+        }
+
+        let d = (self.dec + p.dec).abs();
+        if d > orb {
+            None
+        } else {
+            Some(Aspect::new(180, false, d, self.name, p.name))
+        }
+    }
 }
 
 /// 本行星与另一颗行星有相位否，只计算行星间度数，未计算行星所落星座有相位
@@ -266,43 +357,46 @@ impl Planet {
 /// 另一个行星
 /// @return
 /// 此行星与行星p有相位，返回true
-fn p0_has_aspect_p1(p0: &Planet, p1: &Planet, compare: bool) -> Option<Aspect> {
+fn p0_has_aspect_p1(
+    p0: &Planet,
+    p1: &Planet,
+    aspect_type: AspectType,
+    orb: f64,
+    compare: bool,
+) -> Option<Aspect> {
     let f = if compare {
         compare_has_aspect_0
     } else {
         no_compare_has_aspect_0
     };
-    let aspect_values = [0.0, 60.0, 90.0, 120.0, 180.0];
+    let aspect_value = aspect_type.angle();
     let long_diff = swe_degnorm(p0.long - p1.long);
 
-    let aspect = aspect_values.iter().find_map(|&aspect_value| {
-        let aspect = if long_diff <= 180.0 {
-            let mut p = p1.clone();
-            p.long = swe_degnorm(p1.long + aspect_value);
-            f(p0, &p)
-        } else {
-            let mut p = p0.clone();
-            p.long = swe_degnorm(p0.long + aspect_value);
-            f(&p, p1)
-        };
-
-        if let Some(mut aspect) = aspect {
-            aspect.aspect_value += aspect_value as u8;
-            Some(aspect)
-        } else {
-            None
-        }
-    });
+    let aspect = if long_diff <= 180.0 {
+        let mut p = p1.clone();
+        p.long = swe_degnorm(p1.long + f64::from(aspect_value));
+        f(p0, &p, orb)
+    } else {
+        let mut p = p0.clone();
+        p.long = swe_degnorm(p0.long + f64::from(aspect_value));
+        f(&p, p1, orb)
+    };
 
-    aspect
+    aspect.map(|mut aspect| {
+        aspect.aspect_value = aspect_value;
+        aspect.aspect_type = aspect_type;
+        aspect
+    })
 }
 
 /// 非比较盘0度相位，本行星与另一颗行星有相位否，只计算行星间度数，未计算行星所落星座有相位
 /// @param p
 /// 另一个行星或虚点
+/// @param orb_half
+/// 容许度
 /// @return
 /// 此行星与行星p有相位，返回true
-fn no_compare_has_aspect_0(p0: &Planet, p1: &Planet) -> Option<Aspect> {
+fn no_compare_has_aspect_0(p0: &Planet, p1: &Planet, orb_half: f64) -> Option<Aspect> {
     //本命不考虑以下两种情况的相位
     if p0.name == p1.name {
         return None;
@@ -345,7 +439,6 @@ fn no_compare_has_aspect_0(p0: &Planet, p1: &Planet) -> Option<Aspect> {
     // 行星与ASC 、MC、DSC、IC的相位
     // if ascmc.contains(&p0.name) || ascmc.contains(&p1.name) {
     let aspect_diff = included_angle(p0.long, p1.long);
-    let orb_half = f64::from(p0.orb + p1.orb) / 2.0;
 
     // 没有在容许度内，无相位
     if aspect_diff > orb_half {
@@ -432,8 +525,8 @@ fn no_compare_has_aspect_0(p0: &Planet, p1: &Planet) -> Option<Aspect> {
 // 比较盘0度相位，只计算行星间度数，未计算行星所落星座有相位
 /// p0,本命盘行星，不动
 /// p1，比较盘行星，动
-fn compare_has_aspect_0(p0: &Planet, p1: &Planet) -> Option<Aspect> {
-    let orb_half = f64::from(p0.orb + p1.orb) / 2.0;
+/// orb_half，容许度
+fn compare_has_aspect_0(p0: &Planet, p1: &Planet, orb_half: f64) -> Option<Aspect> {
     let aspect_diff = included_angle(p0.long, p1.long);
     // 对p0,p1都是四轴，也适用，因为都是四轴，orb_half==0
     if aspect_diff > orb_half {
@@ -0,0 +1,168 @@
+use crate::{
+    aspect::{AspectConfig, AspectSetting, AspectType, OrbRule},
+    config::PlanetConfig,
+    planet::{Planet, PlanetName::*},
+};
+use parameterized::parameterized;
+use swe::swe_degnorm;
+
+fn planet(name: crate::PlanetName, long: f64, speed: f64, orb: u8) -> Planet {
+    Planet::new(
+        name,
+        long,
+        1.0,
+        speed,
+        1.0,
+        1.0,
+        &PlanetConfig::new(name, orb, 1.0, 2.0),
+    )
+}
+
+// 次要相位，只有启用时才计算
+#[parameterized(aspect_type = {
+    AspectType::Semisextile,
+    AspectType::Semisquare,
+    AspectType::Quintile,
+    AspectType::Sesquiquadrate,
+    AspectType::Biquintile,
+    AspectType::Quincunx,
+})]
+fn test_minor_aspect(aspect_type: AspectType) {
+    let aspect_value = aspect_type.angle();
+    let p0 = planet(Sun, 1.0, 1.0, 0);
+    let p1 = planet(Moon, 1.0 + f64::from(aspect_value), 1.0, 0);
+
+    for compare in [false, true] {
+        assert!(
+            p0.has_aspect(&p1, compare).is_none(),
+            "默认不计算{aspect_value}度相位"
+        );
+
+        let aspect = p0.has_aspect_with_config(&p1, compare, &AspectConfig::all());
+        assert!(aspect.is_some(), "{aspect_value}度相位");
+        let aspect = aspect.unwrap();
+        assert_eq!(aspect_value, aspect.aspect_value);
+        assert_eq!(aspect_type, aspect.aspect_type);
+        assert_eq!(0.0, aspect.d);
+        assert_eq!(p0.name, aspect.p0);
+        assert_eq!(p1.name, aspect.p1);
+
+        let aspect = p1.has_aspect_with_config(&p0, compare, &AspectConfig::all());
+        assert!(aspect.is_some(), "{aspect_value}度相位");
+        assert_eq!(aspect_type, aspect.unwrap().aspect_type);
+    }
+}
+
+// 托勒密相位报告相位类型
+#[parameterized(aspect_type = {
+    AspectType::Conjunction,
+    AspectType::Sextile,
+    AspectType::Square,
+    AspectType::Trine,
+    AspectType::Opposition,
+})]
+fn test_ptolemaic_aspect_type(aspect_type: AspectType) {
+    let aspect_value = aspect_type.angle();
+    let p0 = planet(Sun, 1.0, 1.0, 0);
+    let p1 = planet(Moon, swe_degnorm(1.0 + f64::from(aspect_value)), 1.0, 0);
+
+    let aspect = p0.has_aspect(&p1, false);
+    assert!(aspect.is_some(), "{aspect_value}度相位");
+    let aspect = aspect.unwrap();
+    assert_eq!(aspect_value, aspect.aspect_value);
+    assert_eq!(aspect_type, aspect.aspect_type);
+}
+
+// 相位度数不是30的倍数时，不检查行星所落星座
+#[test]
+fn test_no_sign_check_for_non_multiple_of_30() {
+    let config = AspectConfig {
+        aspects: vec![AspectSetting::new(AspectType::Semisquare, None)],
+        orb_rule: OrbRule::Moiety,
+    };
+
+    // 白羊29度与双子12度，相距43度
+    let p0 = planet(Sun, 29.0, 1.0, 4);
+    let p1 = planet(Moon, 72.0, 13.0, 4);
+    let aspect = p0.has_aspect_with_config(&p1, false, &config);
+    assert!(aspect.is_some());
+    let aspect = aspect.unwrap();
+    assert_eq!(AspectType::Semisquare, aspect.aspect_type);
+    assert_eq!(2.0, aspect.d);
+}
+
+// 相位度数是30的倍数时，行星所落星座也须成此相位
+#[test]
+fn test_sign_check_for_quincunx() {
+    let config = AspectConfig {
+        aspects: vec![AspectSetting::new(AspectType::Quincunx, None)],
+        orb_rule: OrbRule::Moiety,
+    };
+
+    // 白羊29度与天秤0度，相距151度，星座相距180度
+    let p0 = planet(Sun, 29.0, 1.0, 4);
+    let p1 = planet(Moon, 180.0, 13.0, 4);
+    assert!(p0.has_aspect_with_config(&p1, false, &config).is_none());
+
+    // 白羊29度与处女27度，相距148度，星座相距150度
+    let p1 = planet(Moon, 177.0, 13.0, 4);
+    let aspect = p0.has_aspect_with_config(&p1, false, &config);
+    assert!(aspect.is_some());
+    assert_eq!(AspectType::Quincunx, aspect.unwrap().aspect_type);
+}
+
+// 固定容许度与单独设置的容许度
+#[test]
+fn test_orb_rule() {
+    // 相距124度，半容许度为3度
+    let p0 = planet(Sun, 1.0, 1.0, 3);
+    let p1 = planet(Moon, 125.0, 13.0, 3);
+    assert!(p0.has_aspect(&p1, false).is_none());
+
+    let config = AspectConfig {
+        orb_rule: OrbRule::Fixed(5.0),
+        ..Default::default()
+    };
+    let aspect = p0.has_aspect_with_config(&p1, false, &config);
+    assert!(aspect.is_some());
+    let aspect = aspect.unwrap();
+    assert_eq!(AspectType::Trine, aspect.aspect_type);
+    assert_eq!(4.0, aspect.d);
+
+    // 三合单独设置为2度，覆盖固定容许度
+    let config = AspectConfig {
+        aspects: vec![AspectSetting::new(AspectType::Trine, Some(2.0))],
+        orb_rule: OrbRule::Fixed(5.0),
+    };
+    assert!(p0.has_aspect_with_config(&p1, false, &config).is_none());
+    assert!(p0.has_aspect_with_config(&p1, true, &config).is_none());
+}
+
+// 多个相位都在容许度内时，取最精确的相位
+#[test]
+fn test_closest_aspect() {
+    let config = AspectConfig {
+        aspects: vec![
+            AspectSetting::new(AspectType::Semisquare, None),
+            AspectSetting::new(AspectType::Sextile, None),
+        ],
+        orb_rule: OrbRule::Fixed(10.0),
+    };
+
+    // 白羊20度与双子19度，相距59度，星座相距60度
+    let p0 = planet(Sun, 20.0, 1.0, 0);
+    let p1 = planet(Moon, 79.0, 13.0, 0);
+    let aspect = p0.has_aspect_with_config(&p1, false, &config);
+    assert!(aspect.is_some());
+    let aspect = aspect.unwrap();
+    assert_eq!(AspectType::Sextile, aspect.aspect_type);
+    assert_eq!(1.0, aspect.d);
+
+    // 白羊20度与双子12度，相距52度，距半刑7度、六合8度
+    let p1 = planet(Moon, 72.0, 13.0, 0);
+    let aspect = p0.has_aspect_with_config(&p1, false, &config);
+    assert!(aspect.is_some());
+    let aspect = aspect.unwrap();
+    assert_eq!(AspectType::Semisquare, aspect.aspect_type);
+    assert_eq!(7.0, aspect.d);
+}
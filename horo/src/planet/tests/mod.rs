@@ -8,12 +8,31 @@ mod no_compare_has_aspect;
 // 比较盘相位
 mod compare_has_aspect;
 
+// 按相位配置计算相位
+mod aspect_config;
+
 // 映点
 mod antiscoin;
 
 // 反映点
 mod contraantiscia;
 
+// 平行、对等平行
+mod parallel;
+
+// 其它模块测试用的星体：黄纬、赤经、赤纬为0，每日速度1度，默认配置
+pub(crate) fn planet(name: crate::PlanetName, long: f64) -> Planet {
+    Planet::new(
+        name,
+        long,
+        0.0,
+        1.0,
+        0.0,
+        0.0,
+        &PlanetConfig::default_config(&name),
+    )
+}
+
 // 构造函数正确存储输入参数
 #[test]
 fn test_new() {
@@ -34,6 +53,10 @@ fn test_new() {
     assert_eq!(1.0, p.ra, "赤经");
     assert_eq!(1.0, p.dec, "赤纬");
     assert_eq!(1, p.orb, "容许度");
+    assert!(!p.out_of_bounds, "界外");
+    assert!(p.house.is_none(), "宫位");
+    assert!(p.mundane_position.is_none(), "世俗位置");
+    assert!(p.condition.is_none(), "行星状态");
 }
 
 // 快
@@ -0,0 +1,74 @@
+use crate::{
+    aspect::AspectType,
+    config::PlanetConfig,
+    planet::{Planet, PlanetName::*},
+};
+use parameterized::parameterized;
+
+fn planet(name: crate::PlanetName, dec: f64) -> Planet {
+    Planet::new(
+        name,
+        1.0,
+        1.0,
+        1.0,
+        1.0,
+        dec,
+        &PlanetConfig::new(name, 7, 1.0, 2.0),
+    )
+}
+
+// 平行：赤纬相同，同在天赤道以北或以南
+#[parameterized(dec = {
+    (20.0, 20.0, 0.0),
+    (20.0, 20.9, 0.9),
+    (20.0, 19.1, 0.9),
+    (-20.0, -20.9, 0.9),
+    (0.0, 0.5, 0.5),
+})]
+fn test_parallel(dec: (f64, f64, f64)) {
+    let p0 = planet(Sun, dec.0);
+    let p1 = planet(Moon, dec.1);
+
+    let aspect = p0.has_parallel(&p1, 1.0);
+    assert!(aspect.is_some(), "赤纬{}与{}平行", dec.0, dec.1);
+    let aspect = aspect.unwrap();
+    assert_eq!(0, aspect.aspect_value);
+    assert_eq!(AspectType::Conjunction, aspect.aspect_type);
+    assert!((aspect.d - dec.2).abs() < 1e-9);
+    assert_eq!(Sun, aspect.p0);
+    assert_eq!(Moon, aspect.p1);
+
+    assert!(p0.has_contraparallel(&p1, 1.0).is_none());
+}
+
+// 对等平行：赤纬绝对值相同，一在天赤道以北，一在天赤道以南
+#[parameterized(dec = {
+    (20.0, -20.0, 0.0),
+    (20.0, -20.9, 0.9),
+    (-20.0, 19.1, 0.9),
+    (0.3, -0.2, 0.1),
+})]
+fn test_contraparallel(dec: (f64, f64, f64)) {
+    let p0 = planet(Sun, dec.0);
+    let p1 = planet(Moon, dec.1);
+
+    let aspect = p0.has_contraparallel(&p1, 1.0);
+    assert!(aspect.is_some(), "赤纬{}与{}对等平行", dec.0, dec.1);
+    let aspect = aspect.unwrap();
+    assert_eq!(180, aspect.aspect_value);
+    assert_eq!(AspectType::Opposition, aspect.aspect_type);
+    assert!((aspect.d - dec.2).abs() < 1e-9);
+
+    assert!(p0.has_parallel(&p1, 1.0).is_none());
+}
+
+// 超出容许度
+#[test]
+fn test_out_of_orb() {
+    let p0 = planet(Sun, 20.0);
+
+    assert!(p0.has_parallel(&planet(Moon, 21.1), 1.0).is_none());
+    assert!(p0.has_parallel(&planet(Moon, 21.1), 1.5).is_some());
+    assert!(p0.has_contraparallel(&planet(Moon, -18.9), 1.0).is_none());
+    assert!(p0.has_contraparallel(&planet(Moon, -18.9), 1.5).is_some());
+}
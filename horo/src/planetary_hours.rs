@@ -0,0 +1,347 @@
+use crate::{
+    Error, PlanetName,
+    utils::{mod180, newton_iteration},
+};
+use geo_position::GeoPosition;
+use horo_date_time::HoroDateTime;
+use swe::{Body, HouseSystem, swe_calc_ut, swe_close, swe_degnorm, swe_houses, swe_set_ephe_path};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+// 迦勒底序，按星期排列
+//月、火、水、木、金、土、日
+// 一、二、三、四、五、六、日
+// 1  2   3  4   5  6   0
+const CHALDEAN_ORDER: [PlanetName; 7] = [
+    PlanetName::Sun,
+    PlanetName::Moon,
+    PlanetName::Mars,
+    PlanetName::Mercury,
+    PlanetName::Jupiter,
+    PlanetName::Venus,
+    PlanetName::Saturn,
+];
+
+// 时主星序，土、木、火、日、金、水、月
+const PLANETARY_HOURS_LIST: [PlanetName; 7] = [
+    PlanetName::Saturn,
+    PlanetName::Jupiter,
+    PlanetName::Mars,
+    PlanetName::Sun,
+    PlanetName::Venus,
+    PlanetName::Mercury,
+    PlanetName::Moon,
+];
+
+/// 行星时
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct PlanetaryHour {
+    /// 第几个行星时，1-12为白天，13-24为夜晚
+    pub number: u8,
+    /// 时主星
+    pub ruler: PlanetName,
+    /// 开始时间
+    pub start: HoroDateTime,
+    /// 结束时间
+    pub end: HoroDateTime,
+}
+
+/// 一个行星日（日出至次日日出）的24个不等长行星时
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct PlanetaryHours {
+    /// 日主星
+    pub planetary_day: PlanetName,
+    /// 日出时间
+    pub sunrise: HoroDateTime,
+    /// 日落时间
+    pub sunset: HoroDateTime,
+    /// 次日日出时间
+    pub next_sunrise: HoroDateTime,
+    /// 24个行星时
+    pub hours: Vec<PlanetaryHour>,
+}
+
+impl PlanetaryHours {
+    /// 计算给定时刻所在行星日的行星时
+    pub fn new(date: &HoroDateTime, geo: &GeoPosition, ephe_path: &str) -> Result<Self, Error> {
+        let sunrise = sun_on_asc(date, geo, ephe_path)?;
+        let sunset = sun_on_dsc(date, geo, ephe_path)?;
+        // 计算第二天太阳在asc的时刻，以大约中午时刻迭代，即sunOnDscTime+1.25天
+        let next_sunrise = sun_on_asc(&sunrise.plus_days(1.25)?, geo, ephe_path)?;
+
+        // 计算星期
+        // 儒略历，公元前4713年1月1日，是星期一
+        // 此日，jd = 0
+        let t = HoroDateTime::new(sunrise.year, sunrise.month, sunrise.day, 12, 0, 0, 0.0)?;
+        // jd:0,1,2,3 ,4 ,5 ,6
+        //    7,8,9,10,11,12,13
+        //    1,2,3,4 ,5 ,6, 0
+        // jd % 7 得到0, 6，对应1到7，因此需要加1
+        // 加1后，得到1到7，因此需要%7，因为7对应0，是星期日
+        let n = (t.jd_utc as u32 % 7 + 1) % 7;
+
+        // 日主星
+        let planetary_day = CHALDEAN_ORDER[n as usize];
+
+        // 白天、夜晚各12个行星时，按ut1均分
+        let day_hour = (sunset.jd_ut1 - sunrise.jd_ut1) / 12.0;
+        let night_hour = (next_sunrise.jd_ut1 - sunset.jd_ut1) / 12.0;
+        let boundary = |i: usize| {
+            if i < 12 {
+                sunrise.jd_ut1 + day_hour * i as f64
+            } else {
+                sunset.jd_ut1 + night_hour * (i - 12) as f64
+            }
+        };
+
+        let first = first_planetary_hours_index(planetary_day);
+        let mut hours = Vec::with_capacity(24);
+        let mut start = sunrise;
+        for i in 0..24 {
+            let end = match i {
+                11 => sunset,
+                23 => next_sunrise,
+                _ => HoroDateTime::from_jd_ut1_zone(boundary(i + 1), date.tz)?,
+            };
+            hours.push(PlanetaryHour {
+                number: i as u8 + 1,
+                ruler: PLANETARY_HOURS_LIST[(first + i) % 7],
+                start,
+                end,
+            });
+            start = end;
+        }
+
+        Ok(Self {
+            planetary_day,
+            sunrise,
+            sunset,
+            next_sunrise,
+            hours,
+        })
+    }
+
+    /// 给定时刻的时主星
+    pub fn ruler_at(&self, date: &HoroDateTime) -> PlanetName {
+        let first = first_planetary_hours_index(self.planetary_day);
+        if date.jd_utc < self.sunset.jd_utc {
+            let m = (12.0 * (date.jd_utc - self.sunrise.jd_utc)
+                / (self.sunset.jd_utc - self.sunrise.jd_utc)) as usize;
+            PLANETARY_HOURS_LIST[(first + m) % 7]
+        } else {
+            let m = (12.0 * (date.jd_utc - self.sunset.jd_utc)
+                / (self.next_sunrise.jd_utc - self.sunset.jd_utc)) as usize;
+            PLANETARY_HOURS_LIST[(first + m + 12) % 7]
+        }
+    }
+}
+
+/// 计算一段时间内每个行星日的行星时
+/// start_date: 开始时间
+/// end_date: 结束时间，结果包含start_date和end_date所在的行星日
+pub fn planetary_hours(
+    start_date: HoroDateTime,
+    end_date: HoroDateTime,
+    geo: GeoPosition,
+    ephe_path: &str,
+) -> Result<Vec<PlanetaryHours>, Error> {
+    if end_date.jd_utc < start_date.jd_utc {
+        return Err(Error::InvalidDateTime(
+            "结束时间不能早于开始时间".to_owned(),
+        ));
+    }
+
+    let mut days = vec![];
+    let mut t = start_date;
+    loop {
+        let day = PlanetaryHours::new(&t, &geo, ephe_path)?;
+        let next_sunrise = day.next_sunrise;
+        days.push(day);
+        if next_sunrise.jd_utc > end_date.jd_utc {
+            break;
+        }
+        // 以次日日出后半天迭代，落在下一个行星日内
+        t = next_sunrise.plus_days(0.5)?;
+    }
+
+    Ok(days)
+}
+
+// 第一个时主星
+fn first_planetary_hours_index(planetary_day: PlanetName) -> usize {
+    PLANETARY_HOURS_LIST
+        .iter()
+        .position(|p| *p == planetary_day)
+        .unwrap()
+}
+
+/// 计算给定时刻之前，太阳在东方地平线上的时刻
+fn sun_on_asc(t: &HoroDateTime, geo: &GeoPosition, ephe_path: &str) -> Result<HoroDateTime, Error> {
+    swe_set_ephe_path(ephe_path);
+    let xx = swe_calc_ut(t.jd_ut1, Body::SeSun, &[])
+        .map_err(|e| Error::Function(format!("函数sun_on_asc计算太阳位置错误:{e}")))?;
+    swe_close();
+
+    let sun_long = xx[0];
+
+    let (_, ascmc) = swe_houses(t.jd_ut1, geo.lat, geo.long, HouseSystem::B)
+        .map_err(|_e| Error::Function("函数sun_on_asc()，计算asc错误".to_string()))?;
+
+    let asc_long = ascmc[0];
+
+    // 为牛顿迭代设置初值，太阳在东方地平线时刻大约等于jd0
+    let d = swe_degnorm(asc_long - sun_long);
+    // 太阳周日运动，1天走过360度，走1度需要1/360天，走d度，需要1/360*d天
+    // 因此jd0 = jd_utc - d/360.0
+    let jd0 = t.jd_ut1 - d / 360.0;
+
+    let jd = newton_iteration(jd0, |jd| {
+        swe_set_ephe_path(ephe_path);
+        let xx = swe_calc_ut(jd, Body::SeSun, &[]).map_err(|e| {
+            Error::Function(format!("函数sun_on_asc()，牛顿迭代计算太阳位置错误:{e}"))
+        })?;
+        let (_, ascmc) = swe_houses(jd, geo.lat, geo.long, HouseSystem::B)
+            .map_err(|_e| Error::Function("函数sun_on_asc()，牛顿迭代计算asc错误".to_string()))?;
+        swe_close();
+
+        // 将结果转换到[0, 360)，再将结果转换到[-180, 180]
+        // 这样可以确保曲线上太阳与asc黄经度数相等的点是连续的，
+        Ok(mod180(swe_degnorm(ascmc[0] - xx[0])))
+    })?;
+
+    let date = HoroDateTime::from_jd_ut1_zone(jd, t.tz)?;
+    Ok(date)
+}
+
+/// 计算太阳在西方地平线上的时刻
+/// 如果太阳在地平线上，则计算之后的时刻
+/// 如果太阳在地平线下，则计算之前的时刻
+fn sun_on_dsc(t: &HoroDateTime, geo: &GeoPosition, ephe_path: &str) -> Result<HoroDateTime, Error> {
+    swe_set_ephe_path(ephe_path);
+    let xx = swe_calc_ut(t.jd_ut1, Body::SeSun, &[])
+        .map_err(|e| Error::Function(format!("函数sun_on_dsc计算太阳位置错误:{e}")))?;
+    swe_close();
+
+    let sun_long = xx[0];
+
+    let (_, ascmc) = swe_houses(t.jd_ut1, geo.lat, geo.long, HouseSystem::B)
+        .map_err(|_e| Error::Function("函数sun_on_dsc()，计算asc错误".to_string()))?;
+
+    let asc_long = ascmc[0];
+
+    let d = swe_degnorm(asc_long - sun_long);
+    // 假定白天长度为0.5天
+    let jd0 = t.jd_ut1 - d / 360.0 + 0.5;
+
+    let jd = newton_iteration(jd0, |jd| {
+        swe_set_ephe_path(ephe_path);
+
+        let xx = swe_calc_ut(jd, Body::SeSun, &[]).map_err(|e| {
+            Error::Function(format!("函数sun_on_dsc()，牛顿迭代计算太阳位置错误:{e}"))
+        })?;
+        let (_, ascmc) = swe_houses(jd, geo.lat, geo.long, HouseSystem::B)
+            .map_err(|_e| Error::Function("函数sun_on_dsc()，牛顿迭代计算asc错误".to_string()))?;
+
+        swe_close();
+
+        // 将结果转换到[0, 360)，再将结果转换到[-180, 180]
+        // 这样可以确保曲线上太阳与asc黄经度数相等的点是连续的，
+        Ok(mod180(swe_degnorm(swe_degnorm(ascmc[0] + 180.0) - xx[0])))
+    })?;
+
+    let date = HoroDateTime::from_jd_ut1_zone(jd, t.tz)?;
+    Ok(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_position::GeoPosition;
+    use horo_date_time::HoroDateTime;
+    use std::env;
+
+    use crate::PlanetName;
+
+    use super::{PlanetaryHours, planetary_hours};
+
+    fn ephe_path() -> String {
+        dotenvy::dotenv().ok();
+        env::var("EPHE_PATH")
+            .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...")
+    }
+
+    fn geo() -> GeoPosition {
+        GeoPosition::new(
+            102.0 + 41.0 / 60.0 + 59.0 / 3600.0,
+            25.0 + 1.0 / 60.0 + 53.0 / 3600.0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_planetary_hours_of_day() {
+        let ephe_path = ephe_path();
+        // 2024-3-1，星期五
+        let date = HoroDateTime::new(2024, 3, 1, 12, 0, 0, 8.0).unwrap();
+        let day = PlanetaryHours::new(&date, &geo(), &ephe_path).unwrap();
+
+        assert_eq!(day.planetary_day, PlanetName::Venus);
+        assert_eq!(day.hours.len(), 24);
+        assert_eq!(day.hours[0].ruler, day.planetary_day);
+        assert_eq!(day.hours[0].start.jd_utc, day.sunrise.jd_utc);
+        assert_eq!(day.hours[11].end.jd_utc, day.sunset.jd_utc);
+        assert_eq!(day.hours[12].start.jd_utc, day.sunset.jd_utc);
+        assert_eq!(day.hours[23].end.jd_utc, day.next_sunrise.jd_utc);
+        assert_eq!(day.hours[23].ruler, PlanetName::Moon);
+
+        for (i, hour) in day.hours.iter().enumerate() {
+            assert_eq!(usize::from(hour.number), i + 1);
+            assert!(hour.start.jd_utc < hour.end.jd_utc);
+            if i > 0 {
+                assert_eq!(hour.start.jd_utc, day.hours[i - 1].end.jd_utc);
+            }
+
+            let middle = HoroDateTime::from_jd_ut1_zone(
+                (hour.start.jd_ut1 + hour.end.jd_ut1) / 2.0,
+                hour.start.tz,
+            )
+            .unwrap();
+            assert_eq!(day.ruler_at(&middle), hour.ruler, "第{}个行星时", i + 1);
+        }
+    }
+
+    #[test]
+    fn test_planetary_hours_range() {
+        let ephe_path = ephe_path();
+        let start = HoroDateTime::new(2024, 3, 1, 12, 0, 0, 8.0).unwrap();
+        let end = HoroDateTime::new(2024, 3, 7, 12, 0, 0, 8.0).unwrap();
+        let days = planetary_hours(start, end, geo(), &ephe_path).unwrap();
+
+        assert_eq!(days.len(), 7);
+        let planetary_days: Vec<_> = days.iter().map(|d| d.planetary_day).collect();
+        assert_eq!(
+            planetary_days,
+            [
+                PlanetName::Venus,
+                PlanetName::Saturn,
+                PlanetName::Sun,
+                PlanetName::Moon,
+                PlanetName::Mars,
+                PlanetName::Mercury,
+                PlanetName::Jupiter,
+            ]
+        );
+        for d in days.windows(2) {
+            assert!((d[1].sunrise.jd_utc - d[0].next_sunrise.jd_utc).abs() < 1e-6);
+        }
+
+        assert!(planetary_hours(end, start, geo(), &ephe_path).is_err());
+    }
+}
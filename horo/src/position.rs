@@ -0,0 +1,68 @@
+use geo_position::GeoPosition;
+use swe::{Body, Flag, swe_set_topo};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 行星位置的计算方式
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum PositionMode {
+    /// 地心
+    #[default]
+    Geocentric,
+    /// 站心，以出生地计算，值为海拔高度，单位：米
+    Topocentric(f64),
+    /// 日心，星盘中没有太阳、月亮，有地球，月交点仍按地心计算，
+    /// 不计算行星状态、月亮状态、区分、阿拉伯点、必然尊贵、界外、行星所在宫位及与恒星的Paran，
+    /// 行星不与四轴、福点成相位、映点、平行，十二分部、九分部及中点也不含四轴
+    Heliocentric,
+}
+
+impl PositionMode {
+    /// 站心时，设置瑞士星历表的观测位置，须在swe_set_ephe_path之后、计算行星之前调用
+    pub(crate) fn set_topo(&self, geo: &GeoPosition) {
+        if let PositionMode::Topocentric(elevation) = self {
+            swe_set_topo(geo.long, geo.lat, *elevation);
+        }
+    }
+
+    /// 计算星体时，除SeflgSpeed、SeflgEquatorial等之外，还需加上的flag
+    pub(crate) fn flag(&self, body: Body) -> Option<Flag> {
+        match self {
+            PositionMode::Geocentric => None,
+            PositionMode::Topocentric(_) => Some(Flag::SeflgTopoctr),
+            // 日心时以地心的太阳求地球的位置，月交点是月亮轨道与黄道的交点
+            PositionMode::Heliocentric => match body {
+                Body::SeSun | Body::SeMeanNode | Body::SeTrueNode => None,
+                _ => Some(Flag::SeflgHelctr),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swe::{Body, Flag};
+
+    use super::PositionMode;
+
+    #[test]
+    fn test_flag() {
+        assert!(PositionMode::Geocentric.flag(Body::SeMoon).is_none());
+        assert!(matches!(
+            PositionMode::Topocentric(0.0).flag(Body::SeMoon),
+            Some(Flag::SeflgTopoctr)
+        ));
+        assert!(matches!(
+            PositionMode::Heliocentric.flag(Body::SeMars),
+            Some(Flag::SeflgHelctr)
+        ));
+        assert!(PositionMode::Heliocentric.flag(Body::SeSun).is_none());
+        assert!(PositionMode::Heliocentric.flag(Body::SeMeanNode).is_none());
+    }
+}
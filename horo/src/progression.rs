@@ -0,0 +1,629 @@
+use geo_position::GeoPosition;
+use horo_date_time::HoroDateTime;
+use swe::{Body, swe_calc_ut, swe_close, swe_cotrans, swe_degnorm, swe_houses, swe_set_ephe_path};
+
+use crate::{
+    Aspect, AspectConfig, Error, Horoscope, HoroscopeComparison, HoroscopeConfig, HouseName,
+    PlanetConfig, PlanetName, Transit, TransitConfig, transits,
+    utils::{calc_eps, mod180},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+// 回归年的天数
+pub(crate) const TROPICAL_YEAR: f64 = 365.2422;
+// 回归月的天数
+const LUNAR_MONTH: f64 = 27.321582;
+// Naibod：太阳每天的平均移动，单位：度
+pub(crate) const NAIBOD: f64 = 360.0 / TROPICAL_YEAR;
+
+/// 推运的方法
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum ProgressionType {
+    /// 次限推运：出生后一天对应一年
+    #[default]
+    Secondary,
+    /// 三限推运：出生后一天对应一个回归月
+    Tertiary,
+    /// 小限推运：出生后一个回归月对应一年
+    Minor,
+}
+
+impl ProgressionType {
+    // 实际时间每过一天，推运盘时间走过的天数
+    fn rate(&self) -> f64 {
+        match self {
+            ProgressionType::Secondary => 1.0 / TROPICAL_YEAR,
+            ProgressionType::Tertiary => 1.0 / LUNAR_MONTH,
+            ProgressionType::Minor => LUNAR_MONTH / TROPICAL_YEAR,
+        }
+    }
+
+    // 实际时间对应的推运盘时间，儒略日（ut1）
+    fn progressed_jd(&self, native_jd: f64, jd: f64) -> f64 {
+        native_jd + (jd - native_jd) * self.rate()
+    }
+
+    // 推运盘时间对应的实际时间，儒略日（ut1）
+    fn process_jd(&self, native_jd: f64, progressed_jd: f64) -> f64 {
+        native_jd + (progressed_jd - native_jd) / self.rate()
+    }
+}
+
+/// 推运中天的方法
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum McProgression {
+    /// 太阳弧：本命中天的黄经加上推运太阳走过的黄经
+    #[default]
+    SolarArc,
+    /// Naibod赤经：推运盘时间每过一天，本命中天的赤经加59分08秒
+    NaibodInRa,
+    /// 平太阳：推运盘时间每过一天，本命中天的黄经加59分08秒
+    MeanSun,
+}
+
+/// 推运盘
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Progression {
+    /// 出生时间
+    pub native_date: HoroDateTime,
+    /// 推运时间
+    pub process_date: HoroDateTime,
+    /// 推运盘的时间
+    pub progressed_date: HoroDateTime,
+    /// 推运的方法
+    pub progression_type: ProgressionType,
+    /// 推运中天的方法
+    pub mc_progression: McProgression,
+    /// 推运盘：行星、福点、阿拉伯点按推运盘的时间计算，四轴及宫头按推运的中天及出生地纬度计算
+    pub horoscope: Horoscope,
+    /// 推运盘与本命盘的相位，p0为本命星体，p1为推运星体
+    pub aspects: Vec<Aspect>,
+    /// 推运盘与本命盘的映点
+    pub antiscoins: Vec<Aspect>,
+    /// 推运盘与本命盘的反映点
+    pub contraantiscias: Vec<Aspect>,
+    /// 推运盘与本命盘的平行，相位值为0
+    pub parallels: Vec<Aspect>,
+    /// 推运盘与本命盘的对等平行，相位值为180
+    pub contraparallels: Vec<Aspect>,
+}
+
+/// 次限推运：出生后一天对应一年
+/// * native_date: 出生时间
+/// * process_date: 推运时间，不早于出生时间
+/// * geo: 出生地
+/// * mc_progression: 推运中天的方法
+pub fn secondary_progression(
+    native_date: HoroDateTime,
+    process_date: HoroDateTime,
+    geo: GeoPosition,
+    house_name: HouseName,
+    planets_config: &[PlanetConfig],
+    mc_progression: McProgression,
+    config: &HoroscopeConfig,
+    ephe_path: &str,
+) -> Result<Progression, Error> {
+    progression(
+        ProgressionType::Secondary,
+        native_date,
+        process_date,
+        geo,
+        house_name,
+        planets_config,
+        mc_progression,
+        config,
+        ephe_path,
+    )
+}
+
+/// 三限推运：出生后一天对应一个回归月，参数同次限推运
+pub fn tertiary_progression(
+    native_date: HoroDateTime,
+    process_date: HoroDateTime,
+    geo: GeoPosition,
+    house_name: HouseName,
+    planets_config: &[PlanetConfig],
+    mc_progression: McProgression,
+    config: &HoroscopeConfig,
+    ephe_path: &str,
+) -> Result<Progression, Error> {
+    progression(
+        ProgressionType::Tertiary,
+        native_date,
+        process_date,
+        geo,
+        house_name,
+        planets_config,
+        mc_progression,
+        config,
+        ephe_path,
+    )
+}
+
+/// 小限推运：出生后一个回归月对应一年，参数同次限推运
+pub fn minor_progression(
+    native_date: HoroDateTime,
+    process_date: HoroDateTime,
+    geo: GeoPosition,
+    house_name: HouseName,
+    planets_config: &[PlanetConfig],
+    mc_progression: McProgression,
+    config: &HoroscopeConfig,
+    ephe_path: &str,
+) -> Result<Progression, Error> {
+    progression(
+        ProgressionType::Minor,
+        native_date,
+        process_date,
+        geo,
+        house_name,
+        planets_config,
+        mc_progression,
+        config,
+        ephe_path,
+    )
+}
+
+/// 查询期间推运行星与本命盘中行星、四轴、宫头、福点及阿拉伯点形成的相位，
+/// 进入、离开容许度的容许度为1度
+/// * natal: 本命盘
+/// * start_date: 查询的开始时间，不早于出生时间
+/// * end_date: 查询的结束时间
+/// * progression_type: 推运的方法
+/// * config: 相位配置，只使用其中启用的相位
+///
+/// 返回值：按第一次正相位的时间排序，时间均为实际时间，transiting为推运行星
+pub fn progressed_aspects(
+    natal: &Horoscope,
+    start_date: HoroDateTime,
+    end_date: HoroDateTime,
+    progression_type: ProgressionType,
+    config: &AspectConfig,
+    ephe_path: &str,
+) -> Result<Vec<Transit>, Error> {
+    let native_jd = natal.date.jd_ut1;
+    if start_date.jd_utc < natal.date.jd_utc {
+        return Err(Error::InvalidProcessDateTime(
+            "推运时间不能早于出生时间".to_string(),
+        ));
+    }
+    if end_date.jd_utc <= start_date.jd_utc {
+        return Err(Error::InvalidProcessDateTime(
+            "查询的结束时间应晚于开始时间".to_string(),
+        ));
+    }
+
+    let progressed_date = |date: &HoroDateTime| {
+        HoroDateTime::from_jd_ut1_zone(
+            progression_type.progressed_jd(native_jd, date.jd_ut1),
+            start_date.tz,
+        )
+    };
+    let process_date = |date: &HoroDateTime| {
+        HoroDateTime::from_jd_ut1_zone(
+            progression_type.process_jd(native_jd, date.jd_ut1),
+            start_date.tz,
+        )
+    };
+
+    let transit_config = TransitConfig {
+        planets: natal.planets.iter().map(|p| p.name).collect(),
+        aspects: config.aspects.iter().map(|s| s.aspect_type).collect(),
+        orb: 1.0,
+    };
+    transits(
+        natal,
+        progressed_date(&start_date)?,
+        progressed_date(&end_date)?,
+        &transit_config,
+        ephe_path,
+    )?
+    .into_iter()
+    .map(|transit| -> Result<Transit, Error> {
+        Ok(Transit {
+            exact_dates: transit
+                .exact_dates
+                .iter()
+                .map(process_date)
+                .collect::<Result<_, _>>()?,
+            entry_date: transit.entry_date.as_ref().map(process_date).transpose()?,
+            exit_date: transit.exit_date.as_ref().map(process_date).transpose()?,
+            ..transit
+        })
+    })
+    .collect()
+}
+
+/// 推运盘，progression_type为推运的方法，其余参数同次限推运
+pub fn progression(
+    progression_type: ProgressionType,
+    native_date: HoroDateTime,
+    process_date: HoroDateTime,
+    geo: GeoPosition,
+    house_name: HouseName,
+    planets_config: &[PlanetConfig],
+    mc_progression: McProgression,
+    config: &HoroscopeConfig,
+    ephe_path: &str,
+) -> Result<Progression, Error> {
+    if process_date.jd_utc < native_date.jd_utc {
+        return Err(Error::InvalidProcessDateTime(
+            "推运时间不能早于出生时间".to_string(),
+        ));
+    }
+
+    let natal = Horoscope::new(
+        native_date,
+        geo,
+        house_name,
+        planets_config,
+        config,
+        ephe_path,
+    )?;
+
+    let progressed_jd = progression_type.progressed_jd(native_date.jd_ut1, process_date.jd_ut1);
+    let progressed_date = HoroDateTime::from_jd_ut1_zone(progressed_jd, native_date.tz)?;
+    // 推运盘时间走过的天数
+    let days = progressed_jd - native_date.jd_ut1;
+
+    // 推运太阳走过的黄经
+    let ayanamsa = config.zodiac.ayanamsa(progressed_date.jd_ut1, ephe_path);
+    swe_set_ephe_path(ephe_path);
+    let xx = swe_calc_ut(progressed_date.jd_ut1, Body::SeSun, &[])
+        .map_err(|e| Error::Function(format!("计算推运太阳黄道经度错误:{e}")))?;
+    swe_close();
+    let natal_sun_long = natal
+        .planets
+        .iter()
+        .find(|p| p.name == PlanetName::Sun)
+        .map(|p| p.long)
+        .unwrap_or_default();
+    let solar_arc = swe_degnorm(xx[0] - ayanamsa - natal_sun_long);
+
+    let eps = calc_eps(progressed_date.jd_ut1, ephe_path)?;
+    let armc = progressed_armc(
+        mc_progression,
+        natal.mc.long,
+        natal.mc.ra,
+        solar_arc,
+        days,
+        ayanamsa,
+        eps,
+    );
+
+    // 调整地理经度，使推运盘时间的中天赤经为推运的中天赤经
+    let (_, ascmc) = swe_houses(progressed_date.jd_ut1, geo.lat, geo.long, house_name.into())
+        .map_err(|_| Error::Function("swe_houses()调用失败".to_owned()))?;
+    let progressed_geo = GeoPosition {
+        long: mod180(geo.long + mod180(armc - ascmc[2])),
+        lat: geo.lat,
+    };
+
+    let mut horoscope = Horoscope::new(
+        progressed_date,
+        progressed_geo,
+        house_name,
+        planets_config,
+        config,
+        ephe_path,
+    )?;
+    horoscope.geo = geo;
+
+    let (aspects, antiscoins, contraantiscias) =
+        HoroscopeComparison::calculate_aspects(&natal, &horoscope, config);
+    let (parallels, contraparallels) =
+        HoroscopeComparison::calculate_parallels(&natal, &horoscope, config);
+
+    Ok(Progression {
+        native_date,
+        process_date,
+        progressed_date,
+        progression_type,
+        mc_progression,
+        horoscope,
+        aspects,
+        antiscoins,
+        contraantiscias,
+        parallels,
+        contraparallels,
+    })
+}
+
+// 推运的中天赤经，黄经为所选黄道的经度
+fn progressed_armc(
+    mc_progression: McProgression,
+    natal_mc_long: f64,
+    natal_mc_ra: f64,
+    solar_arc: f64,
+    days: f64,
+    ayanamsa: f64,
+    eps: f64,
+) -> f64 {
+    let ra_of = |long: f64| swe_cotrans(swe_degnorm(long + ayanamsa), 0.0, 1.0, -eps)[0];
+    match mc_progression {
+        McProgression::SolarArc => ra_of(natal_mc_long + solar_arc),
+        McProgression::NaibodInRa => swe_degnorm(natal_mc_ra + NAIBOD * days),
+        McProgression::MeanSun => ra_of(natal_mc_long + NAIBOD * days),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use horo_date_time::HoroDateTime;
+    use parameterized::parameterized;
+    use swe::{Body, swe_calc_ut, swe_close, swe_degnorm, swe_set_ephe_path};
+
+    use crate::{
+        AspectConfig, Error, Horoscope, HoroscopeConfig, HouseName, PlanetConfig,
+        PlanetName::*,
+        TransitPoint,
+        horoscope::tests::{get_ephe_path, natal_chart, native_geo},
+    };
+
+    use super::{
+        McProgression, NAIBOD, ProgressionType, minor_progression, progressed_armc,
+        progressed_aspects, secondary_progression, tertiary_progression,
+    };
+
+    // 黄赤倾角为0时，赤经等于黄经
+    #[parameterized(case = {
+        (McProgression::SolarArc, 10.0, 30.0, 40.0),
+        (McProgression::SolarArc, 350.0, 30.0, 20.0),
+        (McProgression::NaibodInRa, 10.0, 30.0, 100.0 + NAIBOD * 30.0),
+        (McProgression::MeanSun, 10.0, 30.0, 10.0 + NAIBOD * 30.0),
+    })]
+    fn test_progressed_armc(case: (McProgression, f64, f64, f64)) {
+        let armc = progressed_armc(case.0, case.1, 100.0, case.2, 30.0, 0.0, 0.0);
+        assert!((armc - case.3).abs() < 1e-9, "{armc} != {}", case.3);
+    }
+
+    // 出生后30年
+    #[parameterized(case = {
+        (ProgressionType::Secondary, 30.0),
+        (ProgressionType::Tertiary, 30.0 * 365.2422 / 27.321582),
+        (ProgressionType::Minor, 30.0 * 27.321582),
+    })]
+    fn test_progressed_jd(case: (ProgressionType, f64)) {
+        let native_jd = 2451545.0;
+        let jd = native_jd + 30.0 * 365.2422;
+        let progressed_jd = case.0.progressed_jd(native_jd, jd);
+        assert!((progressed_jd - native_jd - case.1).abs() < 1e-6);
+        assert!((case.0.process_jd(native_jd, progressed_jd) - jd).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_secondary_progression() {
+        let ephe_path = get_ephe_path();
+
+        let native_date = HoroDateTime::new(2000, 3, 20, 10, 30, 20, 8.0).unwrap();
+        let process_date = HoroDateTime::new(2030, 3, 20, 10, 30, 20, 8.0).unwrap();
+        let geo = native_geo();
+        let planets_config = PlanetConfig::default_configs(false);
+        let config = HoroscopeConfig::default();
+
+        assert!(matches!(
+            secondary_progression(
+                process_date,
+                native_date,
+                geo,
+                HouseName::Alcabitus,
+                &planets_config,
+                McProgression::SolarArc,
+                &config,
+                &ephe_path,
+            ),
+            Err(Error::InvalidProcessDateTime(_))
+        ));
+
+        let natal = natal_chart(native_date, &config);
+        let sun_long = |horo: &Horoscope| horo.planets.iter().find(|p| p.name == Sun).unwrap().long;
+
+        // 一天对应一年
+        let years = (process_date.jd_ut1 - native_date.jd_ut1) / 365.2422;
+
+        for mc_progression in [
+            McProgression::SolarArc,
+            McProgression::NaibodInRa,
+            McProgression::MeanSun,
+        ] {
+            let progression = secondary_progression(
+                native_date,
+                process_date,
+                geo,
+                HouseName::Alcabitus,
+                &planets_config,
+                mc_progression,
+                &config,
+                &ephe_path,
+            )
+            .unwrap();
+
+            assert!(
+                (progression.progressed_date.jd_ut1 - native_date.jd_ut1 - years).abs() < 1e-5,
+                "{mc_progression:?}"
+            );
+            assert_eq!(geo.long, progression.horoscope.geo.long);
+
+            let d = match mc_progression {
+                McProgression::SolarArc => {
+                    swe_degnorm(progression.horoscope.mc.long - natal.mc.long)
+                        - swe_degnorm(sun_long(&progression.horoscope) - sun_long(&natal))
+                }
+                McProgression::NaibodInRa => {
+                    swe_degnorm(progression.horoscope.mc.ra - natal.mc.ra)
+                        - 360.0 / 365.2422 * years
+                }
+                McProgression::MeanSun => {
+                    swe_degnorm(progression.horoscope.mc.long - natal.mc.long)
+                        - 360.0 / 365.2422 * years
+                }
+            };
+            assert!(d.abs() < 1e-4, "{mc_progression:?}: {d}");
+
+            // 推运盘与本命盘的相位，p0为本命星体
+            for aspect in &progression.aspects {
+                assert!(
+                    natal
+                        .planets
+                        .iter()
+                        .chain([&natal.asc, &natal.mc, &natal.dsc, &natal.ic])
+                        .chain([&natal.part_of_fortune])
+                        .any(|p| p.name == aspect.p0)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tertiary_and_minor_progression() {
+        let ephe_path = get_ephe_path();
+
+        let native_date = HoroDateTime::new(2000, 3, 20, 10, 30, 20, 8.0).unwrap();
+        let process_date = HoroDateTime::new(2030, 3, 20, 10, 30, 20, 8.0).unwrap();
+        let geo = native_geo();
+        let planets_config = PlanetConfig::default_configs(false);
+        let config = HoroscopeConfig::default();
+
+        let days = process_date.jd_ut1 - native_date.jd_ut1;
+
+        // 三限：一天对应一个回归月
+        let tertiary = tertiary_progression(
+            native_date,
+            process_date,
+            geo,
+            HouseName::Alcabitus,
+            &planets_config,
+            McProgression::SolarArc,
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+        assert_eq!(ProgressionType::Tertiary, tertiary.progression_type);
+        assert!(
+            (tertiary.progressed_date.jd_ut1 - native_date.jd_ut1 - days / 27.321582).abs() < 1e-5
+        );
+
+        // 小限：一个回归月对应一年
+        let minor = minor_progression(
+            native_date,
+            process_date,
+            geo,
+            HouseName::Alcabitus,
+            &planets_config,
+            McProgression::SolarArc,
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+        assert_eq!(ProgressionType::Minor, minor.progression_type);
+        assert!(
+            (minor.progressed_date.jd_ut1 - native_date.jd_ut1 - days / 365.2422 * 27.321582).abs()
+                < 1e-5
+        );
+
+        // 推运盘的行星即推运盘时间的行星
+        let horo = natal_chart(minor.progressed_date, &config);
+        for (p0, p1) in horo.planets.iter().zip(&minor.horoscope.planets) {
+            assert_eq!(p0.name, p1.name);
+            assert!((p0.long - p1.long).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_progressed_aspects() {
+        let ephe_path = get_ephe_path();
+
+        let native_date = HoroDateTime::new(2000, 3, 20, 10, 30, 20, 8.0).unwrap();
+        let start_date = HoroDateTime::new(2020, 1, 1, 0, 0, 0, 8.0).unwrap();
+        let end_date = HoroDateTime::new(2030, 1, 1, 0, 0, 0, 8.0).unwrap();
+        let natal = natal_chart(native_date, &HoroscopeConfig::default());
+        let config = AspectConfig::default();
+
+        assert!(matches!(
+            progressed_aspects(
+                &natal,
+                end_date,
+                start_date,
+                ProgressionType::Secondary,
+                &config,
+                &ephe_path
+            ),
+            Err(Error::InvalidProcessDateTime(_))
+        ));
+
+        let natal_long = |point: TransitPoint| match point {
+            TransitPoint::Cusp(i) => natal.cusps[usize::from(i - 1)],
+            TransitPoint::Planet(name) => {
+                [&natal.asc, &natal.mc, &natal.dsc, &natal.ic]
+                    .into_iter()
+                    .chain(&natal.planets)
+                    .chain([&natal.part_of_fortune])
+                    .find(|p| p.name == name)
+                    .unwrap()
+                    .long
+            }
+        };
+
+        swe_set_ephe_path(&ephe_path);
+        for (progression_type, rate) in [
+            (ProgressionType::Secondary, 1.0 / 365.2422),
+            (ProgressionType::Tertiary, 1.0 / 27.321582),
+            (ProgressionType::Minor, 27.321582 / 365.2422),
+        ] {
+            let aspects = progressed_aspects(
+                &natal,
+                start_date,
+                end_date,
+                progression_type,
+                &config,
+                &ephe_path,
+            )
+            .unwrap();
+
+            // 三限、小限推运的月亮十年内必然与本命盘形成相位
+            if progression_type != ProgressionType::Secondary {
+                assert!(aspects.iter().any(|aspect| aspect.transiting == Moon));
+            }
+
+            for aspect in &aspects {
+                let body = match aspect.transiting {
+                    Sun => Body::SeSun,
+                    Moon => Body::SeMoon,
+                    Mercury => Body::SeMercury,
+                    Venus => Body::SeVenus,
+                    Mars => Body::SeMars,
+                    Jupiter => Body::SeJupiter,
+                    Saturn => Body::SeSaturn,
+                    _ => Body::SeMeanNode,
+                };
+                let angle = f64::from(aspect.aspect_type.angle());
+                for date in &aspect.exact_dates {
+                    // 正相位的时间为实际时间，在查询期间内
+                    assert!(date.jd_utc >= start_date.jd_utc && date.jd_utc <= end_date.jd_utc);
+
+                    let progressed_jd =
+                        native_date.jd_ut1 + (date.jd_ut1 - native_date.jd_ut1) * rate;
+                    let xx = swe_calc_ut(progressed_jd, body, &[]).unwrap();
+                    let d = swe_degnorm(xx[0] - natal_long(aspect.natal));
+                    let d = d.min(360.0 - d);
+                    assert!(
+                        (d - angle).abs() < 1e-4,
+                        "{progression_type:?} {aspect:?}: {d}"
+                    );
+                }
+            }
+        }
+        swe_close();
+    }
+}
@@ -3,7 +3,7 @@ use horo_date_time::{HoroDateTime, horo_date_time};
 use swe::swe_degnorm;
 
 use crate::{
-    Error, Horoscope, HouseName, PlanetConfig, PlanetName, Promittor,
+    Error, Horoscope, HoroscopeConfig, HouseName, PlanetConfig, PlanetName, Promittor,
     direction::{PTOLEMY_TERM, PtolemyTerm},
 };
 
@@ -26,10 +26,18 @@ pub fn quadrant_process(
     geo: GeoPosition,
     house_name: HouseName,
     planets_config: &[PlanetConfig],
+    config: &HoroscopeConfig,
     ephe_path: &str,
 ) -> Result<Vec<QuadrantProcess>, Error> {
     // 计算原星盘
-    let horo = Horoscope::new(native_date, geo, house_name, planets_config, ephe_path)?;
+    let horo = Horoscope::new(
+        native_date,
+        geo,
+        house_name,
+        planets_config,
+        config,
+        ephe_path,
+    )?;
 
     // 关键时间点：ASC(出生)、MC(20岁)、DSC(40岁)、IC(60岁)、ASC结束(80岁)
     let asc_date = horo.date;
@@ -158,10 +166,18 @@ pub fn quadrant_process_longitude(
     geo: GeoPosition,
     house_name: HouseName,
     planets_config: &[PlanetConfig],
+    config: &HoroscopeConfig,
     ephe_path: &str,
 ) -> Result<f64, Error> {
     // 计算原星盘
-    let horo = Horoscope::new(native_date, geo, house_name, planets_config, ephe_path)?;
+    let horo = Horoscope::new(
+        native_date,
+        geo,
+        house_name,
+        planets_config,
+        config,
+        ephe_path,
+    )?;
 
     // 象限边界时间
     let mc_date = horo_date_time(
@@ -245,13 +261,15 @@ fn promittors_of_process(horo: &Horoscope) -> Vec<(Promittor, f64)> {
         .planets
         .iter()
         .chain(std::iter::once(&horo.part_of_fortune))
+        .chain(horo.lots.iter().map(|lot| &lot.planet))
         .flat_map(|planet| {
             let mut promittors = vec![];
             // 计算合相
             promittors.push((Promittor::Conjunction(planet.name), planet.long));
 
-            // 映点
-            let antiscoins_long = swe_degnorm(180.0 - planet.long);
+            // 映点，以回归黄道的冬夏至轴对称，再换回所选黄道
+            let antiscoins_long =
+                swe_degnorm(180.0 - (planet.long + horo.ayanamsa) - horo.ayanamsa);
             promittors.push((Promittor::Antiscoins(planet.name), antiscoins_long));
 
             // 南北交点不用计算反映点，也不需要计算基它相位
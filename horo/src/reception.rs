@@ -0,0 +1,302 @@
+use crate::{
+    DignityType, Planet, PlanetName,
+    dignity::{SEVEN_PLANETS, dignity_ruler, domicile_ruler, sign_of},
+};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 接纳：行星received落在行星receiver的尊贵位置上，receiver接纳received
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Reception {
+    /// 接纳的行星
+    pub receiver: PlanetName,
+    /// 被接纳的行星
+    pub received: PlanetName,
+    /// 被接纳行星所在位置上，接纳行星拥有的尊贵：庙、旺、三分、界、面
+    pub dignities: Vec<DignityType>,
+    /// 互容：true，单向接纳：false
+    pub mutual: bool,
+}
+
+/// 互容：两颗行星各自落在对方的尊贵位置上，无论是否成相位
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct MutualReception {
+    pub p0: PlanetName,
+    pub p1: PlanetName,
+    /// p0所在位置上，p1拥有的尊贵
+    pub p1_receives_p0: Vec<DignityType>,
+    /// p1所在位置上，p0拥有的尊贵
+    pub p0_receives_p1: Vec<DignityType>,
+}
+
+/// 行星的定位星链：从行星起，依次取所在星座的庙主星，直至入庙或循环
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct DispositorChain {
+    /// 行星
+    pub planet: PlanetName,
+    /// 定位星链，第一个为行星本身
+    pub chain: Vec<PlanetName>,
+}
+
+/// 七颗古典行星的定位星
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Dispositors {
+    /// 各行星的定位星链
+    pub chains: Vec<DispositorChain>,
+    /// 最终定位星：所有定位星链都终止于同一颗入庙的行星时，该行星为最终定位星，否则为None
+    pub final_dispositor: Option<PlanetName>,
+}
+
+impl Dispositors {
+    /// 计算七颗古典行星的定位星链及最终定位星
+    pub(crate) fn new(planets: &[Planet]) -> Self {
+        let long_of = |name: PlanetName| planets.iter().find(|p| p.name == name).map(|p| p.long);
+
+        let chains: Vec<_> = SEVEN_PLANETS
+            .iter()
+            .filter(|&&name| long_of(name).is_some())
+            .map(|&planet| {
+                let mut chain = vec![planet];
+                let mut current = planet;
+                while let Some(long) = long_of(current) {
+                    let dispositor = domicile_ruler(sign_of(long));
+                    if chain.contains(&dispositor) {
+                        break;
+                    }
+                    chain.push(dispositor);
+                    current = dispositor;
+                }
+                DispositorChain { planet, chain }
+            })
+            .collect();
+
+        // 定位星链的最后一颗行星入庙，链才终止于此，否则为循环
+        let terminal = |chain: &DispositorChain| {
+            let last = *chain.chain.last()?;
+            let long = long_of(last)?;
+            (domicile_ruler(sign_of(long)) == last).then_some(last)
+        };
+        let final_dispositor = chains
+            .first()
+            .and_then(terminal)
+            .filter(|&planet| chains.iter().all(|chain| terminal(chain) == Some(planet)));
+
+        Self {
+            chains,
+            final_dispositor,
+        }
+    }
+}
+
+/// 行星received所在位置上，行星receiver拥有的尊贵
+fn received_dignities(
+    receiver: PlanetName,
+    received_long: f64,
+    is_diurnal: bool,
+) -> Vec<DignityType> {
+    [
+        DignityType::Domicile,
+        DignityType::Exaltation,
+        DignityType::Triplicity,
+        DignityType::Term,
+        DignityType::Face,
+    ]
+    .into_iter()
+    .filter(|&dignity| dignity_ruler(dignity, received_long, is_diurnal) == Some(receiver))
+    .collect()
+}
+
+/// 两颗行星间的接纳，只计算七颗古典行星
+///
+/// 返回值：p0接纳p1、p1接纳p0，没有接纳的一方不返回
+pub(crate) fn calc_receptions(p0: &Planet, p1: &Planet, is_diurnal: bool) -> Vec<Reception> {
+    if p0.name == p1.name || !SEVEN_PLANETS.contains(&p0.name) || !SEVEN_PLANETS.contains(&p1.name)
+    {
+        return vec![];
+    }
+
+    let p0_receives_p1 = received_dignities(p0.name, p1.long, is_diurnal);
+    let p1_receives_p0 = received_dignities(p1.name, p0.long, is_diurnal);
+    let mutual = !p0_receives_p1.is_empty() && !p1_receives_p0.is_empty();
+
+    [
+        (p0.name, p1.name, p0_receives_p1),
+        (p1.name, p0.name, p1_receives_p0),
+    ]
+    .into_iter()
+    .filter(|(_, _, dignities)| !dignities.is_empty())
+    .map(|(receiver, received, dignities)| Reception {
+        receiver,
+        received,
+        dignities,
+        mutual,
+    })
+    .collect()
+}
+
+/// 七颗古典行星间的互容，无论是否成相位
+pub(crate) fn calc_mutual_receptions(planets: &[Planet], is_diurnal: bool) -> Vec<MutualReception> {
+    let planets: Vec<_> = planets
+        .iter()
+        .filter(|p| SEVEN_PLANETS.contains(&p.name))
+        .collect();
+
+    let mut mutual_receptions = vec![];
+    for (i, p0) in planets.iter().enumerate() {
+        for p1 in &planets[i + 1..] {
+            let p0_receives_p1 = received_dignities(p0.name, p1.long, is_diurnal);
+            let p1_receives_p0 = received_dignities(p1.name, p0.long, is_diurnal);
+            if !p0_receives_p1.is_empty() && !p1_receives_p0.is_empty() {
+                mutual_receptions.push(MutualReception {
+                    p0: p0.name,
+                    p1: p1.name,
+                    p1_receives_p0,
+                    p0_receives_p1,
+                })
+            }
+        }
+    }
+    mutual_receptions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DignityType, PlanetName::*, planet::tests::planet};
+
+    use super::{Dispositors, calc_mutual_receptions, calc_receptions, received_dignities};
+
+    #[test]
+    fn test_received_dignities() {
+        // 白羊19度：火星的庙，太阳的旺、白天盘三分、面，水星的界
+        assert_eq!(
+            received_dignities(Mars, 19.0, true),
+            vec![DignityType::Domicile]
+        );
+        assert_eq!(
+            received_dignities(Sun, 19.0, true),
+            vec![
+                DignityType::Exaltation,
+                DignityType::Triplicity,
+                DignityType::Face
+            ]
+        );
+        assert_eq!(
+            received_dignities(Jupiter, 19.0, false),
+            vec![DignityType::Triplicity]
+        );
+        assert!(received_dignities(Saturn, 19.0, true).is_empty());
+    }
+
+    #[test]
+    fn test_calc_receptions() {
+        // 火星在金牛、金星在白羊：以庙互容
+        let mars = planet(Mars, 45.0);
+        let venus = planet(Venus, 5.0);
+        let receptions = calc_receptions(&mars, &venus, true);
+        assert_eq!(receptions.len(), 2);
+        assert!(receptions.iter().all(|r| r.mutual));
+        assert_eq!(receptions[0].receiver, Mars);
+        assert_eq!(receptions[0].received, Venus);
+        assert!(receptions[0].dignities.contains(&DignityType::Domicile));
+        assert_eq!(receptions[1].receiver, Venus);
+        assert_eq!(receptions[1].received, Mars);
+        assert!(receptions[1].dignities.contains(&DignityType::Domicile));
+
+        // 土星在白羊、月亮在摩羯：月亮落在土星的庙，土星不落在月亮的尊贵位置
+        let saturn = planet(Saturn, 25.0);
+        let moon = planet(Moon, 280.0);
+        let receptions = calc_receptions(&saturn, &moon, true);
+        assert_eq!(receptions.len(), 1);
+        assert_eq!(receptions[0].receiver, Saturn);
+        assert_eq!(receptions[0].received, Moon);
+        assert!(!receptions[0].mutual);
+
+        // 非古典行星没有接纳
+        assert!(calc_receptions(&planet(NorthNode, 45.0), &venus, true).is_empty());
+    }
+
+    #[test]
+    fn test_calc_mutual_receptions() {
+        let planets = [
+            planet(Mars, 45.0),
+            planet(Venus, 5.0),
+            planet(Moon, 280.0),
+            planet(Saturn, 100.0),
+            planet(Sun, 200.0),
+        ];
+        let mutual_receptions = calc_mutual_receptions(&planets, true);
+        assert_eq!(
+            mutual_receptions
+                .iter()
+                .map(|r| (r.p0, r.p1))
+                .collect::<Vec<_>>(),
+            vec![(Mars, Venus), (Mars, Moon), (Venus, Sun), (Moon, Saturn)]
+        );
+        // 火星在金牛、月亮在摩羯：以旺互容
+        assert!(
+            mutual_receptions[1]
+                .p0_receives_p1
+                .contains(&DignityType::Exaltation)
+        );
+        assert!(
+            mutual_receptions[1]
+                .p1_receives_p0
+                .contains(&DignityType::Exaltation)
+        );
+        // 月亮在摩羯、土星在巨蟹：以庙互容
+        assert_eq!(
+            mutual_receptions[3].p1_receives_p0,
+            vec![DignityType::Domicile]
+        );
+        assert_eq!(
+            mutual_receptions[3].p0_receives_p1,
+            vec![DignityType::Domicile]
+        );
+    }
+
+    #[test]
+    fn test_dispositors() {
+        // 木星在人马入庙，其余行星最终都由木星定位
+        let planets = [
+            planet(Sun, 250.0),
+            planet(Moon, 340.0),
+            planet(Mercury, 5.0),
+            planet(Venus, 130.0),
+            planet(Mars, 260.0),
+            planet(Jupiter, 245.0),
+            planet(Saturn, 355.0),
+        ];
+        let dispositors = Dispositors::new(&planets);
+        assert_eq!(dispositors.chains.len(), 7);
+        assert_eq!(dispositors.chains[2].chain, vec![Mercury, Mars, Jupiter]);
+        assert_eq!(dispositors.chains[3].chain, vec![Venus, Sun, Jupiter]);
+        assert_eq!(dispositors.chains[5].chain, vec![Jupiter]);
+        assert_eq!(dispositors.final_dispositor, Some(Jupiter));
+
+        // 火星在金牛、金星在白羊，以庙互容，没有最终定位星
+        let planets = [
+            planet(Sun, 250.0),
+            planet(Moon, 340.0),
+            planet(Mercury, 5.0),
+            planet(Venus, 5.0),
+            planet(Mars, 45.0),
+            planet(Jupiter, 245.0),
+            planet(Saturn, 355.0),
+        ];
+        let dispositors = Dispositors::new(&planets);
+        assert_eq!(dispositors.chains[4].chain, vec![Mars, Venus]);
+        assert_eq!(dispositors.final_dispositor, None);
+    }
+}
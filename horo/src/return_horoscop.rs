@@ -4,7 +4,7 @@ mod tests;
 use swe::{Body, swe_calc_ut, swe_close, swe_degnorm, swe_set_ephe_path};
 
 use crate::{
-    Aspect, Error, Horoscope, HouseName, Planet, PlanetConfig,
+    Aspect, Error, Horoscope, HoroscopeConfig, HouseName, Lot, Planet, PlanetConfig,
     utils::{mod180, newton_iteration},
 };
 use geo_position::GeoPosition;
@@ -51,6 +51,9 @@ pub struct ReturnHoroscop {
     /// 福点
     pub part_of_fortune: Planet,
 
+    /// 阿拉伯点
+    pub lots: Vec<Lot>,
+
     /// 行星相位，仅包含四轴、行星间的相位
     pub aspects: Vec<Aspect>,
     // 映点
@@ -66,6 +69,7 @@ pub fn solar_return(
     geo: GeoPosition,
     house_name: HouseName,
     planets_config: &[PlanetConfig],
+    config: &HoroscopeConfig,
     ephe_path: &str,
 ) -> Result<ReturnHoroscop, Error> {
     // 计算本命星盘太阳黄道经度
@@ -73,12 +77,14 @@ pub fn solar_return(
     let xx = swe_calc_ut(native_date.jd_ut1, Body::SeSun, &[])
         .map_err(|e| Error::Function(format!("计算本命星盘太阳黄道经度错误:{e}")))?;
     swe_close();
-    let native_sun_long = xx[0];
+    // 所选黄道的经度
+    let native_sun_long = xx[0] - config.zodiac.ayanamsa(native_date.jd_ut1, ephe_path);
 
     // 计算推运时刻黄道经度
+    swe_set_ephe_path(ephe_path);
     let xx = swe_calc_ut(process_date.jd_ut1, Body::SeSun, &[])
         .map_err(|e| Error::Function(format!("计算推运时刻太阳黄道经度错误:{e}")))?;
-    let process_sun_long = xx[0];
+    let process_sun_long = xx[0] - config.zodiac.ayanamsa(process_date.jd_ut1, ephe_path);
 
     // 计算迭代初值
     let jd0 = process_date.jd_ut1 - swe_degnorm(process_sun_long - native_sun_long);
@@ -93,7 +99,8 @@ pub fn solar_return(
 
         swe_close();
 
-        Ok(mod180(xx[0] - native_sun_long))
+        let sun_long = xx[0] - config.zodiac.ayanamsa(t0.jd_ut1, ephe_path);
+        Ok(mod180(sun_long - native_sun_long))
     })?;
 
     let solar_return_date = HoroDateTime::from_jd_ut1_zone(return_jd, process_date.tz)?;
@@ -103,6 +110,7 @@ pub fn solar_return(
         geo,
         house_name,
         planets_config,
+        config,
         ephe_path,
     )?;
     Ok(ReturnHoroscop {
@@ -118,6 +126,7 @@ pub fn solar_return(
         ic: horo.ic,
         // 福点
         part_of_fortune: horo.part_of_fortune,
+        lots: horo.lots,
         planets: horo.planets,
         aspects: horo.aspects,
         antiscoins: horo.antiscoins,
@@ -132,6 +141,7 @@ pub fn lunar_return(
     geo: GeoPosition,
     house_name: HouseName,
     planets_config: &[PlanetConfig],
+    config: &HoroscopeConfig,
     ephe_path: &str,
 ) -> Result<ReturnHoroscop, Error> {
     // 计算本命星盘月亮黄道经度
@@ -139,12 +149,14 @@ pub fn lunar_return(
     let xx = swe_calc_ut(native_date.jd_ut1, Body::SeMoon, &[])
         .map_err(|e| Error::Function(format!("计算本命星盘月亮黄道经度错误:{e}")))?;
     swe_close();
-    let native_moon_long = xx[0];
+    // 所选黄道的经度
+    let native_moon_long = xx[0] - config.zodiac.ayanamsa(native_date.jd_ut1, ephe_path);
 
     // 计算推运时刻黄道经度
+    swe_set_ephe_path(ephe_path);
     let xx = swe_calc_ut(process_date.jd_ut1, Body::SeMoon, &[])
         .map_err(|e| Error::Function(format!("计算推运时刻月亮黄道经度错误:{e}")))?;
-    let process_moon_long = xx[0];
+    let process_moon_long = xx[0] - config.zodiac.ayanamsa(process_date.jd_ut1, ephe_path);
 
     // 计算迭代初值
     // ‌月亮每天在天空中移动约13.18度‌（来自百度AI）
@@ -160,7 +172,8 @@ pub fn lunar_return(
 
         swe_close();
 
-        Ok(mod180(xx[0] - native_moon_long))
+        let moon_long = xx[0] - config.zodiac.ayanamsa(t0.jd_ut1, ephe_path);
+        Ok(mod180(moon_long - native_moon_long))
     })?;
 
     let lunar_return_date = HoroDateTime::from_jd_ut1_zone(return_jd, process_date.tz)?;
@@ -170,6 +183,7 @@ pub fn lunar_return(
         geo.clone(),
         house_name.clone(),
         planets_config,
+        config,
         ephe_path,
     )?;
     Ok(ReturnHoroscop {
@@ -185,6 +199,7 @@ pub fn lunar_return(
         ic: horo.ic,
         // 福点
         part_of_fortune: horo.part_of_fortune,
+        lots: horo.lots,
         planets: horo.planets,
         aspects: horo.aspects,
         antiscoins: horo.antiscoins,
@@ -4,7 +4,10 @@ use geo_position::GeoPosition;
 use horo_date_time::HoroDateTime;
 
 use crate::{
-    config::PlanetConfig,
+    Ayanamsa, Horoscope,
+    PlanetName::Sun,
+    Zodiac,
+    config::{HoroscopeConfig, PlanetConfig},
     house::HouseName,
     return_horoscop::{lunar_return, solar_return},
 };
@@ -37,6 +40,7 @@ fn test_solar_return() {
         geo,
         house_name.clone(),
         &planet_configs,
+        &HoroscopeConfig::default(),
         &ephe_path,
     );
 
@@ -95,6 +99,7 @@ fn test_lunar_return() {
         geo.clone(),
         house_name.clone(),
         &planet_configs,
+        &HoroscopeConfig::default(),
         &ephe_path,
     );
 
@@ -124,3 +129,64 @@ fn test_lunar_return() {
     assert!(!lunar_return_horo.planets.is_empty(), "应该有行星数据");
     assert!(!lunar_return_horo.aspects.is_empty(), "应该有相位数据");
 }
+
+// 恒星黄道的太阳返照
+#[test]
+fn test_sidereal_solar_return() {
+    dotenvy::dotenv().ok();
+    let ephe_path = env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let native_date = HoroDateTime::new(1990, 6, 15, 10, 30, 0, 8.0).unwrap();
+    let process_date = HoroDateTime::new(2023, 12, 26, 12, 0, 0, 7.0).unwrap();
+    let geo = GeoPosition::new(116.4, 39.9).unwrap();
+    let planet_configs = PlanetConfig::default_all_configs();
+    let config = HoroscopeConfig {
+        zodiac: Zodiac::Sidereal(Ayanamsa::Lahiri),
+        ..Default::default()
+    };
+
+    let native = Horoscope::new(
+        native_date.clone(),
+        geo,
+        HouseName::Alcabitus,
+        &planet_configs,
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+    let native_sun = native.planets.iter().find(|p| p.name == Sun).unwrap();
+
+    let sidereal_return = solar_return(
+        native_date.clone(),
+        process_date.clone(),
+        geo,
+        HouseName::Alcabitus,
+        &planet_configs,
+        &config,
+        &ephe_path,
+    )
+    .unwrap();
+    let tropical_return = solar_return(
+        native_date,
+        process_date,
+        geo,
+        HouseName::Alcabitus,
+        &planet_configs,
+        &HoroscopeConfig::default(),
+        &ephe_path,
+    )
+    .unwrap();
+
+    // 返照盘的恒星黄道太阳位置与本命盘相同
+    let return_sun = sidereal_return
+        .planets
+        .iter()
+        .find(|p| p.name == Sun)
+        .unwrap();
+    assert!((return_sun.long - native_sun.long).abs() < 1e-3);
+
+    // 33年的岁差约0.46度，恒星黄道返照时间比回归黄道返照时间晚约11小时
+    let d = sidereal_return.return_date.jd_utc - tropical_return.return_date.jd_utc;
+    assert!(d > 0.4 && d < 0.55, "返照时间相差{d}天");
+}
@@ -0,0 +1,200 @@
+use geo_position::GeoPosition;
+use horo_date_time::HoroDateTime;
+use swe::{CalcFlag, swe_azalt, swe_degnorm};
+
+use crate::{Planet, PlanetName};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 区分：昼、夜
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Sect {
+    /// 昼
+    Diurnal,
+    /// 夜
+    Nocturnal,
+}
+
+/// 行星的区分状态
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct PlanetSect {
+    /// 行星
+    pub name: PlanetName,
+    /// 行星所属的区分：太阳、木星、土星属昼，月亮、金星、火星属夜，水星东出属昼、西入属夜
+    pub sect: Sect,
+    /// 行星的区分与星盘的区分相同
+    pub in_sect: bool,
+    /// 在地平线上
+    pub above_horizon: bool,
+    /// 在阳性星座（火象、风象星座）
+    pub masculine_sign: bool,
+    /// Halb：昼行星白天在地平线上、夜间在地平线下，夜行星夜间在地平线上、白天在地平线下
+    pub halb: bool,
+    /// Hayz：昼行星在白天盘的地平线上、落阳性星座，夜行星在夜间盘的地平线上、落阴性星座
+    pub hayz: bool,
+}
+
+/// 星盘的区分
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SectAnalysis {
+    /// 星盘的区分，白天盘为昼，夜间盘为夜
+    pub sect: Sect,
+    /// 区分内的吉星：白天盘为木星，夜间盘为金星
+    pub benefic_of_sect: PlanetName,
+    /// 区分内的凶星：白天盘为土星，夜间盘为火星
+    pub malefic_of_sect: PlanetName,
+    /// 区分外的吉星：白天盘为金星，夜间盘为木星
+    pub benefic_contrary_to_sect: PlanetName,
+    /// 区分外的凶星：白天盘为火星，夜间盘为土星
+    pub malefic_contrary_to_sect: PlanetName,
+    /// 七颗古典行星的区分状态
+    pub planets: Vec<PlanetSect>,
+}
+
+impl SectAnalysis {
+    /// 计算星盘的区分
+    /// * planets: 星盘中的行星，只计算七颗古典行星
+    /// * ayanamsa: 岁差，回归黄道为0
+    pub(crate) fn new(
+        date: &HoroDateTime,
+        geo: &GeoPosition,
+        is_diurnal: bool,
+        planets: &[Planet],
+        ayanamsa: f64,
+    ) -> Self {
+        let sect = if is_diurnal {
+            Sect::Diurnal
+        } else {
+            Sect::Nocturnal
+        };
+        let sun_long = planets
+            .iter()
+            .find(|p| p.name == PlanetName::Sun)
+            .map(|p| p.long);
+
+        let planets = planets
+            .iter()
+            .filter_map(|planet| {
+                let planet_sect = planet_sect(planet, sun_long?)?;
+                // 以海平面高度为准，与星盘昼夜的算法相同
+                let azalt = swe_azalt(
+                    date.jd_ut1,
+                    CalcFlag::ECL2HOR,
+                    &[geo.long, geo.lat, 0.0],
+                    0.0,
+                    0.0,
+                    &[swe_degnorm(planet.long + ayanamsa), planet.lat, 0.0],
+                );
+                let above_horizon = azalt[1] >= 0.0;
+                let masculine_sign = ((planet.long / 30.0) as u8).is_multiple_of(2);
+
+                let halb = match planet_sect {
+                    Sect::Diurnal => above_horizon == is_diurnal,
+                    Sect::Nocturnal => above_horizon != is_diurnal,
+                };
+                // Hayz：行星在区分内、Halb，且星座阴阳与行星的区分相合
+                let hayz = planet_sect == sect
+                    && halb
+                    && (masculine_sign == (planet_sect == Sect::Diurnal));
+
+                Some(PlanetSect {
+                    name: planet.name,
+                    sect: planet_sect,
+                    in_sect: planet_sect == sect,
+                    above_horizon,
+                    masculine_sign,
+                    halb,
+                    hayz,
+                })
+            })
+            .collect();
+
+        let (benefic_of_sect, malefic_of_sect, benefic_contrary_to_sect, malefic_contrary_to_sect) =
+            match sect {
+                Sect::Diurnal => (
+                    PlanetName::Jupiter,
+                    PlanetName::Saturn,
+                    PlanetName::Venus,
+                    PlanetName::Mars,
+                ),
+                Sect::Nocturnal => (
+                    PlanetName::Venus,
+                    PlanetName::Mars,
+                    PlanetName::Jupiter,
+                    PlanetName::Saturn,
+                ),
+            };
+
+        Self {
+            sect,
+            benefic_of_sect,
+            malefic_of_sect,
+            benefic_contrary_to_sect,
+            malefic_contrary_to_sect,
+            planets,
+        }
+    }
+}
+
+// 行星所属的区分，水星东出（黄经小于太阳）属昼，西入属夜，非古典行星没有区分
+fn planet_sect(planet: &Planet, sun_long: f64) -> Option<Sect> {
+    match planet.name {
+        PlanetName::Sun | PlanetName::Jupiter | PlanetName::Saturn => Some(Sect::Diurnal),
+        PlanetName::Moon | PlanetName::Venus | PlanetName::Mars => Some(Sect::Nocturnal),
+        PlanetName::Mercury => {
+            if swe_degnorm(sun_long - planet.long) < 180.0 {
+                Some(Sect::Diurnal)
+            } else {
+                Some(Sect::Nocturnal)
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PlanetName, planet::tests::planet};
+
+    use super::{Sect, planet_sect};
+
+    #[test]
+    fn test_planet_sect() {
+        let sun_long = 100.0;
+        for (name, sect) in [
+            (PlanetName::Sun, Sect::Diurnal),
+            (PlanetName::Jupiter, Sect::Diurnal),
+            (PlanetName::Saturn, Sect::Diurnal),
+            (PlanetName::Moon, Sect::Nocturnal),
+            (PlanetName::Venus, Sect::Nocturnal),
+            (PlanetName::Mars, Sect::Nocturnal),
+        ] {
+            assert_eq!(Some(sect), planet_sect(&planet(name, 0.0), sun_long));
+        }
+
+        // 水星东出属昼，西入属夜
+        assert_eq!(
+            Some(Sect::Diurnal),
+            planet_sect(&planet(PlanetName::Mercury, 90.0), sun_long)
+        );
+        assert_eq!(
+            Some(Sect::Nocturnal),
+            planet_sect(&planet(PlanetName::Mercury, 110.0), sun_long)
+        );
+
+        assert_eq!(
+            None,
+            planet_sect(&planet(PlanetName::NorthNode, 0.0), sun_long)
+        );
+    }
+}
@@ -0,0 +1,468 @@
+use horo_date_time::HoroDateTime;
+use swe::{Body, swe_calc_ut, swe_close, swe_cotrans, swe_degnorm, swe_set_ephe_path};
+
+use crate::{
+    Aspect, AspectConfig, AspectType, Error, Horoscope, Planet, PlanetName,
+    progression::{NAIBOD, TROPICAL_YEAR},
+    utils::{mod180, newton_iteration},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+// 小于此值的弧视为0，单位：度
+const MIN_ARC: f64 = 1e-6;
+
+/// 太阳弧推运的弧
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum SolarArcKey {
+    /// 太阳弧：次限推运太阳走过的黄经
+    #[default]
+    SolarArc,
+    /// 象征推运：每年1度
+    OneDegree,
+}
+
+/// 太阳弧推运中，推运星体与本命星体形成正相位
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SolarArcDirection {
+    /// 推运星体
+    pub directed: PlanetName,
+    /// 本命星体
+    pub natal: PlanetName,
+    /// 相位类型
+    pub aspect_type: AspectType,
+    /// 形成正相位时的弧，单位：度
+    pub arc: f64,
+    /// 形成正相位的时间
+    pub date: HoroDateTime,
+}
+
+/// 太阳弧推运：本命盘的行星、四轴、福点、阿拉伯点都加上同一段弧
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct SolarArc {
+    /// 出生时间
+    pub native_date: HoroDateTime,
+    /// 推运时间
+    pub process_date: HoroDateTime,
+    /// 推运的弧
+    pub key: SolarArcKey,
+    /// 推运时间的弧，单位：度
+    pub arc: f64,
+    /// 推运时间的行星、四轴、福点、阿拉伯点
+    pub directed: Vec<Planet>,
+    /// 推运时间推运星体与本命星体的相位，p0为本命星体，p1为推运星体
+    pub aspects: Vec<Aspect>,
+    /// 查询期间推运星体与本命星体形成的正相位，按时间排序
+    pub directions: Vec<SolarArcDirection>,
+}
+
+impl SolarArc {
+    /// 太阳弧推运
+    /// * natal: 本命盘
+    /// * process_date: 推运时间，不早于出生时间
+    /// * start_date、end_date: 查询正相位的时间范围，不早于出生时间
+    /// * key: 推运的弧
+    /// * config: 相位配置
+    pub fn new(
+        natal: &Horoscope,
+        process_date: HoroDateTime,
+        start_date: HoroDateTime,
+        end_date: HoroDateTime,
+        key: SolarArcKey,
+        config: &AspectConfig,
+        ephe_path: &str,
+    ) -> Result<Self, Error> {
+        let native_date = natal.date;
+        if process_date.jd_utc < native_date.jd_utc || start_date.jd_utc < native_date.jd_utc {
+            return Err(Error::InvalidProcessDateTime(
+                "推运时间不能早于出生时间".to_string(),
+            ));
+        }
+        if end_date.jd_utc <= start_date.jd_utc {
+            return Err(Error::InvalidProcessDateTime(
+                "查询的结束时间应晚于开始时间".to_string(),
+            ));
+        }
+
+        let natal_sun_long = natal
+            .planets
+            .iter()
+            .find(|p| p.name == PlanetName::Sun)
+            .map(|p| p.long)
+            .unwrap_or_default();
+        let arc_of = |jd: f64| -> Result<f64, Error> {
+            let years = (jd - native_date.jd_ut1) / TROPICAL_YEAR;
+            match key {
+                SolarArcKey::OneDegree => Ok(years),
+                SolarArcKey::SolarArc => {
+                    let progressed_jd = native_date.jd_ut1 + years;
+                    swe_set_ephe_path(ephe_path);
+                    let xx = swe_calc_ut(progressed_jd, Body::SeSun, &[])
+                        .map_err(|e| Error::Function(format!("计算推运太阳黄道经度错误:{e}")))?;
+                    swe_close();
+                    let sun_long = xx[0] - natal.zodiac.ayanamsa(progressed_jd, ephe_path);
+                    Ok(swe_degnorm(sun_long - natal_sun_long))
+                }
+            }
+        };
+
+        let points = natal_points(natal);
+
+        let arc = arc_of(process_date.jd_ut1)?;
+        let directed: Vec<_> = points
+            .iter()
+            .map(|p| {
+                let mut planet = **p;
+                planet.long = swe_degnorm(p.long + arc);
+                let equator = swe_cotrans(
+                    swe_degnorm(planet.long + natal.ayanamsa),
+                    p.lat,
+                    1.0,
+                    -natal.eps,
+                );
+                planet.ra = equator[0];
+                planet.dec = equator[1];
+                // 推运星体约每年移动1度
+                planet.speed = 1.0 / TROPICAL_YEAR;
+                planet.dignities = None;
+                planet.house = None;
+                planet.mundane_position = None;
+                planet.condition = None;
+                planet
+            })
+            .collect();
+
+        let mut aspects = vec![];
+        for p0 in &points {
+            for p1 in &directed {
+                if let Some(aspect) = p0.has_aspect_with_config(p1, true, config) {
+                    aspects.push(aspect)
+                }
+            }
+        }
+
+        let arc_start = arc_of(start_date.jd_ut1)?;
+        let arc_end = arc_of(end_date.jd_ut1)?;
+        let mut directions = vec![];
+        for p in &points {
+            for q in &points {
+                for setting in &config.aspects {
+                    for arc in direction_arcs(p, q, setting.aspect_type) {
+                        if arc < arc_start || arc > arc_end {
+                            continue;
+                        }
+
+                        let jd = match key {
+                            SolarArcKey::OneDegree => native_date.jd_ut1 + arc * TROPICAL_YEAR,
+                            SolarArcKey::SolarArc => {
+                                let init = native_date.jd_ut1 + arc / NAIBOD * TROPICAL_YEAR;
+                                newton_iteration(init, |jd| Ok(mod180(arc_of(jd)? - arc)))?
+                            }
+                        };
+                        directions.push(SolarArcDirection {
+                            directed: p.name,
+                            natal: q.name,
+                            aspect_type: setting.aspect_type,
+                            arc,
+                            date: HoroDateTime::from_jd_ut1_zone(jd, native_date.tz)?,
+                        });
+                    }
+                }
+            }
+        }
+        directions.sort_by(|a, b| a.date.jd_utc.total_cmp(&b.date.jd_utc));
+
+        Ok(Self {
+            native_date,
+            process_date,
+            key,
+            arc,
+            directed,
+            aspects,
+            directions,
+        })
+    }
+}
+
+// 本命盘的行星、四轴、福点、阿拉伯点
+fn natal_points(natal: &Horoscope) -> Vec<&Planet> {
+    natal
+        .planets
+        .iter()
+        .chain([&natal.asc, &natal.mc, &natal.dsc, &natal.ic])
+        .chain([&natal.part_of_fortune])
+        .chain(natal.lots.iter().map(|lot| &lot.planet))
+        .collect()
+}
+
+// 推运星体p与本命星体q形成正相位所需的弧，不包括出生时即已形成的相位
+fn direction_arcs(p: &Planet, q: &Planet, aspect_type: AspectType) -> Vec<f64> {
+    // 推运星体与其本命位置的合相只在出生时
+    if p.name == q.name && aspect_type == AspectType::Conjunction {
+        return vec![];
+    }
+
+    // ASC与DSC、MC与IC、北交与南交永远对冲，不看相位
+    let fixed_pairs = [
+        [PlanetName::ASC, PlanetName::DSC],
+        [PlanetName::MC, PlanetName::IC],
+        [PlanetName::NorthNode, PlanetName::SouthNode],
+    ];
+    if fixed_pairs
+        .iter()
+        .any(|pair| p.name != q.name && pair.contains(&p.name) && pair.contains(&q.name))
+    {
+        return vec![];
+    }
+
+    // 弧接近0或360度的，是本命盘中已是正相位的相位
+    arcs_to_aspect(p.long, q.long, aspect_type)
+        .into_iter()
+        .filter(|arc| *arc > MIN_ARC && *arc < 360.0 - MIN_ARC)
+        .collect()
+}
+
+// 黄经为directed_long的推运星体与黄经为natal_long的本命星体形成正相位所需的弧，[0, 360)
+fn arcs_to_aspect(directed_long: f64, natal_long: f64, aspect_type: AspectType) -> Vec<f64> {
+    let angle = f64::from(aspect_type.angle());
+    let mut arcs = vec![swe_degnorm(natal_long + angle - directed_long)];
+    if angle != 0.0 && angle != 180.0 {
+        arcs.push(swe_degnorm(natal_long - angle - directed_long));
+    }
+    arcs
+}
+
+#[cfg(test)]
+mod tests {
+    use parameterized::parameterized;
+
+    use horo_date_time::HoroDateTime;
+    use swe::swe_degnorm;
+
+    use crate::{
+        AspectConfig,
+        AspectType::{self, *},
+        Error, HoroscopeConfig, HouseName, McProgression, Planet, PlanetConfig,
+        PlanetName::{self, *},
+        horoscope::tests::{get_ephe_path, natal_chart, native_geo},
+        planet::tests::planet,
+        secondary_progression,
+    };
+
+    use super::{SolarArc, SolarArcKey, arcs_to_aspect, direction_arcs};
+
+    #[parameterized(case = {
+        (10.0, 40.0, Conjunction, vec![30.0]),
+        (40.0, 10.0, Conjunction, vec![330.0]),
+        (10.0, 40.0, Opposition, vec![210.0]),
+        (10.0, 40.0, Square, vec![120.0, 300.0]),
+        (350.0, 10.0, Sextile, vec![80.0, 320.0]),
+    })]
+    fn test_arcs_to_aspect(case: (f64, f64, AspectType, Vec<f64>)) {
+        let arcs = arcs_to_aspect(case.0, case.1, case.2);
+        assert_eq!(arcs.len(), case.3.len());
+        for (arc, expected) in arcs.iter().zip(&case.3) {
+            assert!((arc - expected).abs() < 1e-9, "{arc} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_direction_arcs() {
+        // 出生时已形成的对冲：ASC与DSC、MC与IC、北交与南交，不看相位
+        for (p0, p1) in [
+            (PlanetName::ASC, PlanetName::DSC),
+            (PlanetName::DSC, PlanetName::ASC),
+            (PlanetName::MC, PlanetName::IC),
+            (PlanetName::NorthNode, PlanetName::SouthNode),
+        ] {
+            for aspect_type in [Conjunction, Opposition, Square] {
+                let arcs = direction_arcs(&planet(p0, 10.0), &planet(p1, 190.0), aspect_type);
+                assert!(arcs.is_empty(), "{p0:?} {p1:?} {aspect_type:?}");
+            }
+        }
+
+        // 推运星体与其本命位置只有合相不计算
+        let sun = planet(PlanetName::Sun, 10.0);
+        assert!(direction_arcs(&sun, &sun, Conjunction).is_empty());
+        assert_eq!(vec![180.0], direction_arcs(&sun, &sun, Opposition));
+
+        // 本命盘中已是正相位的，弧为0，不计算；另一侧的相位仍计算
+        let moon = planet(PlanetName::Moon, 100.0);
+        assert_eq!(vec![180.0], direction_arcs(&sun, &moon, Square));
+        assert!(direction_arcs(&sun, &planet(PlanetName::Moon, 10.0), Conjunction).is_empty());
+        assert!(
+            direction_arcs(&planet(PlanetName::Mars, 100.0 + 1e-9), &moon, Conjunction).is_empty()
+        );
+
+        // ASC与MC之间仍计算
+        let arcs = direction_arcs(
+            &planet(PlanetName::ASC, 10.0),
+            &planet(PlanetName::MC, 290.0),
+            Conjunction,
+        );
+        assert_eq!(vec![280.0], arcs);
+    }
+
+    #[test]
+    fn test_solar_arc() {
+        let ephe_path = get_ephe_path();
+
+        let native_date = HoroDateTime::new(2000, 3, 20, 10, 30, 20, 8.0).unwrap();
+        let process_date = HoroDateTime::new(2030, 3, 20, 10, 30, 20, 8.0).unwrap();
+        let start_date = HoroDateTime::new(2020, 1, 1, 0, 0, 0, 8.0).unwrap();
+        let end_date = HoroDateTime::new(2040, 1, 1, 0, 0, 0, 8.0).unwrap();
+        let geo = native_geo();
+
+        let natal = natal_chart(native_date, &HoroscopeConfig::default());
+        let config = AspectConfig::default();
+
+        assert!(matches!(
+            SolarArc::new(
+                &natal,
+                process_date,
+                end_date,
+                start_date,
+                SolarArcKey::SolarArc,
+                &config,
+                &ephe_path
+            ),
+            Err(Error::InvalidProcessDateTime(_))
+        ));
+
+        let years = (process_date.jd_ut1 - native_date.jd_ut1) / 365.2422;
+
+        // 每年1度
+        let one_degree = SolarArc::new(
+            &natal,
+            process_date,
+            start_date,
+            end_date,
+            SolarArcKey::OneDegree,
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+        assert!((one_degree.arc - years).abs() < 1e-9);
+
+        // 太阳弧即次限推运太阳走过的黄经
+        let solar_arc = SolarArc::new(
+            &natal,
+            process_date,
+            start_date,
+            end_date,
+            SolarArcKey::SolarArc,
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+        let progression = secondary_progression(
+            native_date,
+            process_date,
+            geo,
+            HouseName::Alcabitus,
+            &PlanetConfig::default_configs(false),
+            McProgression::SolarArc,
+            &HoroscopeConfig::default(),
+            &ephe_path,
+        )
+        .unwrap();
+        let sun_long = |planets: &[Planet]| planets.iter().find(|p| p.name == Sun).unwrap().long;
+        assert!(
+            (swe_degnorm(sun_long(&progression.horoscope.planets) - sun_long(&natal.planets))
+                - solar_arc.arc)
+                .abs()
+                < 1e-6
+        );
+        assert!(
+            (sun_long(&solar_arc.directed) - sun_long(&progression.horoscope.planets)).abs() < 1e-6
+        );
+
+        for chart in [&one_degree, &solar_arc] {
+            assert_eq!(
+                natal.planets.len() + 5 + natal.lots.len(),
+                chart.directed.len()
+            );
+            for (p, d) in natal.planets.iter().zip(&chart.directed) {
+                assert!((swe_degnorm(d.long - p.long) - chart.arc).abs() < 1e-9);
+            }
+
+            // 正相位时，推运星体与本命星体的距离为相位的度数
+            let long_of = |name: PlanetName| {
+                natal
+                    .planets
+                    .iter()
+                    .chain([&natal.asc, &natal.mc, &natal.dsc, &natal.ic])
+                    .chain([&natal.part_of_fortune])
+                    .find(|p| p.name == name)
+                    .unwrap()
+                    .long
+            };
+            assert!(!chart.directions.is_empty());
+            for (i, direction) in chart.directions.iter().enumerate() {
+                if i > 0 {
+                    assert!(chart.directions[i - 1].date.jd_utc <= direction.date.jd_utc);
+                }
+                assert!(direction.date.jd_utc >= start_date.jd_utc);
+                assert!(direction.date.jd_utc <= end_date.jd_utc);
+
+                let d = swe_degnorm(
+                    long_of(direction.directed) + direction.arc - long_of(direction.natal),
+                );
+                let d = d.min(360.0 - d);
+                assert!(
+                    (d - f64::from(direction.aspect_type.angle())).abs() < 1e-6,
+                    "{direction:?}"
+                );
+            }
+        }
+
+        // 太阳弧推运的正相位时间，太阳弧等于相位所需的弧
+        for direction in solar_arc.directions.iter().take(5) {
+            let chart = SolarArc::new(
+                &natal,
+                direction.date,
+                start_date,
+                end_date,
+                SolarArcKey::SolarArc,
+                &config,
+                &ephe_path,
+            )
+            .unwrap();
+            assert!((chart.arc - direction.arc).abs() < 1e-5, "{direction:?}");
+        }
+
+        // 从出生时开始查询，不应有出生时即已形成的正相位
+        let chart = SolarArc::new(
+            &natal,
+            process_date,
+            native_date,
+            native_date.plus_days(365.2422 * 2.0).unwrap(),
+            SolarArcKey::SolarArc,
+            &config,
+            &ephe_path,
+        )
+        .unwrap();
+        let fixed_pairs = [[ASC, DSC], [MC, IC], [NorthNode, SouthNode]];
+        for direction in &chart.directions {
+            assert!(direction.arc > 1e-6, "{direction:?}");
+            assert!(direction.date.jd_utc > native_date.jd_utc, "{direction:?}");
+            assert!(
+                !fixed_pairs
+                    .iter()
+                    .any(|pair| pair.contains(&direction.directed)
+                        && pair.contains(&direction.natal)),
+                "{direction:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,537 @@
+use horo_date_time::HoroDateTime;
+use swe::{Body, swe_calc_ut, swe_close, swe_set_ephe_path};
+
+use crate::{
+    AspectType, Error, Horoscope, NodeType, PlanetName,
+    utils::{mod180, newton_iteration},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 行运的配置
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct TransitConfig {
+    /// 行运星体，只计算太阳至冥王星及北交点，其余星体忽略；北交点按本命盘的月交点计算方法取平均交点或真交点
+    pub planets: Vec<PlanetName>,
+    /// 计算的相位
+    pub aspects: Vec<AspectType>,
+    /// 容许度，单位：度
+    pub orb: f64,
+}
+
+/// 默认：除月亮外的太阳至冥王星，托勒密相位，容许度1度
+impl Default for TransitConfig {
+    fn default() -> Self {
+        Self {
+            planets: vec![
+                PlanetName::Sun,
+                PlanetName::Mercury,
+                PlanetName::Venus,
+                PlanetName::Mars,
+                PlanetName::Jupiter,
+                PlanetName::Saturn,
+                PlanetName::Uranus,
+                PlanetName::Neptune,
+                PlanetName::Pluto,
+            ],
+            aspects: AspectType::ptolemaic().to_vec(),
+            orb: 1.0,
+        }
+    }
+}
+
+/// 本命盘中被行运星体触及的点
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum TransitPoint {
+    /// 行星、四轴、福点、阿拉伯点
+    Planet(PlanetName),
+    /// 宫头，1-12，与四轴重合的宫头不计
+    Cusp(u8),
+}
+
+/// 行运：行运星体与本命盘中的点在一次进入容许度至离开容许度期间形成的相位
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub struct Transit {
+    /// 行运星体，推运中为推运行星
+    pub transiting: PlanetName,
+    /// 本命盘中的点
+    pub natal: TransitPoint,
+    /// 相位类型
+    pub aspect_type: AspectType,
+    /// 形成正相位的时间，行运星体逆行时可能有三次
+    pub exact_dates: Vec<HoroDateTime>,
+    /// 进入容许度的时间，查询开始时已在容许度内则没有此值
+    pub entry_date: Option<HoroDateTime>,
+    /// 离开容许度的时间，查询结束时仍在容许度内则没有此值
+    pub exit_date: Option<HoroDateTime>,
+}
+
+// 本命盘中一个点在查询期间的一次行运，时间为儒略日（ut1）
+struct Pass {
+    entry: Option<f64>,
+    exacts: Vec<f64>,
+}
+
+/// 计算查询期间行运星体与本命盘中行星、四轴、宫头、福点及阿拉伯点的全部相位
+/// * natal: 本命盘
+/// * start_date: 查询的开始时间
+/// * end_date: 查询的结束时间
+/// * config: 行运的配置
+/// * ephe_path: 星历表的路径
+///
+/// 返回值：按第一次正相位的时间排序，时间的时区与开始时间相同
+pub fn transits(
+    natal: &Horoscope,
+    start_date: HoroDateTime,
+    end_date: HoroDateTime,
+    config: &TransitConfig,
+    ephe_path: &str,
+) -> Result<Vec<Transit>, Error> {
+    if end_date.jd_utc <= start_date.jd_utc {
+        return Err(Error::InvalidProcessDateTime(
+            "行运的结束时间应晚于开始时间".to_string(),
+        ));
+    }
+
+    let jd_start = start_date.jd_ut1;
+    let jd_end = end_date.jd_ut1;
+
+    // 岁差在查询期间近似线性变化
+    let ayanamsa_start = natal.zodiac.ayanamsa(jd_start, ephe_path);
+    let ayanamsa_end = natal.zodiac.ayanamsa(jd_end, ephe_path);
+    let ayanamsa = |jd: f64| {
+        ayanamsa_start + (ayanamsa_end - ayanamsa_start) * (jd - jd_start) / (jd_end - jd_start)
+    };
+
+    let points = natal_points(natal);
+    let angles: Vec<_> = config
+        .aspects
+        .iter()
+        .flat_map(|&aspect_type| {
+            let angle = f64::from(aspect_type.angle());
+            if angle == 0.0 || angle == 180.0 {
+                vec![(aspect_type, angle)]
+            } else {
+                vec![(aspect_type, angle), (aspect_type, -angle)]
+            }
+        })
+        .collect();
+
+    swe_set_ephe_path(ephe_path);
+    let mut passes = vec![];
+    for &transiting in &config.planets {
+        let Some((body, max_speed)) = transiting_body(transiting, natal.node_type) else {
+            continue;
+        };
+
+        let long = |jd: f64| -> Result<f64, Error> {
+            let xx = swe_calc_ut(jd, body, &[])
+                .map_err(|e| Error::Function(format!("计算行运星体错误:{e}")))?;
+            Ok(xx[0] - ayanamsa(jd))
+        };
+
+        // 每步移动不超过容许度的一半，不会漏掉进入、离开容许度
+        let step = (config.orb.max(0.1) / (2.0 * max_speed)).min(1.0);
+        let n = ((jd_end - jd_start) / step).ceil() as usize;
+        let samples = (0..=n)
+            .map(|i| {
+                let jd = (jd_start + step * i as f64).min(jd_end);
+                long(jd).map(|long| (jd, long))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for &(natal_point, natal_long) in &points {
+            for &(aspect_type, angle) in &angles {
+                let f =
+                    |jd: f64| -> Result<f64, Error> { Ok(mod180(long(jd)? - natal_long - angle)) };
+                let values: Vec<_> = samples
+                    .iter()
+                    .map(|&(jd, long)| (jd, mod180(long - natal_long - angle)))
+                    .collect();
+
+                for pass in find_passes(&values, config.orb, f)? {
+                    passes.push((transiting, natal_point, aspect_type, pass));
+                }
+            }
+        }
+    }
+    swe_close();
+
+    let to_date = |jd: f64| HoroDateTime::from_jd_ut1_zone(jd, start_date.tz);
+    let mut transits = passes
+        .into_iter()
+        .map(|(transiting, natal, aspect_type, (pass, exit))| {
+            Ok(Transit {
+                transiting,
+                natal,
+                aspect_type,
+                exact_dates: pass
+                    .exacts
+                    .iter()
+                    .map(|&jd| to_date(jd))
+                    .collect::<Result<_, _>>()?,
+                entry_date: pass.entry.map(to_date).transpose()?,
+                exit_date: exit.map(to_date).transpose()?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    transits.sort_by(|a, b| a.exact_dates[0].jd_utc.total_cmp(&b.exact_dates[0].jd_utc));
+
+    Ok(transits)
+}
+
+// 行运星体及其最大移动速度，单位：度/天，月交点与本命盘相同
+fn transiting_body(name: PlanetName, node_type: NodeType) -> Option<(Body, f64)> {
+    match name {
+        PlanetName::Sun => Some((Body::SeSun, 1.02)),
+        PlanetName::Moon => Some((Body::SeMoon, 15.4)),
+        PlanetName::Mercury => Some((Body::SeMercury, 2.2)),
+        PlanetName::Venus => Some((Body::SeVenus, 1.27)),
+        PlanetName::Mars => Some((Body::SeMars, 0.8)),
+        PlanetName::Jupiter => Some((Body::SeJupiter, 0.25)),
+        PlanetName::Saturn => Some((Body::SeSaturn, 0.14)),
+        PlanetName::Uranus => Some((Body::SeUranus, 0.07)),
+        PlanetName::Neptune => Some((Body::SeNeptune, 0.04)),
+        PlanetName::Pluto => Some((Body::SePluto, 0.04)),
+        PlanetName::NorthNode => match node_type {
+            NodeType::Mean => Some((Body::SeMeanNode, 0.06)),
+            // 真实交点的速度有波动，偶尔顺行
+            NodeType::True => Some((Body::SeTrueNode, 0.3)),
+        },
+        _ => None,
+    }
+}
+
+// 本命盘中的行星、四轴、福点、阿拉伯点及宫头，与四轴重合的宫头不计
+fn natal_points(natal: &Horoscope) -> Vec<(TransitPoint, f64)> {
+    let angles = [&natal.asc, &natal.mc, &natal.dsc, &natal.ic];
+    let mut points: Vec<_> = natal
+        .planets
+        .iter()
+        .chain(angles)
+        .chain([&natal.part_of_fortune])
+        .chain(natal.lots.iter().map(|lot| &lot.planet))
+        .map(|p| (TransitPoint::Planet(p.name), p.long))
+        .collect();
+    points.extend(
+        natal
+            .cusps
+            .iter()
+            .enumerate()
+            .filter(|(_, cusp)| {
+                angles
+                    .iter()
+                    .all(|angle| mod180(angle.long - **cusp).abs() > 1e-9)
+            })
+            .map(|(i, &cusp)| (TransitPoint::Cusp(i as u8 + 1), cusp)),
+    );
+    points
+}
+
+// 由采样值找出每次进入容许度至离开容许度期间的正相位
+// * values: 采样时间及行运星体与相位点的距离，距离在(-180, 180]
+// * f: 给定时间行运星体与相位点的距离
+//
+// 返回值：有正相位的各次行运及其离开容许度的时间
+fn find_passes<F>(values: &[(f64, f64)], orb: f64, f: F) -> Result<Vec<(Pass, Option<f64>)>, Error>
+where
+    F: Fn(f64) -> Result<f64, Error>,
+{
+    let mut passes = vec![];
+    let Some(&(_, d0)) = values.first() else {
+        return Ok(passes);
+    };
+
+    let mut current = (d0.abs() <= orb).then_some(Pass {
+        entry: None,
+        exacts: vec![],
+    });
+    for window in values.windows(2) {
+        let (t0, d0) = window[0];
+        let (t1, d1) = window[1];
+        // 距离跨越±180度，远离容许度
+        if (d1 - d0).abs() > 180.0 {
+            continue;
+        }
+
+        // 采样间隔内距离单调变化，依次穿过-orb、0、orb中的若干个
+        let mut events = vec![];
+        for level in [-orb, 0.0, orb] {
+            if (d0 - level) * (d1 - level) < 0.0 || d1 == level {
+                events.push((solve(t0, d0, t1, d1, level, &f)?, level));
+            }
+        }
+        events.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (t, level) in events {
+            if level == 0.0 {
+                current
+                    .get_or_insert(Pass {
+                        entry: None,
+                        exacts: vec![],
+                    })
+                    .exacts
+                    .push(t);
+            } else if let Some(pass) = current.take() {
+                if !pass.exacts.is_empty() {
+                    passes.push((pass, Some(t)));
+                }
+            } else {
+                current = Some(Pass {
+                    entry: Some(t),
+                    exacts: vec![],
+                });
+            }
+        }
+    }
+
+    if let Some(pass) = current.filter(|pass| !pass.exacts.is_empty()) {
+        passes.push((pass, None));
+    }
+
+    Ok(passes)
+}
+
+// 求[t0, t1]内距离为level的时间，牛顿迭代失败或超出区间时用二分法
+fn solve<F>(t0: f64, d0: f64, t1: f64, d1: f64, level: f64, f: &F) -> Result<f64, Error>
+where
+    F: Fn(f64) -> Result<f64, Error>,
+{
+    let init = t0 + (t1 - t0) * (level - d0) / (d1 - d0);
+    if let Some(t) = newton_iteration(init, |t| Ok(f(t)? - level))
+        .ok()
+        .filter(|t| (t0..=t1).contains(t))
+    {
+        return Ok(t);
+    }
+
+    let (mut a, mut b) = (t0, t1);
+    let mut fa = d0 - level;
+    for _ in 0..60 {
+        let m = (a + b) / 2.0;
+        let fm = f(m)? - level;
+        if fa * fm <= 0.0 {
+            b = m;
+        } else {
+            a = m;
+            fa = fm;
+        }
+    }
+    Ok((a + b) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use swe::{Body, swe_calc_ut, swe_close, swe_degnorm, swe_set_ephe_path};
+
+    use crate::{
+        Error, HoroscopeConfig, NodeType,
+        PlanetName::{self, *},
+        horoscope::tests::{get_ephe_path, natal_chart},
+    };
+    use horo_date_time::HoroDateTime;
+
+    use super::{TransitConfig, TransitPoint, find_passes, transits};
+
+    // 以直线和折返模拟行运星体与相位点的距离
+    fn passes(d: fn(f64) -> f64, orb: f64) -> Vec<(Option<f64>, Vec<f64>, Option<f64>)> {
+        let values: Vec<_> = (0..=100)
+            .map(|i| {
+                let t = i as f64 * 0.1;
+                (t, d(t))
+            })
+            .collect();
+        find_passes(&values, orb, |t| Ok(d(t)))
+            .unwrap()
+            .into_iter()
+            .map(|(pass, exit)| (pass.entry, pass.exacts, exit))
+            .collect()
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!((expected - actual).abs() < 1e-6, "{expected} != {actual}");
+    }
+
+    #[test]
+    fn test_direct_pass() {
+        // 距离从-5度匀速增加到5度，t=5时正相位
+        let result = passes(|t| t - 5.0, 1.0);
+        assert_eq!(result.len(), 1);
+        let (entry, exacts, exit) = &result[0];
+        assert_close(4.0, entry.unwrap());
+        assert_eq!(exacts.len(), 1);
+        assert_close(5.0, exacts[0]);
+        assert_close(6.0, exit.unwrap());
+    }
+
+    #[test]
+    fn test_retrograde_passes() {
+        // 顺行越过、逆行退回、再顺行越过，t=3、5、7时三次正相位，期间一直在容许度内
+        let result = passes(|t| 0.05 * (t - 5.0) * (t - 3.0) * (t - 7.0), 1.0);
+        assert_eq!(result.len(), 1);
+        let (entry, exacts, exit) = &result[0];
+        assert!(entry.unwrap() < 3.0);
+        assert_eq!(exacts.len(), 3);
+        for (expected, actual) in [3.0, 5.0, 7.0].into_iter().zip(exacts) {
+            assert_close(expected, *actual);
+        }
+        assert!(exit.unwrap() > 7.0);
+    }
+
+    #[test]
+    fn test_no_exact() {
+        // 进入容许度但没有正相位
+        let result = passes(|t| 0.5 + (t - 5.0).powi(2), 1.0);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_exact_at_end() {
+        // 查询结束时仍在容许度内
+        let result = passes(|t| t - 9.5, 1.0);
+        assert_eq!(result.len(), 1);
+        let (entry, exacts, exit) = &result[0];
+        assert_close(8.5, entry.unwrap());
+        assert_close(9.5, exacts[0]);
+        assert!(exit.is_none());
+    }
+
+    #[test]
+    fn test_transits() {
+        let ephe_path = get_ephe_path();
+
+        let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+
+        let natal = natal_chart(t, &HoroscopeConfig::default());
+
+        let start_date = HoroDateTime::new(2022, 1, 1, 0, 0, 0, 8.0).unwrap();
+        let end_date = HoroDateTime::new(2022, 7, 1, 0, 0, 0, 8.0).unwrap();
+        let config = TransitConfig::default();
+
+        assert!(matches!(
+            transits(&natal, end_date, start_date, &config, &ephe_path),
+            Err(Error::InvalidProcessDateTime(_))
+        ));
+
+        let result = transits(&natal, start_date, end_date, &config, &ephe_path).unwrap();
+        assert!(!result.is_empty());
+
+        // 半年内行运太阳与本命太阳形成三分、对冲、刑等相位
+        assert!(
+            result
+                .iter()
+                .any(|transit| transit.transiting == Sun
+                    && transit.natal == TransitPoint::Planet(Sun))
+        );
+
+        let body_of = |name: PlanetName| match name {
+            Sun => Body::SeSun,
+            Mercury => Body::SeMercury,
+            Venus => Body::SeVenus,
+            Mars => Body::SeMars,
+            Jupiter => Body::SeJupiter,
+            Saturn => Body::SeSaturn,
+            Uranus => Body::SeUranus,
+            Neptune => Body::SeNeptune,
+            _ => Body::SePluto,
+        };
+        let natal_long = |point: TransitPoint| match point {
+            TransitPoint::Cusp(i) => natal.cusps[usize::from(i - 1)],
+            TransitPoint::Planet(name) => {
+                [&natal.asc, &natal.mc, &natal.dsc, &natal.ic]
+                    .into_iter()
+                    .chain(&natal.planets)
+                    .chain([&natal.part_of_fortune])
+                    .find(|p| p.name == name)
+                    .unwrap()
+                    .long
+            }
+        };
+
+        swe_set_ephe_path(&ephe_path);
+        for (i, transit) in result.iter().enumerate() {
+            assert!(!transit.exact_dates.is_empty());
+            if i > 0 {
+                assert!(result[i - 1].exact_dates[0].jd_utc <= transit.exact_dates[0].jd_utc);
+            }
+            if let Some(entry_date) = transit.entry_date {
+                assert!(entry_date.jd_utc < transit.exact_dates[0].jd_utc);
+            }
+            if let Some(exit_date) = transit.exit_date {
+                assert!(exit_date.jd_utc > transit.exact_dates.last().unwrap().jd_utc);
+            }
+
+            let angle = f64::from(transit.aspect_type.angle());
+            for date in &transit.exact_dates {
+                assert!(date.jd_utc >= start_date.jd_utc && date.jd_utc <= end_date.jd_utc);
+                let xx = swe_calc_ut(date.jd_ut1, body_of(transit.transiting), &[]).unwrap();
+                let d = swe_degnorm(xx[0] - natal_long(transit.natal));
+                let d = d.min(360.0 - d);
+                assert!(
+                    (d - angle).abs() < 1e-4,
+                    "{:?} {:?} {:?}: {d}",
+                    transit.transiting,
+                    transit.natal,
+                    transit.aspect_type
+                );
+            }
+        }
+        swe_close();
+    }
+
+    // 行运的月交点与本命盘相同
+    #[test]
+    fn test_transits_true_node() {
+        let ephe_path = get_ephe_path();
+
+        let t = HoroDateTime::new(2021, 9, 14, 10, 30, 20, 8.0).unwrap();
+        let config = HoroscopeConfig {
+            node_type: NodeType::True,
+            ..Default::default()
+        };
+        let natal = natal_chart(t, &config);
+        assert_eq!(NodeType::True, natal.node_type);
+
+        let start_date = HoroDateTime::new(2022, 1, 1, 0, 0, 0, 8.0).unwrap();
+        let end_date = HoroDateTime::new(2024, 1, 1, 0, 0, 0, 8.0).unwrap();
+        let config = TransitConfig {
+            planets: vec![NorthNode],
+            ..Default::default()
+        };
+        let result = transits(&natal, start_date, end_date, &config, &ephe_path).unwrap();
+        assert!(!result.is_empty());
+
+        swe_set_ephe_path(&ephe_path);
+        for transit in &result {
+            let TransitPoint::Planet(name) = transit.natal else {
+                continue;
+            };
+            let Some(natal_long) = natal
+                .planets
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| p.long)
+            else {
+                continue;
+            };
+            let angle = f64::from(transit.aspect_type.angle());
+            for date in &transit.exact_dates {
+                let xx = swe_calc_ut(date.jd_ut1, Body::SeTrueNode, &[]).unwrap();
+                let d = swe_degnorm(xx[0] - natal_long);
+                let d = d.min(360.0 - d);
+                assert!((d - angle).abs() < 1e-4, "{transit:?}: {d}");
+            }
+        }
+        swe_close();
+    }
+}
@@ -0,0 +1,119 @@
+use swe::{swe_close, swe_get_ayanamsa_ut, swe_set_ephe_path, swe_set_sid_mode};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
+/// 恒星黄道的岁差（Ayanamsa）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Ayanamsa {
+    FaganBradley,
+    Lahiri,
+    DeLuce,
+    Raman,
+    Ushashashi,
+    Krishnamurti,
+    DjwhalKhul,
+    Yukteshwar,
+    JnBhasin,
+    BabylonianHuber,
+    Aldebaran15Tau,
+    Hipparchos,
+    Sassanian,
+    GalacticCenter0Sag,
+    TrueCitra,
+    TrueRevati,
+    TruePushya,
+}
+
+impl Ayanamsa {
+    /// 瑞士星历表的SE_SIDM_*编号
+    fn sid_mode(&self) -> i32 {
+        match self {
+            Ayanamsa::FaganBradley => 0,
+            Ayanamsa::Lahiri => 1,
+            Ayanamsa::DeLuce => 2,
+            Ayanamsa::Raman => 3,
+            Ayanamsa::Ushashashi => 4,
+            Ayanamsa::Krishnamurti => 5,
+            Ayanamsa::DjwhalKhul => 6,
+            Ayanamsa::Yukteshwar => 7,
+            Ayanamsa::JnBhasin => 8,
+            Ayanamsa::BabylonianHuber => 12,
+            Ayanamsa::Aldebaran15Tau => 14,
+            Ayanamsa::Hipparchos => 15,
+            Ayanamsa::Sassanian => 16,
+            Ayanamsa::GalacticCenter0Sag => 17,
+            Ayanamsa::TrueCitra => 27,
+            Ayanamsa::TrueRevati => 28,
+            Ayanamsa::TruePushya => 29,
+        }
+    }
+}
+
+/// 黄道
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum Zodiac {
+    /// 回归黄道
+    #[default]
+    Tropical,
+    /// 恒星黄道
+    Sidereal(Ayanamsa),
+}
+
+impl Zodiac {
+    /// 计算jd_ut时刻的岁差，回归黄道为0
+    /// 恒星黄道经度 = 回归黄道经度 - 岁差
+    pub fn ayanamsa(&self, jd_ut: f64, ephe_path: &str) -> f64 {
+        match self {
+            Zodiac::Tropical => 0.0,
+            Zodiac::Sidereal(ayanamsa) => {
+                swe_set_ephe_path(ephe_path);
+                swe_set_sid_mode(ayanamsa.sid_mode(), 0.0, 0.0);
+                let ayanamsa = swe_get_ayanamsa_ut(jd_ut);
+                swe_close();
+                ayanamsa
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_tropical_ayanamsa() {
+        assert_eq!(0.0, Zodiac::Tropical.ayanamsa(2451545.0, ""));
+    }
+
+    #[test]
+    fn test_sidereal_ayanamsa() {
+        dotenvy::dotenv().ok();
+        let ephe_path = env::var("EPHE_PATH")
+            .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+        // J2000.0
+        let jd_ut = 2451545.0;
+        let lahiri = Zodiac::Sidereal(Ayanamsa::Lahiri).ayanamsa(jd_ut, &ephe_path);
+        assert!((lahiri - 23.853).abs() < 0.01, "Lahiri={lahiri}");
+
+        let fagan_bradley = Zodiac::Sidereal(Ayanamsa::FaganBradley).ayanamsa(jd_ut, &ephe_path);
+        assert!(
+            (fagan_bradley - 24.74).abs() < 0.01,
+            "Fagan-Bradley={fagan_bradley}"
+        );
+
+        // 岁差随时间增加，每年约50角秒
+        let lahiri_next = Zodiac::Sidereal(Ayanamsa::Lahiri).ayanamsa(jd_ut + 365.25, &ephe_path);
+        assert!((lahiri_next - lahiri - 50.3 / 3600.0).abs() < 1.0 / 3600.0);
+    }
+}
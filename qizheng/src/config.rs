@@ -3,6 +3,12 @@ use crate::{
     planet::name::PlanetName::{self, *},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "swagger")]
+use utoipa::ToSchema;
+
 pub struct PlanetConfig {
     pub name: PlanetName,
 
@@ -63,6 +69,18 @@ impl PlanetConfig {
     }
 }
 
+/// 月交点的计算方法，用于计都、罗喉，及北交点、南交点
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "swagger", derive(ToSchema))]
+pub enum NodeType {
+    /// 平均交点
+    #[default]
+    Mean,
+    /// 真交点（密切交点）
+    True,
+}
+
 /// 二十八宿距星配置
 pub struct DistanceStarConfig {
     pub lunar_mansions: LunarMansionsName,
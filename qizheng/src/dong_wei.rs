@@ -275,7 +275,7 @@ pub(crate) fn calc_dong_wei(
 #[cfg(test)]
 mod tests {
     use crate::{
-        DistanceStarConfig, Error, PlanetConfig, PlanetName,
+        DistanceStarConfig, Error, NodeType, PlanetConfig, PlanetName,
         dong_wei::{calc_date_from_dong_wei_long, calc_dong_wei},
         lunar_mansions::calc_distance_star_long,
         planet::calc_planets,
@@ -302,6 +302,7 @@ mod tests {
             native_date.jd_ut1,
             &distance_star_long,
             &planets_config,
+            NodeType::Mean,
             &ephe_path,
         )
         .unwrap();
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use crate::{
-    DistanceStarConfig, Error, Planet, PlanetConfig, PlanetName,
+    DistanceStarConfig, Error, NodeType, Planet, PlanetConfig, PlanetName,
     dong_wei::{DongWei, calc_dong_wei},
     house::{ASCHouse, House, HouseName},
     lunar_mansions::{DistanceStarLong, calc_distance_star_long, calc_xiu_degree},
@@ -69,6 +69,7 @@ impl Horoscope {
         geo: GeoPosition,
         planets_config: &[PlanetConfig],
         distance_star_config: &[DistanceStarConfig],
+        node_type: NodeType,
         ephe_path: &str,
     ) -> Result<Self, Error> {
         if process_date.jd_utc < native_date.jd_utc {
@@ -106,6 +107,7 @@ impl Horoscope {
             native_date.jd_ut1,
             &distance_star_long,
             &planets_config,
+            node_type,
             ephe_path,
         )?;
 
@@ -113,6 +115,7 @@ impl Horoscope {
             process_date.jd_ut1,
             &distance_star_long,
             &planets_config,
+            node_type,
             ephe_path,
         )?;
 
@@ -280,6 +283,7 @@ mod tests {
             geo,
             &planets_config,
             &distance_star_config,
+            NodeType::Mean,
             &ephe_path,
         )
         .unwrap();
@@ -303,6 +307,7 @@ mod tests {
             geo,
             &planets_config,
             &distance_star_config,
+            NodeType::Mean,
             &ephe_path,
         );
 
@@ -326,6 +331,7 @@ mod tests {
             geo,
             &planets_config,
             &distance_star_config,
+            NodeType::Mean,
             &ephe_path,
         );
 
@@ -350,6 +356,7 @@ mod tests {
             geo,
             &planets_config,
             &distance_star_config,
+            NodeType::Mean,
             "invalid/path", // 无效路径
         );
 
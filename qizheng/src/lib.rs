@@ -1,4 +1,4 @@
-pub use config::{DistanceStarConfig, PlanetConfig};
+pub use config::{DistanceStarConfig, NodeType, PlanetConfig};
 pub use dong_wei::DongWei;
 pub use error::Error;
 pub use horo::Horoscope;
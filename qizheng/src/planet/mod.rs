@@ -7,7 +7,7 @@ pub(crate) mod planet_speed_state;
 use crate::{
     DistanceStarLong, Error,
     PlanetName::{self, *},
-    config::PlanetConfig,
+    config::{NodeType, PlanetConfig},
     lunar_mansions::{LunarMansionsName, calc_xiu_degree},
 };
 use horo_date_time::HoroDateTime;
@@ -96,6 +96,7 @@ pub(crate) fn calc_planets(
     jd_ut1: f64,
     distance_star_long: &[DistanceStarLong],
     planets_config: &[PlanetConfig],
+    node_type: NodeType,
     ephe_path: &str,
 ) -> Result<Vec<Planet>, Error> {
     swe_set_ephe_path(ephe_path);
@@ -124,7 +125,10 @@ pub(crate) fn calc_planets(
             PlanetName::木 => Body::SeJupiter,
             PlanetName::土 => Body::SeSaturn,
             PlanetName::孛 => Body::SeMeanApog,
-            _ => Body::SeMeanNode,
+            _ => match node_type {
+                NodeType::Mean => Body::SeMeanNode,
+                NodeType::True => Body::SeTrueNode,
+            },
         };
 
         let (star_long, speed) = if planet_name == PlanetName::气 {
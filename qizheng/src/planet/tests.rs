@@ -4,7 +4,7 @@ use swe::{Body, Flag, swe_calc_ut, swe_close, swe_degnorm, swe_set_ephe_path};
 use crate::{
     DistanceStarLong,
     LunarMansionsName::*,
-    config::{DistanceStarConfig, PlanetConfig},
+    config::{DistanceStarConfig, NodeType, PlanetConfig},
     lunar_mansions::calc_distance_star_long,
 };
 
@@ -242,8 +242,13 @@ fn test_calc_planets_long() {
     let planets_config = PlanetConfig::default_all_configs();
 
     // 5. 调用 calc_planets 函数
-    let planets_result =
-        super::calc_planets(jd_ut1, &distance_star_long, &planets_config, &ephe_path);
+    let planets_result = super::calc_planets(
+        jd_ut1,
+        &distance_star_long,
+        &planets_config,
+        NodeType::Mean,
+        &ephe_path,
+    );
 
     // 6. 验证结果
     assert!(planets_result.is_ok(), "calc_planets should return Ok");
@@ -316,6 +321,58 @@ fn test_calc_planets_long() {
     );
 }
 
+// 测试以真交点计算计都、罗喉
+#[test]
+fn test_calc_planets_true_node() {
+    dotenvy::dotenv().ok();
+    let ephe_path = std::env::var("EPHE_PATH")
+        .expect("没设置 EPHE_PATH 环境变量，可在.env文件中设置或export EPHE_PATH=...");
+
+    let dt = HoroDateTime::new(2025, 7, 13, 12, 0, 0, 8.0).unwrap();
+    let jd_ut1 = dt.jd_ut1;
+
+    let distance_star_configs = DistanceStarConfig::default_all_configs();
+    let distance_star_long =
+        calc_distance_star_long(jd_ut1, &distance_star_configs, &ephe_path).unwrap();
+    let planets_config = PlanetConfig::default_all_configs();
+
+    let planets = super::calc_planets(
+        jd_ut1,
+        &distance_star_long,
+        &planets_config,
+        NodeType::True,
+        &ephe_path,
+    )
+    .unwrap();
+    assert_eq!(planets.len(), 11);
+
+    swe_set_ephe_path(&ephe_path);
+    let xx = swe_calc_ut(jd_ut1, Body::SeTrueNode, &[Flag::SeflgSpeed]).unwrap();
+    swe_close();
+
+    let ketu = planets.iter().find(|p| p.name == 计).unwrap();
+    let rahu = planets.iter().find(|p| p.name == 罗).unwrap();
+    assert_eq!(ketu.long, xx[0]);
+    assert_eq!(ketu.speed, xx[3]);
+    assert_eq!(rahu.long, swe_degnorm(xx[0] + 180.0));
+
+    // 其它行星与平均交点时相同
+    let mean_planets = super::calc_planets(
+        jd_ut1,
+        &distance_star_long,
+        &planets_config,
+        NodeType::Mean,
+        &ephe_path,
+    )
+    .unwrap();
+    for p in planets.iter().filter(|p| p.name != 计 && p.name != 罗) {
+        let mean_p = mean_planets.iter().find(|m| m.name == p.name).unwrap();
+        assert_eq!(p.long, mean_p.long, "{:?}", p.name);
+    }
+    let mean_ketu = mean_planets.iter().find(|p| p.name == 计).unwrap();
+    assert_ne!(ketu.long, mean_ketu.long);
+}
+
 // 测试行星的宿度
 #[test]
 fn test_calc_planets_xiu_degree() {
@@ -454,8 +511,13 @@ fn test_calc_planets_xiu_degree() {
     let planets_config = PlanetConfig::default_all_configs();
 
     // 5. 调用 calc_planets 函数
-    let planets_result =
-        super::calc_planets(jd_ut1, &distance_star_long, &planets_config, &ephe_path);
+    let planets_result = super::calc_planets(
+        jd_ut1,
+        &distance_star_long,
+        &planets_config,
+        NodeType::Mean,
+        &ephe_path,
+    );
 
     // 6. 验证结果
     assert!(planets_result.is_ok(), "calc_planets should return Ok");
@@ -573,8 +635,13 @@ fn test_calc_planets_speed_state() {
     let planets_config = PlanetConfig::default_all_configs();
 
     // 5. 调用 calc_planets 函数
-    let planets_result =
-        super::calc_planets(jd_ut1, &distance_star_long, &planets_config, &ephe_path);
+    let planets_result = super::calc_planets(
+        jd_ut1,
+        &distance_star_long,
+        &planets_config,
+        NodeType::Mean,
+        &ephe_path,
+    );
 
     // 6. 验证结果
     assert!(planets_result.is_ok(), "calc_planets should return Ok");